@@ -0,0 +1,172 @@
+//! Derive macros for [dotavious](https://docs.rs/dotavious), enabled by its `derive`
+//! feature and used through `dotavious::derive`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Ident, LitStr, Member};
+
+/// Implements `dotavious::derive::DotNode` from the `#[dot(...)]` attributes of the
+/// fields of a struct. See the documentation of the trait for the attributes.
+#[proc_macro_derive(DotNode, attributes(dot))]
+pub fn derive_dot_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+enum Mapping {
+    Id,
+    Attribute(LitStr),
+    Setter(Ident),
+    EdgeTo(Vec<(Ident, Expr)>),
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "DotNode can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut id = None;
+    let mut attributes = Vec::new();
+    let mut edges = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        for mapping in field_mappings(field)? {
+            match mapping {
+                Mapping::Id if id.is_some() => {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "Only one field can be the id",
+                    ))
+                }
+                Mapping::Id => id = Some(member.clone()),
+                Mapping::Attribute(name) => attributes.push(quote! {
+                    if let Some(value) = ::dotavious::derive::AttributeValue::attribute_value(
+                        ::std::clone::Clone::clone(&self.#member),
+                    ) {
+                        ::dotavious::attributes::NodeAttributes::add_attribute(
+                            &mut builder,
+                            #name,
+                            value,
+                        );
+                    }
+                }),
+                Mapping::Setter(setter) => attributes.push(quote! {
+                    ::dotavious::attributes::NodeAttributes::#setter(
+                        &mut builder,
+                        ::std::clone::Clone::clone(&self.#member),
+                    );
+                }),
+                Mapping::EdgeTo(edge_attributes) => {
+                    let setters = edge_attributes.iter().map(|(setter, _)| setter);
+                    let values = edge_attributes.iter().map(|(_, value)| value);
+                    edges.push(quote! {
+                        for target in ::dotavious::derive::EdgeTargets::edge_targets(
+                            &self.#member,
+                        ) {
+                            #[allow(unused_mut)]
+                            let mut edge = ::dotavious::EdgeBuilder::new(
+                                ::dotavious::derive::DotNode::node_id(self),
+                                target,
+                            );
+                            #(
+                                ::dotavious::attributes::EdgeAttributes::#setters(
+                                    &mut edge,
+                                    ::std::convert::Into::into(#values),
+                                );
+                            )*
+                            edges.push(edge);
+                        }
+                    });
+                }
+            }
+        }
+    }
+    let id = match id {
+        Some(id) => id,
+        None => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "DotNode requires a field marked #[dot(id)]",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::dotavious::derive::DotNode for #name #type_generics
+        #where_clause
+        {
+            fn node_id(&self) -> ::std::string::String {
+                ::std::string::ToString::to_string(&self.#id)
+            }
+
+            fn node_builder<'dot>(&self) -> ::dotavious::NodeBuilder<'dot> {
+                #[allow(unused_mut)]
+                let mut builder = ::dotavious::NodeBuilder::new(
+                    ::dotavious::derive::DotNode::node_id(self),
+                );
+                #(#attributes)*
+                builder
+            }
+
+            fn edge_builders<'dot>(
+                &self,
+            ) -> ::std::vec::Vec<::dotavious::EdgeBuilder<'dot>> {
+                let mut edges = ::std::vec::Vec::new();
+                #(#edges)*
+                edges
+            }
+        }
+    })
+}
+
+fn field_mappings(field: &syn::Field) -> syn::Result<Vec<Mapping>> {
+    let mut mappings = Vec::new();
+    for attribute in field.attrs.iter().filter(|a| a.path().is_ident("dot")) {
+        attribute.parse_nested_meta(|meta| {
+            let name = match meta.path.get_ident() {
+                Some(name) => name.clone(),
+                None => return Err(meta.error("Expected an attribute or setter name")),
+            };
+            if name == "id" {
+                mappings.push(Mapping::Id);
+            } else if name == "attr" {
+                mappings.push(Mapping::Attribute(meta.value()?.parse()?));
+            } else if name == "edge_to" {
+                let mut edge_attributes = Vec::new();
+                if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    meta.parse_nested_meta(|edge_meta| {
+                        let key = match edge_meta.path.get_ident() {
+                            Some(key) => key.clone(),
+                            None => {
+                                return Err(edge_meta.error("Expected a setter name"))
+                            }
+                        };
+                        edge_attributes.push((key, edge_meta.value()?.parse()?));
+                        Ok(())
+                    })?;
+                }
+                mappings.push(Mapping::EdgeTo(edge_attributes));
+            } else {
+                mappings.push(Mapping::Setter(name));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(mappings)
+}
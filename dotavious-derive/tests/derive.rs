@@ -0,0 +1,129 @@
+use dotavious::attributes::{Color, EdgeStyle, Shape};
+use dotavious::derive::DotNode;
+use dotavious::validation::ErrorCode;
+use dotavious::{Dot, GraphBuilder};
+
+#[derive(DotNode)]
+struct Service {
+    #[dot(id)]
+    name: &'static str,
+    #[dot(label)]
+    title: String,
+    #[dot(shape)]
+    shape: Shape,
+    #[dot(attr = "fillcolor")]
+    fill: Option<Color<'static>>,
+    #[dot(attr = "penwidth")]
+    pen_width: f32,
+    #[dot(edge_to)]
+    calls: Vec<&'static str>,
+    #[dot(edge_to(style = EdgeStyle::Dashed, label = "reads", pen_width = 2.0))]
+    reads: Option<String>,
+}
+
+#[derive(DotNode)]
+struct Task(#[dot(id)] u32, #[dot(edge_to)] Vec<u32>);
+
+#[derive(DotNode)]
+struct Host {
+    #[dot(id)]
+    name: String,
+    #[dot(edge_to)]
+    gateway: Option<String>,
+}
+
+impl Host {
+    // Inherent methods take precedence over the trait's in method call syntax
+    fn node_id(&self) -> String {
+        format!("host-{}", self.name)
+    }
+}
+
+#[test]
+fn derived_node_and_edges() {
+    let service = Service {
+        name: "api",
+        title: "Public API".to_string(),
+        shape: Shape::Box,
+        fill: Some(Color::Named("lightblue")),
+        pen_width: 2.0,
+        calls: vec!["auth", "billing"],
+        reads: Some("users".to_string()),
+    };
+
+    let mut builder = GraphBuilder::new_directed();
+    builder.add_node(service.node().unwrap());
+    for edge in service.edges().unwrap() {
+        builder.add_edge(edge);
+    }
+    let dot = Dot {
+        graph: builder.build().unwrap(),
+    };
+
+    assert_eq!(
+        dot.to_string(),
+        r#"digraph {
+    api [label="Public API", shape=box, fillcolor="lightblue", penwidth=2];
+    api -> auth;
+    api -> billing;
+    api -> users [style=dashed, label="reads", penwidth=2];
+}
+"#
+    );
+}
+
+#[test]
+fn derived_node_skips_absent_attributes() {
+    let service = Service {
+        name: "api",
+        title: "API".to_string(),
+        shape: Shape::Ellipse,
+        fill: None,
+        pen_width: 1.0,
+        calls: Vec::new(),
+        reads: None,
+    };
+
+    let node = service.node().unwrap();
+    assert_eq!(None, node.fill_color());
+    assert!(service.edges().unwrap().is_empty());
+}
+
+#[test]
+fn derived_tuple_struct() {
+    let task = Task(1, vec![2, 3]);
+
+    assert_eq!("1", task.node_id());
+    let edges = task.edges().unwrap();
+    let targets: Vec<&str> = edges.iter().map(|e| e.target.as_str()).collect();
+    assert_eq!(vec!["2", "3"], targets);
+}
+
+#[test]
+fn derived_node_validates_attributes() {
+    let service = Service {
+        name: "api",
+        title: "API".to_string(),
+        shape: Shape::Box,
+        fill: None,
+        pen_width: -1.0,
+        calls: Vec::new(),
+        reads: None,
+    };
+
+    let errors = service.node().unwrap_err();
+    assert_eq!("penwidth", errors[0].field);
+    assert_eq!(ErrorCode::OutOfRange, errors[0].code);
+}
+
+#[test]
+fn derived_ids_ignore_inherent_node_id() {
+    let host = Host {
+        name: "web".to_string(),
+        gateway: Some("gw".to_string()),
+    };
+
+    assert_eq!("host-web", host.node_id());
+    assert_eq!("web", host.node().unwrap().id.as_str());
+    assert_eq!("web", host.edges().unwrap()[0].source);
+}
@@ -0,0 +1,441 @@
+//! Graph algorithms over the nodes and edges of a graph, including nodes which only
+//! exist implicitly through edges and those within subgraphs.
+//!
+//! Nodes are identified by id and results are ordered by the first appearance of nodes.
+//! Edges of undirected graphs are followed in both directions, except for cycle
+//! detection and topological sorting which follow edges in the direction they are
+//! written.
+//!
+//! ```rust
+//! use dotavious::algo;
+//! use dotavious::attributes::{Color, EdgeStyle};
+//! use dotavious::{Edge, GraphBuilder};
+//!
+//! let mut graph = GraphBuilder::new_directed()
+//!     .add_edge(Edge::new("a", "b"))
+//!     .add_edge(Edge::new("b", "a"))
+//!     .build()
+//!     .unwrap();
+//!
+//! for cycle in algo::cycles(&graph) {
+//!     graph.highlight_path(&cycle, EdgeStyle::Bold, Color::Named("red"));
+//! }
+//! ```
+
+use crate::attributes::{AttributeName, AttributeText, Color, EdgeStyle};
+use crate::dot::{Graph, Node};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Returned when topologically sorting a graph with a cycle.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CycleError {
+    /// A cycle of the graph, starting and ending with the same node.
+    pub cycle: Vec<String>,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Graph contains the cycle {}", self.cycle.join(" -> "))
+    }
+}
+
+impl Error for CycleError {}
+
+/// Cycles of the graph, each starting and ending with the same node.
+///
+/// One cycle is returned for each edge closing a cycle during a depth first search,
+/// so every node on a cycle is part of at least one of them, but not every elementary
+/// cycle is listed. Loops are returned as a node followed by itself.
+pub fn cycles(graph: &Graph) -> Vec<Vec<String>> {
+    #[derive(Copy, Clone, PartialEq)]
+    enum State {
+        Unvisited,
+        OnStack,
+        Done,
+    }
+
+    let adjacency = Adjacency::new(graph, true);
+    let mut states = vec![State::Unvisited; adjacency.ids.len()];
+    let mut cycles = Vec::new();
+    for start in 0..adjacency.ids.len() {
+        if states[start] != State::Unvisited {
+            continue;
+        }
+        // Nodes being visited with the position of their next successor to visit.
+        let mut stack = vec![(start, 0)];
+        states[start] = State::OnStack;
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            let successor = match adjacency.successors[node].get(*next) {
+                Some(successor) => *successor,
+                None => {
+                    states[node] = State::Done;
+                    stack.pop();
+                    continue;
+                }
+            };
+            *next += 1;
+            match states[successor] {
+                State::Unvisited => {
+                    states[successor] = State::OnStack;
+                    stack.push((successor, 0));
+                }
+                State::OnStack => {
+                    let from = stack.iter().position(|(n, _)| *n == successor).unwrap();
+                    let mut cycle: Vec<usize> =
+                        stack[from..].iter().map(|(n, _)| *n).collect();
+                    cycle.push(successor);
+                    cycles.push(adjacency.names(cycle));
+                }
+                State::Done => {}
+            }
+        }
+    }
+    cycles
+}
+
+/// Whether the graph contains a cycle, including loops.
+pub fn is_cyclic(graph: &Graph) -> bool {
+    !cycles(graph).is_empty()
+}
+
+/// Nodes ordered so that every node comes before the nodes its edges lead to.
+/// Nodes which are not ordered by edges keep the order they first appear in.
+pub fn topological_sort(graph: &Graph) -> Result<Vec<String>, CycleError> {
+    let adjacency = Adjacency::new(graph, true);
+    let mut in_degrees: Vec<usize> =
+        adjacency.predecessors.iter().map(Vec::len).collect();
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..adjacency.ids.len())
+        .filter(|node| in_degrees[*node] == 0)
+        .map(Reverse)
+        .collect();
+    let mut sorted = Vec::with_capacity(adjacency.ids.len());
+    while let Some(Reverse(node)) = ready.pop() {
+        sorted.push(node);
+        for successor in &adjacency.successors[node] {
+            in_degrees[*successor] -= 1;
+            if in_degrees[*successor] == 0 {
+                ready.push(Reverse(*successor));
+            }
+        }
+    }
+    if sorted.len() < adjacency.ids.len() {
+        let cycle = cycles(graph).into_iter().next().unwrap_or_default();
+        return Err(CycleError { cycle });
+    }
+    Ok(adjacency.names(sorted))
+}
+
+/// Groups of nodes which can all reach each other, in topological order of the groups.
+/// Every node is part of exactly one component.
+pub fn strongly_connected_components(graph: &Graph) -> Vec<Vec<String>> {
+    let adjacency = Adjacency::new(graph, false);
+    let count = adjacency.ids.len();
+
+    // Nodes by increasing finishing time of a depth first search.
+    let mut finished = Vec::with_capacity(count);
+    let mut visited = vec![false; count];
+    for start in 0..count {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut stack = vec![(start, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            match adjacency.successors[node].get(*next) {
+                Some(successor) => {
+                    *next += 1;
+                    if !visited[*successor] {
+                        visited[*successor] = true;
+                        stack.push((*successor, 0));
+                    }
+                }
+                None => {
+                    finished.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    let mut assigned = vec![false; count];
+    let mut components = Vec::new();
+    for start in finished.into_iter().rev() {
+        if assigned[start] {
+            continue;
+        }
+        assigned[start] = true;
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for predecessor in &adjacency.predecessors[node] {
+                if !assigned[*predecessor] {
+                    assigned[*predecessor] = true;
+                    component.push(*predecessor);
+                    stack.push(*predecessor);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(adjacency.names(component));
+    }
+    components
+}
+
+/// Groups of nodes connected by edges regardless of their direction.
+pub fn weakly_connected_components(graph: &Graph) -> Vec<Vec<String>> {
+    let adjacency = Adjacency::new(graph, false);
+    let mut assigned = vec![false; adjacency.ids.len()];
+    let mut components = Vec::new();
+    for start in 0..adjacency.ids.len() {
+        if assigned[start] {
+            continue;
+        }
+        assigned[start] = true;
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            let neighbors = adjacency.successors[node]
+                .iter()
+                .chain(&adjacency.predecessors[node]);
+            for neighbor in neighbors {
+                if !assigned[*neighbor] {
+                    assigned[*neighbor] = true;
+                    component.push(*neighbor);
+                    stack.push(*neighbor);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(adjacency.names(component));
+    }
+    components
+}
+
+/// A path with the fewest edges from `source` to `target`, including both,
+/// or `None` if `target` can not be reached.
+pub fn shortest_path(graph: &Graph, source: &str, target: &str) -> Option<Vec<String>> {
+    let adjacency = Adjacency::new(graph, false);
+    let source = adjacency.position(source)?;
+    let target = adjacency.position(target)?;
+
+    let mut parents: Vec<Option<usize>> = vec![None; adjacency.ids.len()];
+    let mut queue = VecDeque::from(vec![source]);
+    let mut visited = vec![false; adjacency.ids.len()];
+    visited[source] = true;
+    while let Some(node) = queue.pop_front() {
+        if node == target {
+            let mut path = vec![target];
+            let mut current = target;
+            while let Some(parent) = parents[current] {
+                path.push(parent);
+                current = parent;
+            }
+            path.reverse();
+            return Some(adjacency.names(path));
+        }
+        for successor in &adjacency.successors[node] {
+            if !visited[*successor] {
+                visited[*successor] = true;
+                parents[*successor] = Some(node);
+                queue.push_back(*successor);
+            }
+        }
+    }
+    None
+}
+
+/// Nodes which can reach the node, closest first. Empty if the node does not exist.
+pub fn ancestors(graph: &Graph, id: &str) -> Vec<String> {
+    let adjacency = Adjacency::new(graph, false);
+    adjacency.reachable(id, &adjacency.predecessors)
+}
+
+/// Nodes reachable from the node, closest first. Empty if the node does not exist.
+pub fn descendants(graph: &Graph, id: &str) -> Vec<String> {
+    let adjacency = Adjacency::new(graph, false);
+    adjacency.reachable(id, &adjacency.successors)
+}
+
+impl<'a> Graph<'a> {
+    /// Removes every edge from a node to another node which can also be reached through
+    /// a different path, keeping the reachability of the graph with as few edges as
+    /// possible. Parallel edges are left in place, see [`Graph::collapse_parallel_edges`].
+    ///
+    /// Returns the number of edges removed, or an error without modifying the graph if it
+    /// contains a cycle.
+    pub fn transitive_reduction(&mut self) -> Result<usize, CycleError> {
+        if let Some(cycle) = cycles(self).into_iter().next() {
+            return Err(CycleError { cycle });
+        }
+        let adjacency = Adjacency::new(self, true);
+        let descendants: Vec<Vec<usize>> = (0..adjacency.ids.len())
+            .map(|node| adjacency.reachable_positions(node, &adjacency.successors))
+            .collect();
+        let mut redundant = HashSet::new();
+        for (node, successors) in adjacency.successors.iter().enumerate() {
+            for target in successors {
+                let implied = successors.iter().any(|other| {
+                    other != target && descendants[*other].contains(target)
+                });
+                if implied {
+                    redundant.insert((
+                        adjacency.ids[node].to_string(),
+                        adjacency.ids[*target].to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(self.remove_edge_where(|edge| {
+            redundant.contains(&(edge.source.clone(), edge.target.clone()))
+        }))
+    }
+
+    /// Sets the style and color of every edge between consecutive nodes of the path,
+    /// such as one returned by [`shortest_path`] or [`cycles`], and the color of its
+    /// nodes.
+    pub fn highlight_path<S: AsRef<str>>(
+        &mut self,
+        path: &[S],
+        style: EdgeStyle,
+        color: Color<'a>,
+    ) -> &mut Self {
+        let is_directed = self.is_directed;
+        let pairs: Vec<(&str, &str)> = path
+            .windows(2)
+            .map(|pair| (pair[0].as_ref(), pair[1].as_ref()))
+            .collect();
+        let style = AttributeText::from(style);
+        let color_text = AttributeText::from(color);
+        self.for_each_scope_mut(&mut |scope| {
+            for edge in scope.edges.iter_mut() {
+                let highlighted = pairs.iter().any(|(source, target)| {
+                    (edge.source == *source && edge.target == *target)
+                        || (!is_directed
+                            && edge.source == *target
+                            && edge.target == *source)
+                });
+                if highlighted {
+                    edge.attributes.insert(AttributeName::Style, style.clone());
+                    edge.attributes
+                        .insert(AttributeName::Color, color_text.clone());
+                }
+            }
+        });
+        self.highlight_nodes(path, color)
+    }
+
+    /// Sets the color of the nodes, such as those of a component.
+    /// Nodes only existing through edges are declared in the root graph.
+    pub fn highlight_nodes<S: AsRef<str>>(
+        &mut self,
+        ids: &[S],
+        color: Color<'a>,
+    ) -> &mut Self {
+        let color = AttributeText::from(color);
+        for id in ids {
+            let id = id.as_ref();
+            let statements = match self.index().node(id) {
+                Some(node) => node.statements.len(),
+                None => continue,
+            };
+            if statements == 0 {
+                let mut node = Node::new(id.to_string());
+                node.attributes.insert(AttributeName::Color, color.clone());
+                self.nodes.push(node);
+                continue;
+            }
+            // The last statement of a node takes precedence over earlier ones.
+            let mut seen = 0;
+            self.for_each_scope_mut(&mut |scope| {
+                for node in scope.nodes.iter_mut().filter(|n| n.id.as_str() == id) {
+                    seen += 1;
+                    if seen == statements {
+                        node.attributes.insert(AttributeName::Color, color.clone());
+                    }
+                }
+            });
+        }
+        self
+    }
+}
+
+/// Adjacency of nodes by their position in the order nodes first appear.
+struct Adjacency<'g> {
+    ids: Vec<&'g str>,
+    positions: HashMap<&'g str, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<'g> Adjacency<'g> {
+    /// Edges of undirected graphs are followed in both directions unless `as_written`.
+    fn new(graph: &'g Graph, as_written: bool) -> Self {
+        let index = graph.index();
+        let ids: Vec<&'g str> = index.nodes().map(|node| node.id).collect();
+        let positions: HashMap<&'g str, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut adjacency = Self {
+            successors: vec![Vec::new(); ids.len()],
+            predecessors: vec![Vec::new(); ids.len()],
+            ids,
+            positions,
+        };
+        for edge in index.edges() {
+            let source = adjacency.positions[edge.edge.source.as_str()];
+            let target = adjacency.positions[edge.edge.target.as_str()];
+            adjacency.add(source, target);
+            if !graph.is_directed && !as_written {
+                adjacency.add(target, source);
+            }
+        }
+        adjacency
+    }
+
+    fn add(&mut self, source: usize, target: usize) {
+        if !self.successors[source].contains(&target) {
+            self.successors[source].push(target);
+            self.predecessors[target].push(source);
+        }
+    }
+
+    fn position(&self, id: &str) -> Option<usize> {
+        self.positions.get(id).copied()
+    }
+
+    fn names(&self, nodes: Vec<usize>) -> Vec<String> {
+        nodes
+            .into_iter()
+            .map(|node| self.ids[node].to_string())
+            .collect()
+    }
+
+    fn reachable(&self, id: &str, edges: &[Vec<usize>]) -> Vec<String> {
+        match self.position(id) {
+            Some(start) => self.names(self.reachable_positions(start, edges)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Nodes reachable from `start` following `edges`, closest first.
+    fn reachable_positions(&self, start: usize, edges: &[Vec<usize>]) -> Vec<usize> {
+        let mut visited = vec![false; self.ids.len()];
+        visited[start] = true;
+        let mut reached = Vec::new();
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(node) = queue.pop_front() {
+            for next in &edges[node] {
+                if !visited[*next] {
+                    visited[*next] = true;
+                    reached.push(*next);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        reached
+    }
+}
@@ -1,6 +1,9 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ArrowType {
     Normal,
     Dot,
@@ -48,3 +51,32 @@ impl<'a> DotString<'a> for ArrowType {
         }
     }
 }
+
+impl FromStr for ArrowType {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(ArrowType::Normal),
+            "dot" => Ok(ArrowType::Dot),
+            "odot" => Ok(ArrowType::Odot),
+            "none" => Ok(ArrowType::None),
+            "empty" => Ok(ArrowType::Empty),
+            "diamond" => Ok(ArrowType::Diamond),
+            "ediamond" => Ok(ArrowType::Ediamond),
+            "box" => Ok(ArrowType::Box),
+            "open" => Ok(ArrowType::Open),
+            "vee" => Ok(ArrowType::Vee),
+            "inv" => Ok(ArrowType::Inv),
+            "invdot" => Ok(ArrowType::Invdot),
+            "invodot" => Ok(ArrowType::Invodot),
+            "tee" => Ok(ArrowType::Tee),
+            "invempty" => Ok(ArrowType::Invempty),
+            "odiamond" => Ok(ArrowType::Odiamond),
+            "crow" => Ok(ArrowType::Crow),
+            "obox" => Ok(ArrowType::Obox),
+            "halfopen" => Ok(ArrowType::Halfopen),
+            _ => Err(ParseAttributeError::new("ArrowType", s)),
+        }
+    }
+}
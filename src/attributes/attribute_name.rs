@@ -0,0 +1,638 @@
+use crate::attributes::AttributeSpec;
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// The name of a Graphviz attribute, used as the key of attribute maps.
+///
+/// Converting from a string resolves known attribute names to their variant,
+/// any other name becomes [`AttributeName::Other`]. Names are compared by their DOT
+/// representation so `AttributeName::Other("shape".to_string())` equals
+/// `AttributeName::Shape`.
+#[derive(Clone, Debug)]
+pub enum AttributeName {
+    Background,
+    Area,
+    ArrowHead,
+    ArrowSize,
+    ArrowTail,
+    BoundingBox,
+    Beautify,
+    BackgroundColor,
+    Center,
+    Charset,
+    Class,
+    Cluster,
+    ClusterRank,
+    Color,
+    ColorScheme,
+    Comment,
+    Compound,
+    Concentrate,
+    Constraint,
+    Damping,
+    Decorate,
+    DefaultDist,
+    Dim,
+    Dimen,
+    Dir,
+    DirEdgeConstraints,
+    Distortion,
+    Dpi,
+    EdgeHref,
+    EdgeTarget,
+    EdgeTooltip,
+    EdgeUrl,
+    Epsilon,
+    Esep,
+    FillColor,
+    FixedSize,
+    FontColor,
+    FontName,
+    FontNames,
+    FontPath,
+    FontSize,
+    ForceLabels,
+    GradientAngle,
+    Group,
+    HeadLp,
+    HeadClip,
+    HeadHref,
+    HeadLabel,
+    HeadPort,
+    HeadTarget,
+    HeadTooltip,
+    HeadUrl,
+    Height,
+    Href,
+    Id,
+    Image,
+    ImagePath,
+    ImagePos,
+    ImageScale,
+    InputScale,
+    K,
+    Label,
+    LabelScheme,
+    LabelAngle,
+    LabelDistance,
+    LabelFloat,
+    LabelFontColor,
+    LabelFontName,
+    LabelFontSize,
+    LabelHref,
+    LabelJust,
+    LabelLoc,
+    LabelTarget,
+    LabelTooltip,
+    LabelUrl,
+    Landscape,
+    Layer,
+    LayerListSep,
+    Layers,
+    LayerSelect,
+    LayerSep,
+    Layout,
+    Len,
+    Levels,
+    LevelsGap,
+    LHead,
+    LHeight,
+    LineLength,
+    Lp,
+    LTail,
+    LWidth,
+    Margin,
+    MaxIter,
+    McLimit,
+    MinDist,
+    MinLen,
+    Mode,
+    Model,
+    Mosek,
+    NewRank,
+    NodeSep,
+    NoJustify,
+    Normalize,
+    NoTranslate,
+    NsLimit,
+    NsLimit1,
+    OneBlock,
+    Ordering,
+    Orientation,
+    OutputOrder,
+    Overlap,
+    OverlapScaling,
+    OverlapShrink,
+    Pack,
+    PackMode,
+    Pad,
+    Page,
+    PageDir,
+    PenColor,
+    PenWidth,
+    Peripheries,
+    Pin,
+    Pos,
+    QuadTree,
+    Quantum,
+    Rank,
+    RankDir,
+    RankSep,
+    Ratio,
+    Rects,
+    Regular,
+    ReMinCross,
+    RepulsiveForce,
+    Resolution,
+    Root,
+    Rotate,
+    Rotation,
+    SameHead,
+    SameTail,
+    SamplePoints,
+    Scale,
+    SearchSize,
+    Sep,
+    Shape,
+    ShapeFile,
+    ShowBoxes,
+    Sides,
+    Size,
+    Skew,
+    Smoothing,
+    SortV,
+    Splines,
+    Start,
+    Style,
+    Stylesheet,
+    TailLp,
+    TailClip,
+    TailHref,
+    TailLabel,
+    TailPort,
+    TailTarget,
+    TailTooltip,
+    TailUrl,
+    Target,
+    TBBalance,
+    Tooltip,
+    TrueColor,
+    Url,
+    Vertices,
+    ViewPort,
+    VoroMargin,
+    Weight,
+    Width,
+    XDotVersion,
+    XLabel,
+    Xlp,
+
+    /// An attribute unknown to dotavious, such as a custom attribute used by other tools.
+    Other(String),
+}
+
+impl AttributeName {
+    /// The name of the attribute as it appears in DOT.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AttributeName::Background => "_background",
+            AttributeName::Area => "area",
+            AttributeName::ArrowHead => "arrowhead",
+            AttributeName::ArrowSize => "arrowsize",
+            AttributeName::ArrowTail => "arrowtail",
+            AttributeName::BoundingBox => "bb",
+            AttributeName::Beautify => "beautify",
+            AttributeName::BackgroundColor => "bgcolor",
+            AttributeName::Center => "center",
+            AttributeName::Charset => "charset",
+            AttributeName::Class => "class",
+            AttributeName::Cluster => "cluster",
+            AttributeName::ClusterRank => "clusterrank",
+            AttributeName::Color => "color",
+            AttributeName::ColorScheme => "colorscheme",
+            AttributeName::Comment => "comment",
+            AttributeName::Compound => "compound",
+            AttributeName::Concentrate => "concentrate",
+            AttributeName::Constraint => "constraint",
+            AttributeName::Damping => "Damping",
+            AttributeName::Decorate => "decorate",
+            AttributeName::DefaultDist => "defaultdist",
+            AttributeName::Dim => "dim",
+            AttributeName::Dimen => "dimen",
+            AttributeName::Dir => "dir",
+            AttributeName::DirEdgeConstraints => "diredgeconstraints",
+            AttributeName::Distortion => "distortion",
+            AttributeName::Dpi => "dpi",
+            AttributeName::EdgeHref => "edgehref",
+            AttributeName::EdgeTarget => "edgetarget",
+            AttributeName::EdgeTooltip => "edgetooltip",
+            AttributeName::EdgeUrl => "edgeURL",
+            AttributeName::Epsilon => "epsilon",
+            AttributeName::Esep => "esep",
+            AttributeName::FillColor => "fillcolor",
+            AttributeName::FixedSize => "fixedsize",
+            AttributeName::FontColor => "fontcolor",
+            AttributeName::FontName => "fontname",
+            AttributeName::FontNames => "fontnames",
+            AttributeName::FontPath => "fontpath",
+            AttributeName::FontSize => "fontsize",
+            AttributeName::ForceLabels => "forcelabels",
+            AttributeName::GradientAngle => "gradientangle",
+            AttributeName::Group => "group",
+            AttributeName::HeadLp => "head_lp",
+            AttributeName::HeadClip => "headclip",
+            AttributeName::HeadHref => "headhref",
+            AttributeName::HeadLabel => "headlabel",
+            AttributeName::HeadPort => "headport",
+            AttributeName::HeadTarget => "headtarget",
+            AttributeName::HeadTooltip => "headtooltip",
+            AttributeName::HeadUrl => "headURL",
+            AttributeName::Height => "height",
+            AttributeName::Href => "href",
+            AttributeName::Id => "id",
+            AttributeName::Image => "image",
+            AttributeName::ImagePath => "imagepath",
+            AttributeName::ImagePos => "imagepos",
+            AttributeName::ImageScale => "imagescale",
+            AttributeName::InputScale => "inputscale",
+            AttributeName::K => "K",
+            AttributeName::Label => "label",
+            AttributeName::LabelScheme => "label_scheme",
+            AttributeName::LabelAngle => "labelangle",
+            AttributeName::LabelDistance => "labeldistance",
+            AttributeName::LabelFloat => "labelfloat",
+            AttributeName::LabelFontColor => "labelfontcolor",
+            AttributeName::LabelFontName => "labelfontname",
+            AttributeName::LabelFontSize => "labelfontsize",
+            AttributeName::LabelHref => "labelhref",
+            AttributeName::LabelJust => "labeljust",
+            AttributeName::LabelLoc => "labelloc",
+            AttributeName::LabelTarget => "labeltarget",
+            AttributeName::LabelTooltip => "labeltooltip",
+            AttributeName::LabelUrl => "labelURL",
+            AttributeName::Landscape => "landscape",
+            AttributeName::Layer => "layer",
+            AttributeName::LayerListSep => "layerlistsep",
+            AttributeName::Layers => "layers",
+            AttributeName::LayerSelect => "layerselect",
+            AttributeName::LayerSep => "layersep",
+            AttributeName::Layout => "layout",
+            AttributeName::Len => "len",
+            AttributeName::Levels => "levels",
+            AttributeName::LevelsGap => "levelsgap",
+            AttributeName::LHead => "lhead",
+            AttributeName::LHeight => "lheight",
+            AttributeName::LineLength => "linelength",
+            AttributeName::Lp => "lp",
+            AttributeName::LTail => "ltail",
+            AttributeName::LWidth => "lwidth",
+            AttributeName::Margin => "margin",
+            AttributeName::MaxIter => "maxiter",
+            AttributeName::McLimit => "mclimit",
+            AttributeName::MinDist => "mindist",
+            AttributeName::MinLen => "minlen",
+            AttributeName::Mode => "mode",
+            AttributeName::Model => "model",
+            AttributeName::Mosek => "mosek",
+            AttributeName::NewRank => "newrank",
+            AttributeName::NodeSep => "nodesep",
+            AttributeName::NoJustify => "nojustify",
+            AttributeName::Normalize => "normalize",
+            AttributeName::NoTranslate => "notranslate",
+            AttributeName::NsLimit => "nslimit",
+            AttributeName::NsLimit1 => "nslimit1",
+            AttributeName::OneBlock => "oneblock",
+            AttributeName::Ordering => "ordering",
+            AttributeName::Orientation => "orientation",
+            AttributeName::OutputOrder => "outputorder",
+            AttributeName::Overlap => "overlap",
+            AttributeName::OverlapScaling => "overlap_scaling",
+            AttributeName::OverlapShrink => "overlap_shrink",
+            AttributeName::Pack => "pack",
+            AttributeName::PackMode => "packmode",
+            AttributeName::Pad => "pad",
+            AttributeName::Page => "page",
+            AttributeName::PageDir => "pagedir",
+            AttributeName::PenColor => "pencolor",
+            AttributeName::PenWidth => "penwidth",
+            AttributeName::Peripheries => "peripheries",
+            AttributeName::Pin => "pin",
+            AttributeName::Pos => "pos",
+            AttributeName::QuadTree => "quadtree",
+            AttributeName::Quantum => "quantum",
+            AttributeName::Rank => "rank",
+            AttributeName::RankDir => "rankdir",
+            AttributeName::RankSep => "ranksep",
+            AttributeName::Ratio => "ratio",
+            AttributeName::Rects => "rects",
+            AttributeName::Regular => "regular",
+            AttributeName::ReMinCross => "remincross",
+            AttributeName::RepulsiveForce => "repulsiveforce",
+            AttributeName::Resolution => "resolution",
+            AttributeName::Root => "root",
+            AttributeName::Rotate => "rotate",
+            AttributeName::Rotation => "rotation",
+            AttributeName::SameHead => "samehead",
+            AttributeName::SameTail => "sametail",
+            AttributeName::SamplePoints => "samplepoints",
+            AttributeName::Scale => "scale",
+            AttributeName::SearchSize => "searchsize",
+            AttributeName::Sep => "sep",
+            AttributeName::Shape => "shape",
+            AttributeName::ShapeFile => "shapefile",
+            AttributeName::ShowBoxes => "showboxes",
+            AttributeName::Sides => "sides",
+            AttributeName::Size => "size",
+            AttributeName::Skew => "skew",
+            AttributeName::Smoothing => "smoothing",
+            AttributeName::SortV => "sortv",
+            AttributeName::Splines => "splines",
+            AttributeName::Start => "start",
+            AttributeName::Style => "style",
+            AttributeName::Stylesheet => "stylesheet",
+            AttributeName::TailLp => "tail_lp",
+            AttributeName::TailClip => "tailclip",
+            AttributeName::TailHref => "tailhref",
+            AttributeName::TailLabel => "taillabel",
+            AttributeName::TailPort => "tailport",
+            AttributeName::TailTarget => "tailtarget",
+            AttributeName::TailTooltip => "tailtooltip",
+            AttributeName::TailUrl => "tailURL",
+            AttributeName::Target => "target",
+            AttributeName::TBBalance => "TBbalance",
+            AttributeName::Tooltip => "tooltip",
+            AttributeName::TrueColor => "truecolor",
+            AttributeName::Url => "URL",
+            AttributeName::Vertices => "vertices",
+            AttributeName::ViewPort => "viewport",
+            AttributeName::VoroMargin => "voro_margin",
+            AttributeName::Weight => "weight",
+            AttributeName::Width => "width",
+            AttributeName::XDotVersion => "xdotversion",
+            AttributeName::XLabel => "xlabel",
+            AttributeName::Xlp => "xlp",
+            AttributeName::Other(name) => name,
+        }
+    }
+
+    /// The registry entry for the attribute, or `None` if the attribute is unknown.
+    pub fn spec(&self) -> Option<&'static AttributeSpec> {
+        AttributeSpec::find(self.as_str())
+    }
+
+    fn known(name: &str) -> Option<AttributeName> {
+        let attribute_name = match name {
+            "_background" => AttributeName::Background,
+            "area" => AttributeName::Area,
+            "arrowhead" => AttributeName::ArrowHead,
+            "arrowsize" => AttributeName::ArrowSize,
+            "arrowtail" => AttributeName::ArrowTail,
+            "bb" => AttributeName::BoundingBox,
+            "beautify" => AttributeName::Beautify,
+            "bgcolor" => AttributeName::BackgroundColor,
+            "center" => AttributeName::Center,
+            "charset" => AttributeName::Charset,
+            "class" => AttributeName::Class,
+            "cluster" => AttributeName::Cluster,
+            "clusterrank" => AttributeName::ClusterRank,
+            "color" => AttributeName::Color,
+            "colorscheme" => AttributeName::ColorScheme,
+            "comment" => AttributeName::Comment,
+            "compound" => AttributeName::Compound,
+            "concentrate" => AttributeName::Concentrate,
+            "constraint" => AttributeName::Constraint,
+            "Damping" => AttributeName::Damping,
+            "decorate" => AttributeName::Decorate,
+            "defaultdist" => AttributeName::DefaultDist,
+            "dim" => AttributeName::Dim,
+            "dimen" => AttributeName::Dimen,
+            "dir" => AttributeName::Dir,
+            "diredgeconstraints" => AttributeName::DirEdgeConstraints,
+            "distortion" => AttributeName::Distortion,
+            "dpi" => AttributeName::Dpi,
+            "edgehref" => AttributeName::EdgeHref,
+            "edgetarget" => AttributeName::EdgeTarget,
+            "edgetooltip" => AttributeName::EdgeTooltip,
+            "edgeURL" => AttributeName::EdgeUrl,
+            "epsilon" => AttributeName::Epsilon,
+            "esep" => AttributeName::Esep,
+            "fillcolor" => AttributeName::FillColor,
+            "fixedsize" => AttributeName::FixedSize,
+            "fontcolor" => AttributeName::FontColor,
+            "fontname" => AttributeName::FontName,
+            "fontnames" => AttributeName::FontNames,
+            "fontpath" => AttributeName::FontPath,
+            "fontsize" => AttributeName::FontSize,
+            "forcelabels" => AttributeName::ForceLabels,
+            "gradientangle" => AttributeName::GradientAngle,
+            "group" => AttributeName::Group,
+            "head_lp" => AttributeName::HeadLp,
+            "headclip" => AttributeName::HeadClip,
+            "headhref" => AttributeName::HeadHref,
+            "headlabel" => AttributeName::HeadLabel,
+            "headport" => AttributeName::HeadPort,
+            "headtarget" => AttributeName::HeadTarget,
+            "headtooltip" => AttributeName::HeadTooltip,
+            "headURL" => AttributeName::HeadUrl,
+            "height" => AttributeName::Height,
+            "href" => AttributeName::Href,
+            "id" => AttributeName::Id,
+            "image" => AttributeName::Image,
+            "imagepath" => AttributeName::ImagePath,
+            "imagepos" => AttributeName::ImagePos,
+            "imagescale" => AttributeName::ImageScale,
+            "inputscale" => AttributeName::InputScale,
+            "K" => AttributeName::K,
+            "label" => AttributeName::Label,
+            "label_scheme" => AttributeName::LabelScheme,
+            "labelangle" => AttributeName::LabelAngle,
+            "labeldistance" => AttributeName::LabelDistance,
+            "labelfloat" => AttributeName::LabelFloat,
+            "labelfontcolor" => AttributeName::LabelFontColor,
+            "labelfontname" => AttributeName::LabelFontName,
+            "labelfontsize" => AttributeName::LabelFontSize,
+            "labelhref" => AttributeName::LabelHref,
+            "labeljust" => AttributeName::LabelJust,
+            "labelloc" => AttributeName::LabelLoc,
+            "labeltarget" => AttributeName::LabelTarget,
+            "labeltooltip" => AttributeName::LabelTooltip,
+            "labelURL" => AttributeName::LabelUrl,
+            "landscape" => AttributeName::Landscape,
+            "layer" => AttributeName::Layer,
+            "layerlistsep" => AttributeName::LayerListSep,
+            "layers" => AttributeName::Layers,
+            "layerselect" => AttributeName::LayerSelect,
+            "layersep" => AttributeName::LayerSep,
+            "layout" => AttributeName::Layout,
+            "len" => AttributeName::Len,
+            "levels" => AttributeName::Levels,
+            "levelsgap" => AttributeName::LevelsGap,
+            "lhead" => AttributeName::LHead,
+            "lheight" => AttributeName::LHeight,
+            "linelength" => AttributeName::LineLength,
+            "lp" => AttributeName::Lp,
+            "ltail" => AttributeName::LTail,
+            "lwidth" => AttributeName::LWidth,
+            "margin" => AttributeName::Margin,
+            "maxiter" => AttributeName::MaxIter,
+            "mclimit" => AttributeName::McLimit,
+            "mindist" => AttributeName::MinDist,
+            "minlen" => AttributeName::MinLen,
+            "mode" => AttributeName::Mode,
+            "model" => AttributeName::Model,
+            "mosek" => AttributeName::Mosek,
+            "newrank" => AttributeName::NewRank,
+            "nodesep" => AttributeName::NodeSep,
+            "nojustify" => AttributeName::NoJustify,
+            "normalize" => AttributeName::Normalize,
+            "notranslate" => AttributeName::NoTranslate,
+            "nslimit" => AttributeName::NsLimit,
+            "nslimit1" => AttributeName::NsLimit1,
+            "oneblock" => AttributeName::OneBlock,
+            "ordering" => AttributeName::Ordering,
+            "orientation" => AttributeName::Orientation,
+            "outputorder" => AttributeName::OutputOrder,
+            "overlap" => AttributeName::Overlap,
+            "overlap_scaling" => AttributeName::OverlapScaling,
+            "overlap_shrink" => AttributeName::OverlapShrink,
+            "pack" => AttributeName::Pack,
+            "packmode" => AttributeName::PackMode,
+            "pad" => AttributeName::Pad,
+            "page" => AttributeName::Page,
+            "pagedir" => AttributeName::PageDir,
+            "pencolor" => AttributeName::PenColor,
+            "penwidth" => AttributeName::PenWidth,
+            "peripheries" => AttributeName::Peripheries,
+            "pin" => AttributeName::Pin,
+            "pos" => AttributeName::Pos,
+            "quadtree" => AttributeName::QuadTree,
+            "quantum" => AttributeName::Quantum,
+            "rank" => AttributeName::Rank,
+            "rankdir" => AttributeName::RankDir,
+            "ranksep" => AttributeName::RankSep,
+            "ratio" => AttributeName::Ratio,
+            "rects" => AttributeName::Rects,
+            "regular" => AttributeName::Regular,
+            "remincross" => AttributeName::ReMinCross,
+            "repulsiveforce" => AttributeName::RepulsiveForce,
+            "resolution" => AttributeName::Resolution,
+            "root" => AttributeName::Root,
+            "rotate" => AttributeName::Rotate,
+            "rotation" => AttributeName::Rotation,
+            "samehead" => AttributeName::SameHead,
+            "sametail" => AttributeName::SameTail,
+            "samplepoints" => AttributeName::SamplePoints,
+            "scale" => AttributeName::Scale,
+            "searchsize" => AttributeName::SearchSize,
+            "sep" => AttributeName::Sep,
+            "shape" => AttributeName::Shape,
+            "shapefile" => AttributeName::ShapeFile,
+            "showboxes" => AttributeName::ShowBoxes,
+            "sides" => AttributeName::Sides,
+            "size" => AttributeName::Size,
+            "skew" => AttributeName::Skew,
+            "smoothing" => AttributeName::Smoothing,
+            "sortv" => AttributeName::SortV,
+            "splines" => AttributeName::Splines,
+            "start" => AttributeName::Start,
+            "style" => AttributeName::Style,
+            "stylesheet" => AttributeName::Stylesheet,
+            "tail_lp" => AttributeName::TailLp,
+            "tailclip" => AttributeName::TailClip,
+            "tailhref" => AttributeName::TailHref,
+            "taillabel" => AttributeName::TailLabel,
+            "tailport" => AttributeName::TailPort,
+            "tailtarget" => AttributeName::TailTarget,
+            "tailtooltip" => AttributeName::TailTooltip,
+            "tailURL" => AttributeName::TailUrl,
+            "target" => AttributeName::Target,
+            "TBbalance" => AttributeName::TBBalance,
+            "tooltip" => AttributeName::Tooltip,
+            "truecolor" => AttributeName::TrueColor,
+            "URL" => AttributeName::Url,
+            "vertices" => AttributeName::Vertices,
+            "viewport" => AttributeName::ViewPort,
+            "voro_margin" => AttributeName::VoroMargin,
+            "weight" => AttributeName::Weight,
+            "width" => AttributeName::Width,
+            "xdotversion" => AttributeName::XDotVersion,
+            "xlabel" => AttributeName::XLabel,
+            "xlp" => AttributeName::Xlp,
+            _ => return None,
+        };
+        Some(attribute_name)
+    }
+}
+
+impl From<&str> for AttributeName {
+    fn from(name: &str) -> Self {
+        AttributeName::known(name)
+            .unwrap_or_else(|| AttributeName::Other(name.to_owned()))
+    }
+}
+
+impl From<String> for AttributeName {
+    fn from(name: String) -> Self {
+        AttributeName::known(&name).unwrap_or(AttributeName::Other(name))
+    }
+}
+
+impl PartialEq for AttributeName {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for AttributeName {}
+
+impl Hash for AttributeName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+// Allows attribute maps to be queried by string, e.g. `attributes.get("shape")`
+impl Borrow<str> for AttributeName {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for AttributeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::{AttributeName, AttributeSpec};
+
+    #[test]
+    fn known_names_resolve_to_variant() {
+        assert!(matches!(AttributeName::from("shape"), AttributeName::Shape));
+        assert!(matches!(AttributeName::from("URL"), AttributeName::Url));
+        assert!(matches!(
+            AttributeName::from("fillcolour"),
+            AttributeName::Other(_)
+        ));
+    }
+
+    #[test]
+    fn other_equals_known_variant() {
+        assert_eq!(
+            AttributeName::Shape,
+            AttributeName::Other("shape".to_string())
+        );
+    }
+
+    #[test]
+    fn every_spec_has_a_variant() {
+        for spec in AttributeSpec::all() {
+            let name = AttributeName::from(spec.name);
+            assert!(!matches!(name, AttributeName::Other(_)), "{}", spec.name);
+            assert_eq!(spec.name, name.as_str());
+        }
+    }
+}
@@ -1,6 +1,9 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ClusterMode {
     Local,
     Global,
@@ -16,3 +19,16 @@ impl<'a> DotString<'a> for ClusterMode {
         }
     }
 }
+
+impl FromStr for ClusterMode {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(ClusterMode::Local),
+            "global" => Ok(ClusterMode::Global),
+            "none" => Ok(ClusterMode::None),
+            _ => Err(ParseAttributeError::new("ClusterMode", s)),
+        }
+    }
+}
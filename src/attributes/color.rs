@@ -1,5 +1,7 @@
+use crate::attributes::{AttributeText, ParseAttributeError};
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::convert::TryFrom;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Color<'a> {
@@ -48,7 +50,67 @@ impl<'a> DotString<'a> for Color<'a> {
     }
 }
 
+// Named colors borrow from the parsed text which is why colors implement
+// TryFrom<&str> rather than FromStr.
+impl<'a> TryFrom<&'a str> for Color<'a> {
+    type Error = ParseAttributeError;
+
+    /// Parses `#rrggbb`, `#rrggbbaa`, `H,S,V` or `H S V`.
+    /// Anything else is taken as a color name.
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let error = || ParseAttributeError::new("Color", s);
+        if let Some(hex) = s.strip_prefix('#') {
+            let byte = |i: usize| {
+                hex.get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+                    .ok_or_else(error)
+            };
+            return match hex.len() {
+                6 => Ok(Color::RGB {
+                    red: byte(0)?,
+                    green: byte(2)?,
+                    blue: byte(4)?,
+                }),
+                8 => Ok(Color::RGBA {
+                    red: byte(0)?,
+                    green: byte(2)?,
+                    blue: byte(4)?,
+                    alpha: byte(6)?,
+                }),
+                _ => Err(error()),
+            };
+        }
+
+        let components: Vec<&str> = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|c| !c.is_empty())
+            .collect();
+        if components.len() == 3 && components.iter().all(|c| c.parse::<f32>().is_ok()) {
+            let component = |i: usize| components[i].parse::<f32>().map_err(|_| error());
+            return Ok(Color::HSV {
+                hue: component(0)?,
+                saturation: component(1)?,
+                value: component(2)?,
+            });
+        }
+
+        if s.is_empty() {
+            return Err(error());
+        }
+        Ok(Color::Named(s))
+    }
+}
+
+impl<'a> TryFrom<&'a AttributeText<'_>> for Color<'a> {
+    type Error = ParseAttributeError;
+
+    fn try_from(text: &'a AttributeText<'_>) -> Result<Self, Self::Error> {
+        Color::try_from(text.as_str())
+    }
+}
+
 // The sum of the optional weightings must sum to at most 1.
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct WeightedColor<'a> {
     pub color: Color<'a>,
 
@@ -67,6 +129,29 @@ impl<'a> DotString<'a> for WeightedColor<'a> {
     }
 }
 
+impl<'a> TryFrom<&'a str> for WeightedColor<'a> {
+    type Error = ParseAttributeError;
+
+    /// Parses a color optionally followed by `;` and its weight.
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        match s.split_once(';') {
+            Some((color, weight)) => Ok(WeightedColor {
+                color: Color::try_from(color)?,
+                weight: Some(
+                    weight
+                        .parse()
+                        .map_err(|_| ParseAttributeError::new("WeightedColor", s))?,
+                ),
+            }),
+            None => Ok(WeightedColor {
+                color: Color::try_from(s)?,
+                weight: None,
+            }),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct ColorList<'a> {
     pub colors: Vec<WeightedColor<'a>>,
 }
@@ -83,7 +168,7 @@ impl<'a> DotString<'a> for ColorList<'a> {
         }
         dot_string.push_str(&first.unwrap().dot_string());
         for weighted_color in iter {
-            dot_string.push_str(":");
+            dot_string.push(':');
             dot_string.push_str(&weighted_color.dot_string())
         }
 
@@ -91,6 +176,26 @@ impl<'a> DotString<'a> for ColorList<'a> {
     }
 }
 
+impl<'a> TryFrom<&'a str> for ColorList<'a> {
+    type Error = ParseAttributeError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let colors = s
+            .split(':')
+            .map(WeightedColor::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ColorList { colors })
+    }
+}
+
+impl<'a> TryFrom<&'a AttributeText<'_>> for ColorList<'a> {
+    type Error = ParseAttributeError;
+
+    fn try_from(text: &'a AttributeText<'_>) -> Result<Self, Self::Error> {
+        ColorList::try_from(text.as_str())
+    }
+}
+
 /// Convert an element like `(Color, Option<f32>)` into a WeightedColor
 pub trait IntoWeightedColor<'a> {
     fn into_weighted_color(self) -> WeightedColor<'a>;
@@ -110,6 +215,7 @@ impl<'a> IntoWeightedColor<'a> for &(Color<'a>, Option<f32>) {
 mod test {
     use crate::attributes::{Color, ColorList, WeightedColor};
     use crate::DotString;
+    use std::convert::TryFrom;
 
     #[test]
     fn colorlist_dot_string() {
@@ -162,4 +268,41 @@ mod test {
         };
         assert_eq!("0.051 0.718 0.627", color.dot_string());
     }
+
+    #[test]
+    fn color_try_from_str() {
+        assert_eq!(
+            Ok(Color::RGB {
+                red: 160,
+                green: 82,
+                blue: 45
+            }),
+            Color::try_from("#a0522d")
+        );
+        assert_eq!(
+            Ok(Color::RGBA {
+                red: 160,
+                green: 82,
+                blue: 45,
+                alpha: 10
+            }),
+            Color::try_from("#a0522d0a")
+        );
+        assert_eq!(
+            Ok(Color::HSV {
+                hue: 0.051,
+                saturation: 0.718,
+                value: 0.627
+            }),
+            Color::try_from("0.051 0.718 0.627")
+        );
+        assert_eq!(Ok(Color::Named("red")), Color::try_from("red"));
+        assert!(Color::try_from("#a0522").is_err());
+    }
+
+    #[test]
+    fn colorlist_try_from_str() {
+        let color_list = ColorList::try_from("yellow;0.3:blue").unwrap();
+        assert_eq!("yellow;0.3:blue", color_list.dot_string());
+    }
 }
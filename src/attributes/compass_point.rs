@@ -1,5 +1,7 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 // TODO: not sure we need this enum but should support setting nodeport either via
 // headport / tailport attributes e.g. a -> b [tailport=se]
@@ -38,3 +40,23 @@ impl<'a> DotString<'a> for CompassPoint {
         }
     }
 }
+
+impl FromStr for CompassPoint {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(CompassPoint::N),
+            "ne" => Ok(CompassPoint::NE),
+            "e" => Ok(CompassPoint::E),
+            "se" => Ok(CompassPoint::SE),
+            "s" => Ok(CompassPoint::S),
+            "sw" => Ok(CompassPoint::SW),
+            "w" => Ok(CompassPoint::W),
+            "nw" => Ok(CompassPoint::NW),
+            "c" => Ok(CompassPoint::C),
+            "_" => Ok(CompassPoint::None),
+            _ => Err(ParseAttributeError::new("CompassPoint", s)),
+        }
+    }
+}
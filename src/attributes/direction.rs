@@ -1,6 +1,9 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Direction {
     Forward,
     Back,
@@ -18,3 +21,17 @@ impl<'a> DotString<'a> for Direction {
         }
     }
 }
+
+impl FromStr for Direction {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "forward" => Ok(Direction::Forward),
+            "back" => Ok(Direction::Back),
+            "both" => Ok(Direction::Both),
+            "none" => Ok(Direction::None),
+            _ => Err(ParseAttributeError::new("Direction", s)),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::attributes::{AttributeContext, AttributeText};
+use crate::dot::DotString;
+use std::borrow::Cow;
+
+/// A part of an escString.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum EscStringPart {
+    /// Literal text. Backslashes are escaped when rendered.
+    Text(String),
+
+    /// `\G`, replaced by the name of the graph.
+    GraphName,
+
+    /// `\N`, replaced by the name of the node. Only valid for nodes.
+    NodeName,
+
+    /// `\E`, replaced by the name of the edge. Only valid for edges.
+    EdgeName,
+
+    /// `\T`, replaced by the name of the tail node. Only valid for edges.
+    TailName,
+
+    /// `\H`, replaced by the name of the head node. Only valid for edges.
+    HeadName,
+
+    /// `\L`, replaced by the object's label. Not valid within the label itself.
+    Label,
+
+    /// `\n`, ends a centered line.
+    LineCenter,
+
+    /// `\l`, ends a left-justified line.
+    LineLeft,
+
+    /// `\r`, ends a right-justified line.
+    LineRight,
+}
+
+/// Builder for Graphviz escStrings, supporting substitutions of graph, node and edge names
+/// as well as line justification.
+///
+/// <https://graphviz.org/docs/attr-types/escString/>
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct EscString {
+    pub parts: Vec<EscStringPart>,
+}
+
+impl EscString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends literal text.
+    pub fn text<S: Into<String>>(&mut self, text: S) -> &mut Self {
+        self.push(EscStringPart::Text(text.into()))
+    }
+
+    pub fn graph_name(&mut self) -> &mut Self {
+        self.push(EscStringPart::GraphName)
+    }
+
+    pub fn node_name(&mut self) -> &mut Self {
+        self.push(EscStringPart::NodeName)
+    }
+
+    pub fn edge_name(&mut self) -> &mut Self {
+        self.push(EscStringPart::EdgeName)
+    }
+
+    pub fn tail_name(&mut self) -> &mut Self {
+        self.push(EscStringPart::TailName)
+    }
+
+    pub fn head_name(&mut self) -> &mut Self {
+        self.push(EscStringPart::HeadName)
+    }
+
+    pub fn label(&mut self) -> &mut Self {
+        self.push(EscStringPart::Label)
+    }
+
+    pub fn line_center(&mut self) -> &mut Self {
+        self.push(EscStringPart::LineCenter)
+    }
+
+    pub fn line_left(&mut self) -> &mut Self {
+        self.push(EscStringPart::LineLeft)
+    }
+
+    pub fn line_right(&mut self) -> &mut Self {
+        self.push(EscStringPart::LineRight)
+    }
+
+    pub fn build(&self) -> EscString {
+        self.clone()
+    }
+
+    fn push(&mut self, part: EscStringPart) -> &mut Self {
+        self.parts.push(part);
+        self
+    }
+}
+
+impl<'a> DotString<'a> for EscString {
+    fn dot_string(&self) -> Cow<'a, str> {
+        let mut s = String::new();
+        for part in &self.parts {
+            match part {
+                EscStringPart::Text(text) => s.push_str(&text.replace('\\', "\\\\")),
+                EscStringPart::GraphName => s.push_str("\\G"),
+                EscStringPart::NodeName => s.push_str("\\N"),
+                EscStringPart::EdgeName => s.push_str("\\E"),
+                EscStringPart::TailName => s.push_str("\\T"),
+                EscStringPart::HeadName => s.push_str("\\H"),
+                EscStringPart::Label => s.push_str("\\L"),
+                EscStringPart::LineCenter => s.push_str("\\n"),
+                EscStringPart::LineLeft => s.push_str("\\l"),
+                EscStringPart::LineRight => s.push_str("\\r"),
+            }
+        }
+        s.into()
+    }
+}
+
+impl<'a> From<&mut EscString> for AttributeText<'a> {
+    fn from(esc_string: &mut EscString) -> Self {
+        AttributeText::escaped(esc_string.dot_string())
+    }
+}
+
+impl<'a> From<EscString> for AttributeText<'a> {
+    fn from(esc_string: EscString) -> Self {
+        AttributeText::escaped(esc_string.dot_string())
+    }
+}
+
+/// Checks that the substitutions within an escString are valid for the contexts and the
+/// attribute it is used for.
+pub(crate) fn validate_substitutions(
+    value: &str,
+    contexts: &[AttributeContext],
+    is_label: bool,
+) -> Result<(), String> {
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+        let escape = match chars.next() {
+            Some(escape) => escape,
+            None => break,
+        };
+        let valid = match escape {
+            'N' => contexts.contains(&AttributeContext::Node),
+            'E' | 'T' | 'H' => contexts.contains(&AttributeContext::Edge),
+            'L' => !is_label,
+            _ => true,
+        };
+        if !valid {
+            return Err(match escape {
+                'N' => String::from("\\N is only valid for nodes"),
+                'L' => String::from("\\L can not be used within the label itself"),
+                _ => format!("\\{} is only valid for edges", escape),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::esc_string::validate_substitutions;
+    use crate::attributes::{AttributeContext, EscString};
+    use crate::DotString;
+
+    #[test]
+    fn dot_string() {
+        assert_eq!(
+            "\\T -> \\H\\lC:\\\\temp\\r",
+            EscString::new()
+                .tail_name()
+                .text(" -> ")
+                .head_name()
+                .line_left()
+                .text("C:\\temp")
+                .line_right()
+                .dot_string()
+        );
+    }
+
+    #[test]
+    fn substitutions() {
+        let node = [AttributeContext::Node];
+        let edge = [AttributeContext::Edge];
+        assert!(validate_substitutions("\\N in \\G\\l", &node, true).is_ok());
+        assert!(validate_substitutions("\\T\\\\N", &edge, true).is_ok());
+        assert_eq!(
+            Err(String::from("\\T is only valid for edges")),
+            validate_substitutions("\\T", &node, false)
+        );
+        assert_eq!(
+            Err(String::from("\\N is only valid for nodes")),
+            validate_substitutions("\\N", &edge, false)
+        );
+        assert!(validate_substitutions("\\L", &node, true).is_err());
+        assert!(validate_substitutions("\\L", &node, false).is_ok());
+    }
+}
@@ -1,10 +1,13 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// Controls how an image is positioned within its containing node.
 /// Only has an effect when the image is smaller than the containing node.
 ///
 /// The default is to be centered both horizontally and vertically.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ImagePosition {
     TopLeft,
     TopCentered,
@@ -33,6 +36,25 @@ impl<'a> DotString<'a> for ImagePosition {
     }
 }
 
+impl FromStr for ImagePosition {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tl" => Ok(ImagePosition::TopLeft),
+            "tc" => Ok(ImagePosition::TopCentered),
+            "tr" => Ok(ImagePosition::TopRight),
+            "ml" => Ok(ImagePosition::MiddleLeft),
+            "mc" => Ok(ImagePosition::MiddleCentered),
+            "mr" => Ok(ImagePosition::MiddleRight),
+            "bl" => Ok(ImagePosition::BottomLeft),
+            "bc" => Ok(ImagePosition::BottomCentered),
+            "br" => Ok(ImagePosition::BottomRight),
+            _ => Err(ParseAttributeError::new("ImagePosition", s)),
+        }
+    }
+}
+
 /// Controls how an image fills its containing node.
 /// In general, the image is given its natural size, (cf. dpi), and the node size is made large
 /// enough to contain its image, its label, its margin, and its peripheries.
@@ -51,6 +73,7 @@ impl<'a> DotString<'a> for ImagePosition {
 /// When imagescale=both, both the height and the width are scaled separately to fill the node.
 ///
 /// As with the case of expansion, if imagescale=true, width and height are scaled uniformly.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ImageScale {
     Width,
     Height,
@@ -66,3 +89,16 @@ impl<'a> DotString<'a> for ImageScale {
         }
     }
 }
+
+impl FromStr for ImageScale {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "width" => Ok(ImageScale::Width),
+            "height" => Ok(ImageScale::Height),
+            "both" => Ok(ImageScale::Both),
+            _ => Err(ParseAttributeError::new("ImageScale", s)),
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// Justification for cluster labels.
 ///
@@ -9,6 +11,7 @@ use std::borrow::Cow;
 ///
 /// Note that a subgraph inherits attributes from its parent.
 /// Thus, if the root graph sets labeljust=l, the subgraph inherits this value.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum LabelJustification {
     Left,
     Right,
@@ -25,6 +28,19 @@ impl<'a> DotString<'a> for LabelJustification {
     }
 }
 
+impl FromStr for LabelJustification {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "l" => Ok(LabelJustification::Left),
+            "r" => Ok(LabelJustification::Right),
+            "c" => Ok(LabelJustification::Center),
+            _ => Err(ParseAttributeError::new("LabelJustification", s)),
+        }
+    }
+}
+
 /// Vertical placement of labels for nodes, root graphs and clusters.
 ///
 /// For graphs and clusters, only labelloc=t and labelloc=b are allowed,
@@ -42,6 +58,7 @@ impl<'a> DotString<'a> for LabelJustification {
 /// with the bottom of the node, respectively.
 ///
 /// By default, the label is vertically centered.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum LabelLocation {
     Top,
     Center,
@@ -57,3 +74,16 @@ impl<'a> DotString<'a> for LabelLocation {
         }
     }
 }
+
+impl FromStr for LabelLocation {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "t" => Ok(LabelLocation::Top),
+            "c" => Ok(LabelLocation::Center),
+            "b" => Ok(LabelLocation::Bottom),
+            _ => Err(ParseAttributeError::new("LabelLocation", s)),
+        }
+    }
+}
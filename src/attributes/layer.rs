@@ -0,0 +1,316 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Default separator characters used to split the layers attribute and layer ranges.
+pub(crate) const DEFAULT_LAYER_SEP: &str = ":\t ";
+
+/// Default separator characters used to split a layer range into a list of ranges.
+pub(crate) const DEFAULT_LAYER_LIST_SEP: &str = ",";
+
+/// The linearly ordered list of layer names attached to a graph.
+/// The graph is output in separate layers, each containing only the components
+/// belonging to that layer.
+///
+/// The separator is the set of characters splitting names, as given by the graph's
+/// `layersep` attribute. The first character is used when rendering.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Layers {
+    pub names: Vec<String>,
+    pub separator: String,
+}
+
+impl Layers {
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+            separator: DEFAULT_LAYER_SEP.to_string(),
+        }
+    }
+
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Parses a list of layer names split by any of the `separator` characters.
+    pub fn parse_with_separator(
+        s: &str,
+        separator: &str,
+    ) -> Result<Self, ParseAttributeError> {
+        let names: Vec<&str> = split(s, separator).collect();
+        if names.is_empty() {
+            return Err(ParseAttributeError::new("Layers", s));
+        }
+        Ok(Layers::new(names).with_separator(separator))
+    }
+
+    /// Whether the given layer is defined. `all` is always defined and layer indices
+    /// start at 1.
+    pub fn contains(&self, layer: &LayerId) -> bool {
+        match layer {
+            LayerId::All => true,
+            LayerId::Index(index) => *index >= 1 && *index as usize <= self.names.len(),
+            LayerId::Name(name) => self.names.iter().any(|n| n == name),
+        }
+    }
+}
+
+impl<'a> DotString<'a> for Layers {
+    fn dot_string(&self) -> Cow<'a, str> {
+        self.names.join(&first_char(&self.separator, ':')).into()
+    }
+}
+
+impl FromStr for Layers {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Layers::parse_with_separator(s, DEFAULT_LAYER_SEP)
+    }
+}
+
+/// A layer referenced within a layer range.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LayerId {
+    /// Every layer.
+    All,
+
+    /// Layer by its position in the graph's layers, starting at 1.
+    Index(u32),
+
+    /// Layer by its name.
+    Name(String),
+}
+
+impl LayerId {
+    fn parse(s: &str) -> Self {
+        if s == "all" {
+            LayerId::All
+        } else if let Ok(index) = s.parse() {
+            LayerId::Index(index)
+        } else {
+            LayerId::Name(s.to_string())
+        }
+    }
+}
+
+impl<'a> DotString<'a> for LayerId {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            LayerId::All => "all".into(),
+            LayerId::Index(index) => index.to_string().into(),
+            LayerId::Name(name) => name.clone().into(),
+        }
+    }
+}
+
+impl From<&str> for LayerId {
+    fn from(s: &str) -> Self {
+        LayerId::parse(s)
+    }
+}
+
+impl From<u32> for LayerId {
+    fn from(index: u32) -> Self {
+        LayerId::Index(index)
+    }
+}
+
+/// Either a single layer or an inclusive range of layers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LayerInterval {
+    Single(LayerId),
+    Range(LayerId, LayerId),
+}
+
+/// Specifies the layers a node, edge or cluster is present in, or the layers selected
+/// for output.
+///
+/// Separators are the set of characters splitting the range, as given by the graph's
+/// `layersep` and `layerlistsep` attributes. The first character of each is used when
+/// rendering.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LayerRange {
+    pub intervals: Vec<LayerInterval>,
+    pub layer_sep: String,
+    pub layer_list_sep: String,
+}
+
+impl LayerRange {
+    pub fn new(intervals: Vec<LayerInterval>) -> Self {
+        Self {
+            intervals,
+            layer_sep: DEFAULT_LAYER_SEP.to_string(),
+            layer_list_sep: DEFAULT_LAYER_LIST_SEP.to_string(),
+        }
+    }
+
+    /// Present in all layers.
+    pub fn all() -> Self {
+        Self::new(vec![LayerInterval::Single(LayerId::All)])
+    }
+
+    /// Present in a single layer.
+    pub fn layer<L: Into<LayerId>>(layer: L) -> Self {
+        Self::new(vec![LayerInterval::Single(layer.into())])
+    }
+
+    /// Present in all layers from `start` to `end` inclusive.
+    pub fn range<L: Into<LayerId>, M: Into<LayerId>>(start: L, end: M) -> Self {
+        Self::new(vec![LayerInterval::Range(start.into(), end.into())])
+    }
+
+    /// Present in each of the given layers.
+    pub fn list<I, L>(layers: I) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<LayerId>,
+    {
+        Self::new(
+            layers
+                .into_iter()
+                .map(|l| LayerInterval::Single(l.into()))
+                .collect(),
+        )
+    }
+
+    pub fn with_separators<S: Into<String>, T: Into<String>>(
+        mut self,
+        layer_sep: S,
+        layer_list_sep: T,
+    ) -> Self {
+        self.layer_sep = layer_sep.into();
+        self.layer_list_sep = layer_list_sep.into();
+        self
+    }
+
+    /// Parses a layer range, splitting the list of ranges by any of the
+    /// `layer_list_sep` characters and each range by any of the `layer_sep` characters.
+    pub fn parse_with_separators(
+        s: &str,
+        layer_sep: &str,
+        layer_list_sep: &str,
+    ) -> Result<Self, ParseAttributeError> {
+        let intervals = split(s, layer_list_sep)
+            .map(|interval| {
+                let ids: Vec<&str> = split(interval, layer_sep).collect();
+                match ids[..] {
+                    [id] => Ok(LayerInterval::Single(LayerId::parse(id))),
+                    [start, end] => Ok(LayerInterval::Range(
+                        LayerId::parse(start),
+                        LayerId::parse(end),
+                    )),
+                    _ => Err(ParseAttributeError::new("LayerRange", s)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if intervals.is_empty() {
+            return Err(ParseAttributeError::new("LayerRange", s));
+        }
+        Ok(LayerRange::new(intervals).with_separators(layer_sep, layer_list_sep))
+    }
+
+    /// Every layer referenced by this range.
+    pub fn layer_ids(&self) -> impl Iterator<Item = &LayerId> {
+        self.intervals.iter().flat_map(|interval| match interval {
+            LayerInterval::Single(id) => vec![id],
+            LayerInterval::Range(start, end) => vec![start, end],
+        })
+    }
+}
+
+impl<'a> DotString<'a> for LayerRange {
+    fn dot_string(&self) -> Cow<'a, str> {
+        let layer_sep = first_char(&self.layer_sep, ':');
+        self.intervals
+            .iter()
+            .map(|interval| match interval {
+                LayerInterval::Single(id) => id.dot_string().into_owned(),
+                LayerInterval::Range(start, end) => {
+                    format!("{}{}{}", start.dot_string(), layer_sep, end.dot_string())
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&first_char(&self.layer_list_sep, ','))
+            .into()
+    }
+}
+
+impl FromStr for LayerRange {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LayerRange::parse_with_separators(s, DEFAULT_LAYER_SEP, DEFAULT_LAYER_LIST_SEP)
+    }
+}
+
+fn split<'s>(s: &'s str, separators: &'s str) -> impl Iterator<Item = &'s str> {
+    s.split(move |c| separators.contains(c))
+        .filter(|part| !part.is_empty())
+}
+
+fn first_char(separators: &str, default: char) -> String {
+    separators.chars().next().unwrap_or(default).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::{LayerId, LayerInterval, LayerRange, Layers};
+    use crate::DotString;
+
+    #[test]
+    fn layers_dot_string() {
+        assert_eq!("a:b:c", Layers::new(vec!["a", "b", "c"]).dot_string());
+        assert_eq!(
+            "a|b",
+            Layers::new(vec!["a", "b"]).with_separator("|").dot_string()
+        );
+    }
+
+    #[test]
+    fn layers_from_str() {
+        let layers: Layers = "a:b c\td".parse().unwrap();
+        assert_eq!(vec!["a", "b", "c", "d"], layers.names);
+        assert!(Layers::parse_with_separator("a|b:c", "|")
+            .unwrap()
+            .contains(&LayerId::Name("b:c".to_string())));
+        assert!("".parse::<Layers>().is_err());
+    }
+
+    #[test]
+    fn layer_range_dot_string() {
+        assert_eq!("all", LayerRange::all().dot_string());
+        assert_eq!("a", LayerRange::layer("a").dot_string());
+        assert_eq!("a:3", LayerRange::range("a", 3).dot_string());
+        assert_eq!("a,b", LayerRange::list(vec!["a", "b"]).dot_string());
+        assert_eq!(
+            "a|b;c",
+            LayerRange::new(vec![
+                LayerInterval::Range("a".into(), "b".into()),
+                LayerInterval::Single("c".into()),
+            ])
+            .with_separators("|", ";")
+            .dot_string()
+        );
+    }
+
+    #[test]
+    fn layer_range_from_str() {
+        let range: LayerRange = "all,a:2".parse().unwrap();
+        assert_eq!(
+            vec![
+                LayerInterval::Single(LayerId::All),
+                LayerInterval::Range(LayerId::Name("a".to_string()), LayerId::Index(2)),
+            ],
+            range.intervals
+        );
+        assert!("a:b:c".parse::<LayerRange>().is_err());
+        assert!(",".parse::<LayerRange>().is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use crate::attributes::{AttributeSpec, ParseAttributeError};
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// The Graphviz program used to lay out a graph.
+///
+/// Many attributes are only understood by some of the engines and are silently
+/// ignored by the others. See [`LayoutEngine::supports`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LayoutEngine {
+    /// Hierarchical or layered drawings of directed graphs. This is the default engine.
+    Dot,
+    /// Spring model layouts, minimizing a global energy function.
+    Neato,
+    /// Spring model layouts, reducing forces.
+    Fdp,
+    /// Multiscale version of fdp for the layout of large graphs.
+    Sfdp,
+    /// Circular layout.
+    Circo,
+    /// Radial layout.
+    Twopi,
+    /// Layout for clustered graphs, drawing each cluster as a packed array.
+    Osage,
+    /// Draws the graph as a squarified treemap.
+    Patchwork,
+}
+
+impl LayoutEngine {
+    /// Whether `attribute` has any effect when the graph is laid out by this engine.
+    /// Attributes that are not engine specific are supported by every engine.
+    pub fn supports(&self, attribute: &str) -> bool {
+        match attribute_engines(attribute) {
+            Some(engines) => engines.contains(self),
+            None => true,
+        }
+    }
+}
+
+impl<'a> DotString<'a> for LayoutEngine {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            LayoutEngine::Dot => "dot".into(),
+            LayoutEngine::Neato => "neato".into(),
+            LayoutEngine::Fdp => "fdp".into(),
+            LayoutEngine::Sfdp => "sfdp".into(),
+            LayoutEngine::Circo => "circo".into(),
+            LayoutEngine::Twopi => "twopi".into(),
+            LayoutEngine::Osage => "osage".into(),
+            LayoutEngine::Patchwork => "patchwork".into(),
+        }
+    }
+}
+
+impl FromStr for LayoutEngine {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(LayoutEngine::Dot),
+            "neato" => Ok(LayoutEngine::Neato),
+            "fdp" => Ok(LayoutEngine::Fdp),
+            "sfdp" => Ok(LayoutEngine::Sfdp),
+            "circo" => Ok(LayoutEngine::Circo),
+            "twopi" => Ok(LayoutEngine::Twopi),
+            "osage" => Ok(LayoutEngine::Osage),
+            "patchwork" => Ok(LayoutEngine::Patchwork),
+            _ => Err(ParseAttributeError::new("LayoutEngine", s)),
+        }
+    }
+}
+
+/// The layout engines which make use of `attribute`,
+/// or `None` if the attribute is not specific to any engine.
+pub fn attribute_engines(attribute: &str) -> Option<&'static [LayoutEngine]> {
+    AttributeSpec::find(attribute).and_then(|spec| spec.engines)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::LayoutEngine;
+
+    #[test]
+    fn engine_specific_attribute_support() {
+        assert!(LayoutEngine::Neato.supports("overlap"));
+        assert!(!LayoutEngine::Dot.supports("overlap"));
+        assert!(LayoutEngine::Twopi.supports("ranksep"));
+        assert!(!LayoutEngine::Neato.supports("ranksep"));
+    }
+
+    #[test]
+    fn common_attribute_supported_by_all_engines() {
+        assert!(LayoutEngine::Dot.supports("label"));
+        assert!(LayoutEngine::Patchwork.supports("label"));
+    }
+}
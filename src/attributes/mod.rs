@@ -1,56 +1,89 @@
 // TODO: docs
 
 mod arrow_type;
+mod attribute_name;
 mod cluster_mode;
 mod color;
 mod compass_point;
 mod direction;
+mod esc_string;
 mod image;
 mod label;
+mod layer;
+mod layout_engine;
+mod mode;
+mod model;
 mod ordering;
 mod output_mode;
+mod overlap;
 mod pack_mode;
 mod page_direction;
+mod parse_error;
 mod point;
 mod port_position;
+mod quad_type;
 mod rankdir;
 mod ratio;
 mod rectangle;
 mod shape;
+mod smooth_type;
+mod spec;
 mod spline_type;
 mod splines;
+mod start_type;
 mod style;
+mod tb_balance;
 mod viewport;
 
 pub use crate::attributes::arrow_type::ArrowType;
+pub use crate::attributes::attribute_name::AttributeName;
 pub use crate::attributes::cluster_mode::ClusterMode;
 pub use crate::attributes::color::{Color, ColorList, IntoWeightedColor, WeightedColor};
 pub use crate::attributes::compass_point::CompassPoint;
 pub use crate::attributes::direction::Direction;
+pub use crate::attributes::esc_string::{EscString, EscStringPart};
 pub use crate::attributes::image::{ImagePosition, ImageScale};
 pub use crate::attributes::label::{LabelJustification, LabelLocation};
+pub use crate::attributes::layer::{LayerId, LayerInterval, LayerRange, Layers};
+pub(crate) use crate::attributes::layer::{DEFAULT_LAYER_LIST_SEP, DEFAULT_LAYER_SEP};
+pub use crate::attributes::layout_engine::{attribute_engines, LayoutEngine};
+pub use crate::attributes::mode::Mode;
+pub use crate::attributes::model::Model;
 pub use crate::attributes::ordering::Ordering;
 pub use crate::attributes::output_mode::OutputMode;
+pub use crate::attributes::overlap::Overlap;
 pub use crate::attributes::pack_mode::PackMode;
 pub use crate::attributes::page_direction::PageDirection;
+pub use crate::attributes::parse_error::ParseAttributeError;
 pub use crate::attributes::point::Point;
 pub use crate::attributes::port_position::PortPosition;
+pub use crate::attributes::quad_type::QuadType;
 pub use crate::attributes::rankdir::RankDir;
 pub use crate::attributes::ratio::Ratio;
 pub use crate::attributes::rectangle::Rectangle;
 pub use crate::attributes::shape::Shape;
+pub use crate::attributes::smooth_type::SmoothType;
+pub(crate) use crate::attributes::spec::validate_attribute;
+pub use crate::attributes::spec::{AttributeContext, AttributeSpec, ValueType};
 pub use crate::attributes::spline_type::SplineType;
 pub use crate::attributes::splines::Splines;
-pub use crate::attributes::style::{EdgeStyle, GraphStyle, NodeStyle, Styles};
+pub use crate::attributes::start_type::StartType;
+pub use crate::attributes::style::{
+    EdgeStyle, EdgeStyleList, GraphStyle, GraphStyleList, NodeStyle, NodeStyleList,
+    StyleItem, StyleList, Styles,
+};
+pub use crate::attributes::tb_balance::TBBalance;
+pub use crate::attributes::viewport::{FocusType, ViewPort};
 #[doc(hidden)]
 pub use crate::attributes::AttributeText::{AttrStr, EscStr, HtmlStr, QuotedStr};
 use crate::dot::DotString;
-use crate::validation::{ValidationError, ValidationResult};
+use crate::validation::{
+    locate_errors, ElementPath, ErrorCode, ValidationError, ValidationResult,
+};
 use indexmap::map::IndexMap;
 use std::borrow::Cow;
 use std::collections::HashMap;
-use Cow::Borrowed;
-use crate::attributes::viewport::ViewPort;
+use std::convert::TryFrom;
 
 /// The text for a graphviz label on a node or edge.
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -101,6 +134,13 @@ impl<'a> AttributeText<'a> {
         QuotedStr(s.into())
     }
 
+    /// The text without any quotes, delimiters or escaping.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AttrStr(s) | EscStr(s) | HtmlStr(s) | QuotedStr(s) => s,
+        }
+    }
+
     fn escape_char<F>(c: char, mut f: F)
     where
         F: FnMut(char),
@@ -130,9 +170,9 @@ impl<'a> AttributeText<'a> {
     pub fn dot_string(&self) -> String {
         match *self {
             AttrStr(ref s) => format!("{}", s),
-            EscStr(ref s) => format!("\"{}\"", AttributeText::escape_str(&s)),
+            EscStr(ref s) => format!("\"{}\"", AttributeText::escape_str(s)),
             HtmlStr(ref s) => format!("<{}>", s),
-            QuotedStr(ref s) => format!("\"{}\"", escape_double_quotes(&s.to_string())),
+            QuotedStr(ref s) => format!("\"{}\"", escape_double_quotes(s)),
         }
     }
 }
@@ -221,6 +261,36 @@ impl<'a> From<LabelLocation> for AttributeText<'a> {
     }
 }
 
+impl<'a> From<LayerRange> for AttributeText<'a> {
+    fn from(layer_range: LayerRange) -> Self {
+        AttributeText::quoted(layer_range.dot_string())
+    }
+}
+
+impl<'a> From<Layers> for AttributeText<'a> {
+    fn from(layers: Layers) -> Self {
+        AttributeText::quoted(layers.dot_string())
+    }
+}
+
+impl<'a> From<LayoutEngine> for AttributeText<'a> {
+    fn from(layout: LayoutEngine) -> Self {
+        AttributeText::attr(layout.dot_string())
+    }
+}
+
+impl<'a> From<Mode> for AttributeText<'a> {
+    fn from(mode: Mode) -> Self {
+        AttributeText::attr(mode.dot_string())
+    }
+}
+
+impl<'a> From<Model> for AttributeText<'a> {
+    fn from(model: Model) -> Self {
+        AttributeText::attr(model.dot_string())
+    }
+}
+
 impl<'a> From<NodeStyle> for AttributeText<'a> {
     fn from(style: NodeStyle) -> Self {
         AttributeText::attr(style.dot_string())
@@ -239,6 +309,12 @@ impl<'a> From<OutputMode> for AttributeText<'a> {
     }
 }
 
+impl<'a> From<Overlap> for AttributeText<'a> {
+    fn from(overlap: Overlap) -> Self {
+        AttributeText::attr(overlap.dot_string())
+    }
+}
+
 impl<'a> From<PackMode> for AttributeText<'a> {
     fn from(mode: PackMode) -> Self {
         AttributeText::quoted(mode.dot_string())
@@ -263,6 +339,12 @@ impl<'a> From<PortPosition> for AttributeText<'a> {
     }
 }
 
+impl<'a> From<QuadType> for AttributeText<'a> {
+    fn from(quad_type: QuadType) -> Self {
+        AttributeText::attr(quad_type.dot_string())
+    }
+}
+
 impl<'a> From<RankDir> for AttributeText<'a> {
     fn from(rank_dir: RankDir) -> Self {
         AttributeText::attr(rank_dir.dot_string())
@@ -290,6 +372,12 @@ impl<'a> From<Shape> for AttributeText<'a> {
     }
 }
 
+impl<'a> From<SmoothType> for AttributeText<'a> {
+    fn from(smooth_type: SmoothType) -> Self {
+        AttributeText::attr(smooth_type.dot_string())
+    }
+}
+
 impl<'a> From<Splines> for AttributeText<'a> {
     fn from(splines: Splines) -> Self {
         AttributeText::quoted(splines.dot_string())
@@ -302,6 +390,12 @@ impl<'a> From<SplineType> for AttributeText<'a> {
     }
 }
 
+impl<'a> From<StartType> for AttributeText<'a> {
+    fn from(start_type: StartType) -> Self {
+        AttributeText::attr(start_type.dot_string())
+    }
+}
+
 impl<'a> From<Styles> for AttributeText<'a> {
     fn from(styles: Styles) -> Self {
         match styles {
@@ -312,13 +406,24 @@ impl<'a> From<Styles> for AttributeText<'a> {
     }
 }
 
+impl<'a, S: DotString<'a>> From<StyleList<S>> for AttributeText<'a> {
+    fn from(styles: StyleList<S>) -> Self {
+        AttributeText::from(styles.dot_string().into_owned())
+    }
+}
+
+impl<'a> From<TBBalance> for AttributeText<'a> {
+    fn from(balance: TBBalance) -> Self {
+        AttributeText::attr(balance.dot_string())
+    }
+}
+
 impl<'a> From<ViewPort> for AttributeText<'a> {
     fn from(viewport: ViewPort) -> Self {
         AttributeText::quoted(viewport.dot_string())
     }
 }
 
-
 impl<'a> From<u32> for AttributeText<'a> {
     fn from(v: u32) -> Self {
         AttributeText::attr(v.to_string())
@@ -328,7 +433,7 @@ impl<'a> From<u32> for AttributeText<'a> {
 impl<'a> From<String> for AttributeText<'a> {
     fn from(string: String) -> Self {
         // FIXME Attempt to select the enum type appropriate for the encoding required?
-        if is_alphanum(&string) {
+        if is_alphanum(&string) && !is_keyword(&string) {
             AttributeText::attr(string)
         } else {
             AttributeText::quoted(string)
@@ -339,7 +444,7 @@ impl<'a> From<String> for AttributeText<'a> {
 impl<'a> From<&str> for AttributeText<'a> {
     fn from(string: &str) -> Self {
         // FIXME Attempt to select the enum type appropriate for the encoding required?
-        if is_alphanum(&String::from(string)) {
+        if is_alphanum(string) && !is_keyword(string) {
             AttributeText::attr(String::from(string))
         } else {
             AttributeText::quoted(String::from(string))
@@ -353,7 +458,122 @@ impl<'a> From<AttributeText<'a>> for String {
     }
 }
 
-#[derive(Hash, Eq, PartialEq, PartialOrd, Ord, Debug, Clone)]
+macro_rules! try_from_attribute_text {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl TryFrom<&AttributeText<'_>> for $t {
+                type Error = ParseAttributeError;
+
+                fn try_from(text: &AttributeText<'_>) -> Result<Self, Self::Error> {
+                    text.as_str().parse()
+                }
+            }
+        )*
+    };
+}
+
+try_from_attribute_text!(
+    ArrowType,
+    ClusterMode,
+    CompassPoint,
+    Direction,
+    EdgeStyle,
+    GraphStyle,
+    ImagePosition,
+    ImageScale,
+    LabelJustification,
+    LabelLocation,
+    LayerRange,
+    Layers,
+    LayoutEngine,
+    Mode,
+    Model,
+    NodeStyle,
+    Ordering,
+    OutputMode,
+    Overlap,
+    PackMode,
+    PageDirection,
+    Point,
+    PortPosition,
+    QuadType,
+    RankDir,
+    Ratio,
+    Rectangle,
+    Shape,
+    SmoothType,
+    SplineType,
+    Splines,
+    StartType,
+    Styles,
+    TBBalance,
+    ViewPort,
+);
+
+impl TryFrom<&AttributeText<'_>> for bool {
+    type Error = ParseAttributeError;
+
+    /// Graphviz accepts `true`, `false`, `yes` and `no`, ignoring case,
+    /// as well as integers where any non-zero value is true.
+    fn try_from(text: &AttributeText<'_>) -> Result<Self, Self::Error> {
+        let s = text.as_str();
+        if s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("yes") {
+            return Ok(true);
+        }
+        if s.eq_ignore_ascii_case("false") || s.eq_ignore_ascii_case("no") {
+            return Ok(false);
+        }
+        s.parse::<i64>()
+            .map(|i| i != 0)
+            .map_err(|_| ParseAttributeError::new("bool", s))
+    }
+}
+
+impl TryFrom<&AttributeText<'_>> for f32 {
+    type Error = ParseAttributeError;
+
+    fn try_from(text: &AttributeText<'_>) -> Result<Self, Self::Error> {
+        text.as_str()
+            .parse()
+            .map_err(|_| ParseAttributeError::new("f32", text.as_str()))
+    }
+}
+
+impl TryFrom<&AttributeText<'_>> for f64 {
+    type Error = ParseAttributeError;
+
+    fn try_from(text: &AttributeText<'_>) -> Result<Self, Self::Error> {
+        text.as_str()
+            .parse()
+            .map_err(|_| ParseAttributeError::new("f64", text.as_str()))
+    }
+}
+
+impl TryFrom<&AttributeText<'_>> for u32 {
+    type Error = ParseAttributeError;
+
+    fn try_from(text: &AttributeText<'_>) -> Result<Self, Self::Error> {
+        text.as_str()
+            .parse()
+            .map_err(|_| ParseAttributeError::new("u32", text.as_str()))
+    }
+}
+
+/// Looks up an attribute and converts it to its typed value.
+/// Returns `None` if the attribute is not set or its value can not be converted.
+pub(crate) fn get_typed<'t, T>(
+    attributes: &'t IndexMap<AttributeName, AttributeText<'_>>,
+    name: AttributeName,
+) -> Option<T>
+where
+    T: TryFrom<&'t AttributeText<'t>>,
+{
+    attributes
+        .get(&name)
+        .and_then(|text| T::try_from(text).ok())
+}
+
+#[derive(Copy, Hash, Eq, PartialEq, PartialOrd, Ord, Debug, Clone)]
 pub enum AttributeType {
     Graph,
     Node,
@@ -362,12 +582,15 @@ pub enum AttributeType {
 
 pub trait GraphAttributes<'a> {
     fn background(&mut self, background: String) -> &mut Self {
-        self.add_attribute("_background", AttributeText::attr(background))
+        self.add_attribute(AttributeName::Background, AttributeText::attr(background))
     }
 
     /// The color used as the background for entire canvas.
     fn background_color(&mut self, background_color: Color<'a>) -> &mut Self {
-        self.add_attribute("bgcolor", AttributeText::from(background_color))
+        self.add_attribute(
+            AttributeName::BackgroundColor,
+            AttributeText::from(background_color),
+        )
     }
 
     // TODO: constrain
@@ -376,24 +599,38 @@ pub trait GraphAttributes<'a> {
     /// with C a color value and the optional F a floating-point number, 0 ≤ F ≤ 1.
     /// The sum of the floating-point numbers in a colorList must sum to at most 1.
     fn background_colorlist(&mut self, background_colors: ColorList<'a>) -> &mut Self {
-        self.add_attribute("bgcolor", AttributeText::from(background_colors))
+        self.add_attribute(
+            AttributeName::BackgroundColor,
+            AttributeText::from(background_colors),
+        )
+    }
+
+    /// Whether to try and make the final layout more aesthetically pleasing by placing
+    /// the leaves of a node evenly around it.
+    /// Only used by sfdp.
+    /// default: false
+    fn beautify(&mut self, beautify: bool) -> &mut Self {
+        self.add_attribute(AttributeName::Beautify, AttributeText::from(beautify))
     }
 
     /// Type: rect which is "%f,%f,%f,%f"
     /// The rectangle llx,lly,urx,ury gives the coordinates, in points, of the lower-left corner (llx,lly)
     /// and the upper-right corner (urx,ury).
     fn bounding_box(&mut self, bounding_box: String) -> &mut Self {
-        self.add_attribute("bb", AttributeText::quoted(bounding_box))
+        self.add_attribute(
+            AttributeName::BoundingBox,
+            AttributeText::quoted(bounding_box),
+        )
     }
 
     /// If true, the drawing is centered in the output canvas.
     fn center(&mut self, center: bool) -> &mut Self {
-        self.add_attribute("center", AttributeText::from(center))
+        self.add_attribute(AttributeName::Center, AttributeText::from(center))
     }
 
     /// Specifies the character encoding used when interpreting string input as a text label.
     fn charset(&mut self, charset: String) -> &mut Self {
-        self.add_attribute("charset", AttributeText::quoted(charset))
+        self.add_attribute(AttributeName::Charset, AttributeText::quoted(charset))
     }
 
     /// Classnames to attach to the node, edge, graph, or cluster’s SVG element.
@@ -412,7 +649,10 @@ pub trait GraphAttributes<'a> {
     /// Note also that there can be clusters within clusters.
     /// The modes clusterrank=global and clusterrank=none appear to be identical, both turning off the special cluster processing.
     fn cluster_rank(&mut self, cluster_rank: ClusterMode) -> &mut Self {
-        self.add_attribute("clusterrank", AttributeText::from(cluster_rank))
+        self.add_attribute(
+            AttributeName::ClusterRank,
+            AttributeText::from(cluster_rank),
+        )
     }
 
     /// Basic drawing color for graphics, not text. For the latter, use the fontcolor attribute.
@@ -439,17 +679,63 @@ pub trait GraphAttributes<'a> {
     }
 
     fn compound(&mut self, compound: String) -> &mut Self {
-        self.add_attribute("compound", AttributeText::quoted(compound))
+        self.add_attribute(AttributeName::Compound, AttributeText::quoted(compound))
     }
 
     fn concentrate(&mut self, concentrate: String) -> &mut Self {
-        self.add_attribute("concentrate", AttributeText::quoted(concentrate))
+        self.add_attribute(
+            AttributeName::Concentrate,
+            AttributeText::quoted(concentrate),
+        )
+    }
+
+    /// Set the number of dimensions used for the layout.
+    /// Used by fdp, neato and sfdp.
+    /// default: 2, minimum: 2, maximum: 10
+    fn dim(&mut self, dim: u32) -> &mut Self {
+        if !(2..=10).contains(&dim) {
+            self.add_validation_error("dim", "Must be between 2 and 10")
+        }
+        self.add_attribute(AttributeName::Dim, AttributeText::from(dim))
+    }
+
+    /// Set the number of dimensions used for rendering.
+    /// If dimen is set but dim is not, the same value is used for both.
+    /// Used by fdp, neato and sfdp.
+    /// default: 2, minimum: 2, maximum: 10
+    fn dimen(&mut self, dimen: u32) -> &mut Self {
+        if !(2..=10).contains(&dimen) {
+            self.add_validation_error("dimen", "Must be between 2 and 10")
+        }
+        self.add_attribute(AttributeName::Dimen, AttributeText::from(dimen))
     }
 
     /// Specifies the expected number of pixels per inch on a display device.
     /// Also known as resolution
     fn dpi(&mut self, dpi: f32) -> &mut Self {
-        self.add_attribute("dpi", AttributeText::from(dpi))
+        self.add_attribute(AttributeName::Dpi, AttributeText::from(dpi))
+    }
+
+    /// Terminating condition for neato.
+    /// If the length squared of all energy gradients are less than epsilon, the algorithm stops.
+    /// default: .0001 * # nodes
+    fn epsilon(&mut self, epsilon: f32) -> &mut Self {
+        self.add_attribute(AttributeName::Epsilon, AttributeText::from(epsilon))
+    }
+
+    /// Margin, in points, used around polygons for purposes of spline edge routing.
+    /// Should normally be strictly less than sep.
+    /// Not used by dot.
+    /// default: +3
+    fn esep(&mut self, esep: f32) -> &mut Self {
+        self.add_attribute(AttributeName::Esep, AttributeText::from(esep))
+    }
+
+    /// Margin used around polygons for purposes of spline edge routing,
+    /// given separately for the x and y directions.
+    /// Not used by dot.
+    fn esep_point(&mut self, esep: Point) -> &mut Self {
+        self.add_attribute(AttributeName::Esep, AttributeText::from(esep))
     }
 
     /// Color used to fill the background of a node or cluster assuming style=filled, or a filled arrowhead.
@@ -491,11 +777,11 @@ pub trait GraphAttributes<'a> {
     }
 
     fn font_names(&mut self, font_names: String) -> &mut Self {
-        self.add_attribute("fontnames", AttributeText::quoted(font_names))
+        self.add_attribute(AttributeName::FontNames, AttributeText::quoted(font_names))
     }
 
     fn font_path(&mut self, font_path: String) -> &mut Self {
-        self.add_attribute("fontpath", AttributeText::quoted(font_path))
+        self.add_attribute(AttributeName::FontPath, AttributeText::quoted(font_path))
     }
 
     /// Font size, in points, used for text.
@@ -509,7 +795,7 @@ pub trait GraphAttributes<'a> {
     }
 
     fn force_label(&mut self, force_label: bool) -> &mut Self {
-        self.add_attribute("forcelabel", AttributeText::from(force_label))
+        self.add_attribute(AttributeName::ForceLabels, AttributeText::from(force_label))
     }
 
     /// If a gradient fill is being used, this determines the angle of the fill.
@@ -519,7 +805,14 @@ pub trait GraphAttributes<'a> {
     }
 
     fn image_path(&mut self, image_path: String) -> &mut Self {
-        self.add_attribute("imagepath", AttributeText::escaped(image_path))
+        self.add_attribute(AttributeName::ImagePath, AttributeText::escaped(image_path))
+    }
+
+    /// Scales the input positions to convert between length units.
+    /// For example, inputscale=72 converts input positions given in inches to points.
+    /// Used by fdp and neato.
+    fn input_scale(&mut self, input_scale: f32) -> &mut Self {
+        self.add_attribute(AttributeName::InputScale, AttributeText::from(input_scale))
     }
 
     /// An escString or an HTML label.
@@ -535,7 +828,10 @@ pub trait GraphAttributes<'a> {
         &mut self,
         label_justification: LabelJustification,
     ) -> &mut Self {
-        self.add_attribute("labeljust", AttributeText::from(label_justification))
+        self.add_attribute(
+            AttributeName::LabelJust,
+            AttributeText::from(label_justification),
+        )
     }
 
     // Vertical placement of labels for nodes, root graphs and clusters.
@@ -555,37 +851,64 @@ pub trait GraphAttributes<'a> {
     }
 
     fn landscape(&mut self, landscape: bool) -> &mut Self {
-        self.add_attribute("landscape", AttributeText::from(landscape))
+        self.add_attribute(AttributeName::Landscape, AttributeText::from(landscape))
     }
 
     /// Specifies the separator characters used to split an attribute of type layerRange into a
     /// list of ranges.
     fn layer_list_sep(&mut self, layer_list_sep: String) -> &mut Self {
-        self.add_attribute("layerlistsep", AttributeText::attr(layer_list_sep))
+        self.add_attribute(
+            AttributeName::LayerListSep,
+            AttributeText::quoted(layer_list_sep),
+        )
     }
 
     /// Specifies a linearly ordered list of layer names attached to the graph
     /// The graph is then output in separate layers.
     /// Only those components belonging to the current output layer appear.
-    fn layers(&mut self, layers: String) -> &mut Self {
-        Attributes::layer(self.get_attributes_mut(), layers);
-        self
+    /// A custom separator is also set as the graph's layersep.
+    fn layers(&mut self, layers: Layers) -> &mut Self {
+        if layers.separator != DEFAULT_LAYER_SEP {
+            self.layer_sep(layers.separator.clone());
+        }
+        self.add_attribute(AttributeName::Layers, AttributeText::from(layers))
     }
 
     /// Selects a list of layers to be emitted.
-    fn layer_select(&mut self, layer_select: String) -> &mut Self {
-        self.add_attribute("layerselect", AttributeText::attr(layer_select))
+    fn layer_select(&mut self, layer_select: LayerRange) -> &mut Self {
+        self.add_attribute(
+            AttributeName::LayerSelect,
+            AttributeText::from(layer_select),
+        )
+    }
+
+    /// Specifies layers in which the cluster is present.
+    fn layer(&mut self, layer: LayerRange) -> &mut Self {
+        Attributes::layer(self.get_attributes_mut(), layer);
+        self
     }
 
     /// Specifies the separator characters used to split the layers attribute into a list of layer names.
     /// default: ":\t "
     fn layer_sep(&mut self, layer_sep: String) -> &mut Self {
-        self.add_attribute("layersep", AttributeText::attr(layer_sep))
+        self.add_attribute(AttributeName::LayerSep, AttributeText::quoted(layer_sep))
+    }
+
+    /// Which layout engine to use.
+    fn layout(&mut self, layout: LayoutEngine) -> &mut Self {
+        self.add_attribute(AttributeName::Layout, AttributeText::from(layout))
+    }
+
+    /// Number of levels allowed in the multilevel scheme.
+    /// Only used by sfdp.
+    /// default: i32::MAX, minimum: 0
+    fn levels(&mut self, levels: u32) -> &mut Self {
+        self.add_attribute(AttributeName::Levels, AttributeText::from(levels))
     }
 
     /// Height of graph or cluster label, in inches.
     fn lheight(&mut self, lheight: f32) -> &mut Self {
-        self.add_attribute("lheight", AttributeText::from(lheight))
+        self.add_attribute(AttributeName::LHeight, AttributeText::from(lheight))
     }
 
     /// Label position
@@ -597,7 +920,7 @@ pub trait GraphAttributes<'a> {
 
     /// Width of graph or cluster label, in inches.
     fn lwidth(&mut self, lwidth: f32) -> &mut Self {
-        self.add_attribute("lwidth", AttributeText::from(lwidth))
+        self.add_attribute(AttributeName::LWidth, AttributeText::from(lwidth))
     }
 
     /// Sets x and y margins of canvas, in inches.
@@ -620,17 +943,45 @@ pub trait GraphAttributes<'a> {
         self
     }
 
+    /// Sets the number of iterations used.
+    /// Used by fdp and neato.
+    /// default: 100 * # nodes for neato, 600 for fdp
+    fn max_iter(&mut self, max_iter: u32) -> &mut Self {
+        self.add_attribute(AttributeName::MaxIter, AttributeText::from(max_iter))
+    }
+
     /// Multiplicative scale factor used to alter the MinQuit (default = 8) and
     /// MaxIter (default = 24) parameters used during crossing minimization.
     /// These correspond to the number of tries without improvement before quitting and the
     /// maximum number of iterations in each pass.
     fn mclimit(&mut self, mclimit: f32) -> &mut Self {
-        self.add_attribute("mclimit", AttributeText::from(mclimit))
+        self.add_attribute(AttributeName::McLimit, AttributeText::from(mclimit))
     }
 
     /// Specifies the minimum separation between all nodes.
     fn mindist(&mut self, mindist: u32) -> &mut Self {
-        self.add_attribute("mindist", AttributeText::from(mindist))
+        self.add_attribute(AttributeName::MinDist, AttributeText::from(mindist))
+    }
+
+    /// Technique for optimizing the layout.
+    /// Used by neato and sfdp.
+    /// default: major
+    fn mode(&mut self, mode: Mode) -> &mut Self {
+        self.add_attribute(AttributeName::Mode, AttributeText::from(mode))
+    }
+
+    /// Specifies how the distance matrix is computed for the input graph.
+    /// Only used by neato.
+    /// default: shortpath
+    fn model(&mut self, model: Model) -> &mut Self {
+        self.add_attribute(AttributeName::Model, AttributeText::from(model))
+    }
+
+    /// If Graphviz is built with MOSEK defined, mode=ipsep and mosek=true,
+    /// the Mosek software is used to solve the ipsep constraints.
+    /// Only used by neato.
+    fn mosek(&mut self, mosek: bool) -> &mut Self {
+        self.add_attribute(AttributeName::Mosek, AttributeText::from(mosek))
     }
 
     /// Whether to use a single global ranking, ignoring clusters.
@@ -643,7 +994,7 @@ pub trait GraphAttributes<'a> {
     /// This allows nodes to be subject to multiple constraints.
     /// Rank constraints will usually take precedence over edge constraints.
     fn newrank(&mut self, newrank: bool) -> &mut Self {
-        self.add_attribute("newrank", AttributeText::from(newrank))
+        self.add_attribute(AttributeName::NewRank, AttributeText::from(newrank))
     }
 
     /// specifies the minimum space between two adjacent nodes in the same rank, in inches.
@@ -652,7 +1003,7 @@ pub trait GraphAttributes<'a> {
         if nodesep < 0.02 {
             self.add_validation_error("nodesep", "Must be greater than or equal to 0.02")
         }
-        self.add_attribute("nodesep", AttributeText::from(nodesep))
+        self.add_attribute(AttributeName::NodeSep, AttributeText::from(nodesep))
     }
 
     /// By default, the justification of multi-line labels is done within the largest context that makes sense.
@@ -670,11 +1021,26 @@ pub trait GraphAttributes<'a> {
         self
     }
 
+    /// If true, the final layout is rotated so that the first edge
+    /// points horizontally to the right.
+    /// Not used by dot.
+    /// See [`crate::attributes::GraphAttributes::normalize_angle`]
+    fn normalize(&mut self, normalize: bool) -> &mut Self {
+        self.add_attribute(AttributeName::Normalize, AttributeText::from(normalize))
+    }
+
+    /// Rotates the final layout so that the first edge makes the given angle,
+    /// in degrees, with the x-axis.
+    /// Not used by dot.
+    fn normalize_angle(&mut self, normalize: f32) -> &mut Self {
+        self.add_attribute(AttributeName::Normalize, AttributeText::from(normalize))
+    }
+
     /// Sets number of iterations in network simplex applications.
     /// nslimit is used in computing node x coordinates.
     /// If defined, # iterations = nslimit * # nodes; otherwise, # iterations = MAXINT.
     fn nslimit(&mut self, nslimit: f32) -> &mut Self {
-        self.add_attribute("nslimit", AttributeText::from(nslimit))
+        self.add_attribute(AttributeName::NsLimit, AttributeText::from(nslimit))
     }
 
     /// If ordering="out", then the outedges of a node, that is, edges with the node as its tail
@@ -696,7 +1062,7 @@ pub trait GraphAttributes<'a> {
     /// Used only if rotate is not defined.
     /// Default: 0.0 and minimum: 360.0
     fn orientation(&mut self, orientation: f32) -> &mut Self {
-        if orientation < 0.0 || orientation > 360.0 {
+        if !(0.0..=360.0).contains(&orientation) {
             self.add_validation_error("orientation", "Must be between 0 and 360")
         }
         Attributes::orientation(self.get_attributes_mut(), orientation);
@@ -706,7 +1072,17 @@ pub trait GraphAttributes<'a> {
     /// Specify order in which nodes and edges are drawn.
     /// default: breadthfirst
     fn output_order(&mut self, output_order: OutputMode) -> &mut Self {
-        self.add_attribute("outputorder", AttributeText::from(output_order))
+        self.add_attribute(
+            AttributeName::OutputOrder,
+            AttributeText::from(output_order),
+        )
+    }
+
+    /// Determines if and how node overlaps should be removed.
+    /// Not used by dot.
+    /// default: true
+    fn overlap(&mut self, overlap: Overlap) -> &mut Self {
+        self.add_attribute(AttributeName::Overlap, AttributeText::from(overlap))
     }
 
     /// Whether each connected component of the graph should be laid out separately, and then the
@@ -714,7 +1090,7 @@ pub trait GraphAttributes<'a> {
     /// If false, the entire graph is laid out together.
     /// The granularity and method of packing is influenced by the packmode attribute.
     fn pack(&mut self, pack: bool) -> &mut Self {
-        self.add_attribute("pack", AttributeText::from(pack))
+        self.add_attribute(AttributeName::Pack, AttributeText::from(pack))
     }
 
     /// Whether each connected component of the graph should be laid out separately, and then
@@ -723,13 +1099,13 @@ pub trait GraphAttributes<'a> {
     /// margin of 8 is used.
     /// pack is treated as true if the value of pack iso a non-negative integer.
     fn pack_int(&mut self, pack: u32) -> &mut Self {
-        self.add_attribute("pack", AttributeText::from(pack))
+        self.add_attribute(AttributeName::Pack, AttributeText::from(pack))
     }
 
     /// This indicates how connected components should be packed (cf. packMode).
     /// Note that defining packmode will automatically turn on packing as though one had set pack=true.
     fn pack_mode(&mut self, pack_mode: PackMode) -> &mut Self {
-        self.add_attribute("packmode", AttributeText::from(pack_mode))
+        self.add_attribute(AttributeName::PackMode, AttributeText::from(pack_mode))
     }
 
     /// Specifies how much, in inches, to extend the drawing area around the minimal area needed
@@ -745,25 +1121,25 @@ pub trait GraphAttributes<'a> {
     /// This area is part of the drawing and will be filled with the background color, if appropriate.
     /// default: 0.0555
     fn pad_point(&mut self, pad: Point) -> &mut Self {
-        self.add_attribute("pad", AttributeText::from(pad))
+        self.add_attribute(AttributeName::Pad, AttributeText::from(pad))
     }
 
     /// Width and height of output pages, in inches.
     /// Value given is used for both the width and height.
     fn page(&mut self, page: f32) -> &mut Self {
-        self.add_attribute("page", AttributeText::from(page))
+        self.add_attribute(AttributeName::Page, AttributeText::from(page))
     }
 
     /// Width and height of output pages, in inches.
     fn page_point(&mut self, page: Point) -> &mut Self {
-        self.add_attribute("page", AttributeText::from(page))
+        self.add_attribute(AttributeName::Page, AttributeText::from(page))
     }
 
     /// The order in which pages are emitted.
     /// Used only if page is set and applicable.
     /// Limited to one of the 8 row or column major orders.
     fn page_dir(&mut self, page_dir: PageDirection) -> &mut Self {
-        self.add_attribute("pagedir", AttributeText::from(page_dir))
+        self.add_attribute(AttributeName::PageDir, AttributeText::from(page_dir))
     }
 
     /// If quantum > 0.0, node label dimensions will be rounded to integral multiples of the quantum.
@@ -772,7 +1148,14 @@ pub trait GraphAttributes<'a> {
         if quantum < 0.0 {
             self.add_validation_error("quantum", "Must be greater than or equal to 0")
         }
-        self.add_attribute("quantum", AttributeText::from(quantum))
+        self.add_attribute(AttributeName::Quantum, AttributeText::from(quantum))
+    }
+
+    /// Quadtree scheme to use.
+    /// Only used by sfdp.
+    /// default: normal
+    fn quadtree(&mut self, quadtree: QuadType) -> &mut Self {
+        self.add_attribute(AttributeName::QuadTree, AttributeText::from(quadtree))
     }
 
     /// Sets direction of graph layout.
@@ -781,7 +1164,7 @@ pub trait GraphAttributes<'a> {
     /// This attribute also has a side-effect in determining how record nodes are interpreted.
     /// See record shapes.
     fn rank_dir(&mut self, rank_dir: RankDir) -> &mut Self {
-        self.add_attribute("rankdir", AttributeText::from(rank_dir))
+        self.add_attribute(AttributeName::RankDir, AttributeText::from(rank_dir))
     }
 
     /// sets the desired rank separation, in inches.
@@ -790,23 +1173,83 @@ pub trait GraphAttributes<'a> {
     /// the centers of all ranks are spaced equally apart.
     /// Note that both settings are possible, e.g., ranksep="1.2 equally".
     fn rank_sep(&mut self, rank_sep: String) -> &mut Self {
-        self.add_attribute("ranksep", AttributeText::attr(rank_sep))
+        self.add_attribute(AttributeName::RankSep, AttributeText::attr(rank_sep))
+    }
+
+    /// In twopi, specifies the radial separation of concentric circles, in inches.
+    /// The first value is the separation between the root and the first circle and each
+    /// further value the separation to the next circle. The last value is used for
+    /// any remaining circles.
+    fn rank_sep_radial(&mut self, rank_sep: &[f32]) -> &mut Self {
+        let rank_sep = rank_sep
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<String>>()
+            .join(":");
+        self.add_attribute(AttributeName::RankSep, AttributeText::quoted(rank_sep))
     }
 
     /// Sets the aspect ratio (drawing height/drawing width) for the drawing.
     /// Note that this is adjusted before the size attribute constraints are enforced.
     fn ratio(&mut self, ratio: Ratio) -> &mut Self {
-        self.add_attribute("ratio", AttributeText::from(ratio))
+        self.add_attribute(AttributeName::Ratio, AttributeText::from(ratio))
     }
 
     /// If true and there are multiple clusters, run crossing minimization a second time.
     fn remincross(&mut self, remincross: bool) -> &mut Self {
-        self.add_attribute("remincross", AttributeText::from(remincross))
+        self.add_attribute(AttributeName::ReMinCross, AttributeText::from(remincross))
+    }
+
+    /// The power of the repulsive force used in an extended Fruchterman-Reingold force
+    /// directed model. Values larger than 1 tend to reduce the warping effect at the
+    /// expense of less clustering.
+    /// Only used by sfdp.
+    /// default: 1.0, minimum: 0.0
+    fn repulsive_force(&mut self, repulsive_force: f32) -> &mut Self {
+        if repulsive_force < 0.0 {
+            self.add_validation_error(
+                "repulsiveforce",
+                "Must be greater than or equal to 0",
+            )
+        }
+        self.add_attribute(
+            AttributeName::RepulsiveForce,
+            AttributeText::from(repulsive_force),
+        )
+    }
+
+    /// Name of the node to use as the center of the layout.
+    /// Used by circo and twopi.
+    fn root(&mut self, root: String) -> &mut Self {
+        self.add_attribute(AttributeName::Root, AttributeText::from(root))
     }
 
     /// If rotate=90, sets drawing orientation to landscape.
     fn rotate(&mut self, rotate: u32) -> &mut Self {
-        self.add_attribute("rotate", AttributeText::from(rotate))
+        self.add_attribute(AttributeName::Rotate, AttributeText::from(rotate))
+    }
+
+    /// During network simplex, the maximum number of edges with negative cut values
+    /// to search when looking for an edge with minimum cut value.
+    /// Only used by dot.
+    /// default: 30
+    fn search_size(&mut self, search_size: u32) -> &mut Self {
+        self.add_attribute(AttributeName::SearchSize, AttributeText::from(search_size))
+    }
+
+    /// Margin to leave around nodes when removing node overlap.
+    /// This guarantees a minimal non-zero distance between nodes.
+    /// Not used by dot.
+    /// default: +4
+    fn sep(&mut self, sep: f32) -> &mut Self {
+        self.add_attribute(AttributeName::Sep, AttributeText::from(sep))
+    }
+
+    /// Margin to leave around nodes when removing node overlap,
+    /// given separately for the x and y directions.
+    /// Not used by dot.
+    fn sep_point(&mut self, sep: Point) -> &mut Self {
+        self.add_attribute(AttributeName::Sep, AttributeText::from(sep))
     }
 
     /// Print guide boxes in PostScript at the beginning of routesplines if showboxes=1, or at
@@ -842,7 +1285,14 @@ pub trait GraphAttributes<'a> {
     /// are less than size, the drawing is scaled up uniformly until at
     /// least one dimension equals its dimension in size.
     fn size_point(&mut self, size: Point) -> &mut Self {
-        self.add_attribute("size", AttributeText::from(size))
+        self.add_attribute(AttributeName::Size, AttributeText::from(size))
+    }
+
+    /// Specifies a post-processing step used to smooth out an uneven distribution of nodes.
+    /// Only used by sfdp.
+    /// default: none
+    fn smoothing(&mut self, smoothing: SmoothType) -> &mut Self {
+        self.add_attribute(AttributeName::Smoothing, AttributeText::from(smoothing))
     }
 
     /// If packmode indicates an array packing, sortv specifies an insertion order
@@ -855,7 +1305,24 @@ pub trait GraphAttributes<'a> {
 
     /// Controls how, and if, edges are represented.
     fn splines(&mut self, splines: Splines) -> &mut Self {
-        self.add_attribute("splines", AttributeText::from(splines))
+        self.add_attribute(AttributeName::Splines, AttributeText::from(splines))
+    }
+
+    /// Spring constant used in virtual physical model.
+    /// Rendered as the K attribute.
+    /// Used by fdp and sfdp.
+    /// default: 0.3, minimum: 0
+    fn spring_constant(&mut self, k: f32) -> &mut Self {
+        if k < 0.0 {
+            self.add_validation_error("K", "Must be greater than or equal to 0")
+        }
+        self.add_attribute(AttributeName::K, AttributeText::from(k))
+    }
+
+    /// Parameter used to determine the initial layout of nodes.
+    /// Used by fdp and neato.
+    fn start(&mut self, start: StartType) -> &mut Self {
+        self.add_attribute(AttributeName::Start, AttributeText::from(start))
     }
 
     /// Set style information for components of the graph.
@@ -864,10 +1331,21 @@ pub trait GraphAttributes<'a> {
         self
     }
 
+    /// Set a list of styles, such as `filled,rounded`.
+    fn styles(&mut self, styles: GraphStyleList) -> &mut Self {
+        self.add_attribute(AttributeName::Style, AttributeText::from(styles))
+    }
+
     /// A URL or pathname specifying an XML style sheet, used in SVG output.
     /// Combine with class to style elements using CSS selectors.
     fn stylesheet(&mut self, stylesheet: String) -> &mut Self {
-        self.add_attribute("stylesheet", AttributeText::attr(stylesheet))
+        self.add_attribute(AttributeName::Stylesheet, AttributeText::attr(stylesheet))
+    }
+
+    /// Which rank to move floating (loose) nodes to.
+    /// Only used by dot.
+    fn tb_balance(&mut self, tb_balance: TBBalance) -> &mut Self {
+        self.add_attribute(AttributeName::TBBalance, AttributeText::from(tb_balance))
     }
 
     /// If the object has a URL, this attribute determines which window of the browser is used for the URL.
@@ -881,7 +1359,7 @@ pub trait GraphAttributes<'a> {
     /// for some node in the graph.
     /// The output model will use the input model when possible.
     fn true_color(&mut self, true_color: bool) -> &mut Self {
-        self.add_attribute("truecolor", AttributeText::from(true_color))
+        self.add_attribute(AttributeName::TrueColor, AttributeText::from(true_color))
     }
 
     /// Hyperlinks incorporated into device-dependent output.
@@ -902,64 +1380,79 @@ pub trait GraphAttributes<'a> {
     /// in points, of the center of the viewport, or the name N of a node whose center should used
     /// as the focus.
     fn viewport(&mut self, viewport: ViewPort) -> &mut Self {
-        self.add_attribute("viewport", AttributeText::from(viewport))
+        self.add_attribute(AttributeName::ViewPort, AttributeText::from(viewport))
     }
 
     /// Add an attribute to the node.
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self;
 
     /// Add multiple attributes to the node.
-    fn add_attributes(
+    fn add_attributes<K: Into<AttributeName>>(
         &'a mut self,
-        attributes: HashMap<String, AttributeText<'a>>,
-    ) -> &mut Self;
+        attributes: HashMap<K, AttributeText<'a>>,
+    ) -> &'a mut Self;
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>>;
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>>;
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str);
 }
 
 impl<'a> GraphAttributes<'a> for GraphAttributeStatementBuilder<'a> {
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
-        self.attributes.insert(key.into(), value);
+        let key = key.into();
+        validate_attribute(&mut self.errors, GRAPH_CONTEXTS, &key, &value);
+        self.attributes.insert(key, value);
         self
     }
 
     /// Add multiple attributes to the node.
-    fn add_attributes(
+    fn add_attributes<K: Into<AttributeName>>(
         &'a mut self,
-        attributes: HashMap<String, AttributeText<'a>>,
-    ) -> &mut Self {
-        self.attributes.extend(attributes);
+        attributes: HashMap<K, AttributeText<'a>>,
+    ) -> &'a mut Self {
+        for (key, value) in attributes {
+            self.add_attribute(key, value);
+        }
         self
     }
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>> {
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>> {
         &mut self.attributes
     }
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str) {
-        self.errors.push(ValidationError {
-            field: Borrowed(field),
-            message: Borrowed(message),
-        })
+        self.errors
+            .push(ValidationError::new(ErrorCode::OutOfRange, field, message))
     }
 }
 
+// Graph attribute statements can be used in the root graph as well as subgraphs
+const GRAPH_CONTEXTS: &[AttributeContext] = &[
+    AttributeContext::Graph,
+    AttributeContext::SubGraph,
+    AttributeContext::Cluster,
+];
+
 // I'm not a huge fan of needing this builder but having a hard time getting around &mut without it
 pub struct GraphAttributeStatementBuilder<'a> {
-    pub attributes: IndexMap<String, AttributeText<'a>>,
+    pub attributes: IndexMap<AttributeName, AttributeText<'a>>,
     errors: Vec<ValidationError>,
 }
 
+impl<'a> Default for GraphAttributeStatementBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> GraphAttributeStatementBuilder<'a> {
     pub fn new() -> Self {
         Self {
@@ -968,69 +1461,92 @@ impl<'a> GraphAttributeStatementBuilder<'a> {
         }
     }
 
-    pub fn build(&self) -> ValidationResult<IndexMap<String, AttributeText<'a>>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+    pub fn build(&self) -> ValidationResult<IndexMap<AttributeName, AttributeText<'a>>> {
+        if self.errors.iter().any(ValidationError::is_error) {
+            let path = ElementPath::default();
+            return Err(locate_errors(&self.errors, &path, &self.attributes));
         }
         Ok(self.build_ignore_validation())
     }
 
-    pub fn build_ignore_validation(&self) -> IndexMap<String, AttributeText<'a>> {
+    pub fn build_ignore_validation(&self) -> IndexMap<AttributeName, AttributeText<'a>> {
         self.attributes.clone()
     }
 }
 
 pub(crate) struct Attributes;
 impl Attributes {
-    pub fn class(attributes: &mut IndexMap<String, AttributeText>, class: String) {
-        Self::add_attribute(attributes, "class", AttributeText::quoted(class))
+    pub fn class(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        class: String,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::Class,
+            AttributeText::quoted(class),
+        )
     }
 
     pub fn color<'a>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         color: Color<'a>,
     ) {
-        Self::add_attribute(attributes, "color", AttributeText::from(color))
+        Self::add_attribute(attributes, AttributeName::Color, AttributeText::from(color))
     }
 
     pub fn color_with_colorlist<'a>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         color: ColorList<'a>,
     ) {
-        Self::add_attribute(attributes, "color", AttributeText::from(color))
+        Self::add_attribute(attributes, AttributeName::Color, AttributeText::from(color))
     }
 
     pub fn color_scheme(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         color_scheme: String,
     ) {
         Self::add_attribute(
             attributes,
-            "colorscheme",
+            AttributeName::ColorScheme,
             AttributeText::quoted(color_scheme),
         )
     }
 
-    pub fn comment(attributes: &mut IndexMap<String, AttributeText>, comment: String) {
-        Self::add_attribute(attributes, "comment", AttributeText::quoted(comment))
+    pub fn comment(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        comment: String,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::Comment,
+            AttributeText::quoted(comment),
+        )
     }
 
     pub fn fill_color<'a>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         fill_color: Color<'a>,
     ) {
-        Self::add_attribute(attributes, "fillcolor", AttributeText::from(fill_color))
+        Self::add_attribute(
+            attributes,
+            AttributeName::FillColor,
+            AttributeText::from(fill_color),
+        )
     }
 
     pub fn fill_color_with_colorlist<'a>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         fill_colors: ColorList<'a>,
     ) {
-        Self::add_attribute(attributes, "fillcolor", AttributeText::from(fill_colors))
+        Self::add_attribute(
+            attributes,
+            AttributeName::FillColor,
+            AttributeText::from(fill_colors),
+        )
     }
 
     pub fn fill_color_with_iter<'a, I>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         fill_colors: I,
     ) where
         I: IntoIterator,
@@ -1043,130 +1559,218 @@ impl Attributes {
 
         let color_list = ColorList { colors };
 
-        Self::add_attribute(attributes, "fillcolor", AttributeText::from(color_list))
+        Self::add_attribute(
+            attributes,
+            AttributeName::FillColor,
+            AttributeText::from(color_list),
+        )
     }
 
     pub fn font_color<'a>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         font_color: Color<'a>,
     ) {
-        Self::add_attribute(attributes, "fontcolor", AttributeText::from(font_color))
+        Self::add_attribute(
+            attributes,
+            AttributeName::FontColor,
+            AttributeText::from(font_color),
+        )
     }
 
     pub fn font_name(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         font_name: String,
     ) {
-        Self::add_attribute(attributes, "fontname", AttributeText::quoted(font_name))
+        Self::add_attribute(
+            attributes,
+            AttributeName::FontName,
+            AttributeText::quoted(font_name),
+        )
     }
 
-    pub fn font_size(attributes: &mut IndexMap<String, AttributeText>, font_size: f32) {
-        Self::add_attribute(attributes, "fontsize", AttributeText::from(font_size))
+    pub fn font_size(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        font_size: f32,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::FontSize,
+            AttributeText::from(font_size),
+        )
     }
 
     pub fn gradient_angle(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         gradient_angle: u32,
     ) {
         Self::add_attribute(
             attributes,
-            "gradientangle",
+            AttributeName::GradientAngle,
             AttributeText::from(gradient_angle),
         )
     }
 
-    pub fn label<S: Into<String>>(attributes: &mut IndexMap<String, AttributeText>, text: S) {
-        Self::add_attribute(attributes, "label", AttributeText::quoted(text.into()));
+    pub fn label<S: Into<String>>(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        text: S,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::Label,
+            AttributeText::quoted(text.into()),
+        );
     }
 
     pub fn label_location(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         label_location: LabelLocation,
     ) {
-        Self::add_attribute(attributes, "labelloc", AttributeText::from(label_location))
+        Self::add_attribute(
+            attributes,
+            AttributeName::LabelLoc,
+            AttributeText::from(label_location),
+        )
     }
 
-    // TODO: Layer, LayerRange, LayerList, LayerSep
-    // TODO: layer struct
-    pub fn layer(attributes: &mut IndexMap<String, AttributeText>, layer: String) {
-        Self::add_attribute(attributes, "layer", AttributeText::attr(layer))
+    pub fn layer(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        layer: LayerRange,
+    ) {
+        Self::add_attribute(attributes, AttributeName::Layer, AttributeText::from(layer))
     }
 
-    pub fn label_position(attributes: &mut IndexMap<String, AttributeText>, lp: Point) {
-        Self::add_attribute(attributes, "lp", AttributeText::from(lp))
+    pub fn label_position(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        lp: Point,
+    ) {
+        Self::add_attribute(attributes, AttributeName::Lp, AttributeText::from(lp))
     }
 
-    pub fn margin(attributes: &mut IndexMap<String, AttributeText>, margin: Point) {
-        Self::add_attribute(attributes, "margin", AttributeText::from(margin))
+    pub fn margin(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        margin: Point,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::Margin,
+            AttributeText::from(margin),
+        )
     }
 
     pub fn no_justify(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         no_justify: bool,
     ) {
-        Self::add_attribute(attributes, "nojustify", AttributeText::from(no_justify))
+        Self::add_attribute(
+            attributes,
+            AttributeName::NoJustify,
+            AttributeText::from(no_justify),
+        )
     }
 
     pub fn ordering(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         ordering: Ordering,
     ) {
-        Self::add_attribute(attributes, "ordering", AttributeText::from(ordering))
+        Self::add_attribute(
+            attributes,
+            AttributeName::Ordering,
+            AttributeText::from(ordering),
+        )
     }
 
     pub fn orientation(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         orientation: f32,
     ) {
-        Self::add_attribute(attributes, "orientation", AttributeText::from(orientation))
+        Self::add_attribute(
+            attributes,
+            AttributeName::Orientation,
+            AttributeText::from(orientation),
+        )
     }
 
-    pub fn pen_width(attributes: &mut IndexMap<String, AttributeText>, pen_width: f32) {
-        Self::add_attribute(attributes, "penwidth", AttributeText::from(pen_width))
+    pub fn pen_width(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        pen_width: f32,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::PenWidth,
+            AttributeText::from(pen_width),
+        )
     }
 
     // TODO: splinetype
-    pub fn pos(attributes: &mut IndexMap<String, AttributeText>, pos: Point) {
-        Self::add_attribute(attributes, "pos", AttributeText::from(pos))
+    pub fn pos(attributes: &mut IndexMap<AttributeName, AttributeText>, pos: Point) {
+        Self::add_attribute(attributes, AttributeName::Pos, AttributeText::from(pos))
     }
 
     pub fn show_boxes(
-        attributes: &mut IndexMap<String, AttributeText>,
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
         show_boxes: u32,
     ) {
-        Self::add_attribute(attributes, "showboxes", AttributeText::from(show_boxes))
+        Self::add_attribute(
+            attributes,
+            AttributeName::ShowBoxes,
+            AttributeText::from(show_boxes),
+        )
     }
 
-    pub fn sortv(attributes: &mut IndexMap<String, AttributeText>, sortv: u32) {
-        Self::add_attribute(attributes, "sortv", AttributeText::from(sortv))
+    pub fn sortv(attributes: &mut IndexMap<AttributeName, AttributeText>, sortv: u32) {
+        Self::add_attribute(attributes, AttributeName::SortV, AttributeText::from(sortv))
     }
 
-    pub fn style(attributes: &mut IndexMap<String, AttributeText>, style: Styles) {
-        Self::add_attribute(attributes, "style", AttributeText::from(style))
+    pub fn style(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        style: Styles,
+    ) {
+        Self::add_attribute(attributes, AttributeName::Style, AttributeText::from(style))
     }
 
-    pub fn target(attributes: &mut IndexMap<String, AttributeText>, target: String) {
-        Self::add_attribute(attributes, "target", AttributeText::escaped(target))
+    pub fn target(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        target: String,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::Target,
+            AttributeText::escaped(target),
+        )
     }
 
-    pub fn tooltip(attributes: &mut IndexMap<String, AttributeText>, tooltip: String) {
-        Self::add_attribute(attributes, "tooltip", AttributeText::escaped(tooltip))
+    pub fn tooltip(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        tooltip: String,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::Tooltip,
+            AttributeText::escaped(tooltip),
+        )
     }
 
-    pub fn url(attributes: &mut IndexMap<String, AttributeText>, url: String) {
-        Self::add_attribute(attributes, "url", AttributeText::escaped(url))
+    pub fn url(attributes: &mut IndexMap<AttributeName, AttributeText>, url: String) {
+        Self::add_attribute(attributes, AttributeName::Url, AttributeText::escaped(url))
     }
 
-    pub fn xlabel(attributes: &mut IndexMap<String, AttributeText>, width: String) {
-        Self::add_attribute(attributes, "xlabel", AttributeText::escaped(width))
+    pub fn xlabel(
+        attributes: &mut IndexMap<AttributeName, AttributeText>,
+        width: String,
+    ) {
+        Self::add_attribute(
+            attributes,
+            AttributeName::XLabel,
+            AttributeText::escaped(width),
+        )
     }
 
-    pub fn xlp(attributes: &mut IndexMap<String, AttributeText>, xlp: Point) {
-        Self::add_attribute(attributes, "xlp", AttributeText::from(xlp))
+    pub fn xlp(attributes: &mut IndexMap<AttributeName, AttributeText>, xlp: Point) {
+        Self::add_attribute(attributes, AttributeName::Xlp, AttributeText::from(xlp))
     }
 
-    pub fn add_attribute<'a, S: Into<String>>(
-        attributes: &mut IndexMap<String, AttributeText<'a>>,
+    pub fn add_attribute<'a, S: Into<AttributeName>>(
+        attributes: &mut IndexMap<AttributeName, AttributeText<'a>>,
         key: S,
         value: AttributeText<'a>,
     ) {
@@ -1181,7 +1785,7 @@ pub trait NodeAttributes<'a> {
         if area <= 0.0 {
             self.add_validation_error("area", "Must be greater than 0")
         }
-        self.add_attribute("area", AttributeText::from(area))
+        self.add_attribute(AttributeName::Area, AttributeText::from(area))
     }
 
     /// Classnames to attach to the node’s SVG element.
@@ -1216,7 +1820,7 @@ pub trait NodeAttributes<'a> {
     /// Distortion factor for shape=polygon.
     /// Positive values cause top part to be larger than bottom; negative values do the opposite.
     fn distortion(&mut self, distortion: f32) -> &mut Self {
-        self.add_attribute("distortion", AttributeText::from(distortion))
+        self.add_attribute(AttributeName::Distortion, AttributeText::from(distortion))
     }
 
     /// Color used to fill the background of a node or cluster assuming style=filled, or a filled arrowhead.
@@ -1250,7 +1854,7 @@ pub trait NodeAttributes<'a> {
     /// If false, the size of a node is determined by smallest width and height needed
     /// to contain its label and image, if any, with a margin specified by the margin attribute.
     fn fixed_size(&mut self, fixed_size: bool) -> &mut Self {
-        self.add_attribute("fixedsize", AttributeText::from(fixed_size))
+        self.add_attribute(AttributeName::FixedSize, AttributeText::from(fixed_size))
     }
 
     /// Color used for text.
@@ -1281,7 +1885,7 @@ pub trait NodeAttributes<'a> {
     /// If the end points of an edge belong to the same group, i.e., have the same group attribute,
     /// parameters are set to avoid crossings and keep the edges straight.
     fn group(&mut self, group: String) -> &mut Self {
-        self.add_attribute("group", AttributeText::attr(group))
+        self.add_attribute(AttributeName::Group, AttributeText::attr(group))
     }
 
     /// Height of node, in inches.
@@ -1290,7 +1894,7 @@ pub trait NodeAttributes<'a> {
         if height < 0.02 {
             self.add_validation_error("height", "Must be greater than or equal to 0.02")
         }
-        self.add_attribute("height", AttributeText::from(height))
+        self.add_attribute(AttributeName::Height, AttributeText::from(height))
     }
 
     /// Gives the name of a file containing an image to be displayed inside a node.
@@ -1298,28 +1902,28 @@ pub trait NodeAttributes<'a> {
     /// typically JPEG, PNG, GIF, BMP, SVG, or Postscript, and be able to be converted
     /// into the desired output format.
     fn image(&mut self, image: String) -> &mut Self {
-        self.add_attribute("image", AttributeText::quoted(image))
+        self.add_attribute(AttributeName::Image, AttributeText::quoted(image))
     }
 
     /// Controls how an image is positioned within its containing node.
     /// Only has an effect when the image is smaller than the containing node.
     fn image_pos(&mut self, image_pos: ImagePosition) -> &mut Self {
-        self.add_attribute("imagepos", AttributeText::from(image_pos))
+        self.add_attribute(AttributeName::ImagePos, AttributeText::from(image_pos))
     }
 
     /// Controls how an image fills its containing node.
     fn image_scale_bool(&mut self, image_scale: bool) -> &mut Self {
-        self.add_attribute("imagescale", AttributeText::from(image_scale))
+        self.add_attribute(AttributeName::ImageScale, AttributeText::from(image_scale))
     }
 
     /// Controls how an image fills its containing node.
     fn image_scale(&mut self, image_scale: ImageScale) -> &mut Self {
-        self.add_attribute("imagescale", AttributeText::from(image_scale))
+        self.add_attribute(AttributeName::ImageScale, AttributeText::from(image_scale))
     }
 
     /// Text label attached to objects.
     fn label<S: Into<Cow<'a, str>>>(&mut self, text: S) -> &mut Self {
-        self.add_attribute("label", AttributeText::quoted(text))
+        self.add_attribute(AttributeName::Label, AttributeText::quoted(text))
     }
 
     // Vertical placement of labels for nodes, root graphs and clusters.
@@ -1339,7 +1943,7 @@ pub trait NodeAttributes<'a> {
     }
 
     /// Specifies layers in which the node, edge or cluster is present.
-    fn layer(&mut self, layer: String) -> &mut Self {
+    fn layer(&mut self, layer: LayerRange) -> &mut Self {
         Attributes::layer(self.get_attributes_mut(), layer);
         self
     }
@@ -1403,7 +2007,7 @@ pub trait NodeAttributes<'a> {
     /// Used only if rotate is not defined.
     /// Default: 0.0 and minimum: 360.0
     fn orientation(&mut self, orientation: f32) -> &mut Self {
-        if orientation < 0.0 || orientation > 360.0 {
+        if !(0.0..=360.0).contains(&orientation) {
             self.add_validation_error("orientation", "Must be between 0 and 360")
         }
         Attributes::orientation(self.get_attributes_mut(), orientation);
@@ -1420,7 +2024,14 @@ pub trait NodeAttributes<'a> {
 
     /// Set number of peripheries used in polygonal shapes and cluster boundaries.
     fn peripheries(&mut self, peripheries: u32) -> &mut Self {
-        self.add_attribute("penwidth", AttributeText::from(peripheries))
+        self.add_attribute(AttributeName::Peripheries, AttributeText::from(peripheries))
+    }
+
+    /// If true and the node has a pos attribute on input, neato or fdp prevents the
+    /// node from moving from the input position.
+    /// default: false
+    fn pin(&mut self, pin: bool) -> &mut Self {
+        self.add_attribute(AttributeName::Pin, AttributeText::from(pin))
     }
 
     /// Position of node, or spline control points.
@@ -1434,23 +2045,32 @@ pub trait NodeAttributes<'a> {
 
     /// Rectangles for fields of records, in points.
     fn rects(&mut self, rect: Rectangle) -> &mut Self {
-        self.add_attribute("rects", AttributeText::from(rect))
+        self.add_attribute(AttributeName::Rects, AttributeText::from(rect))
     }
 
     /// If true, force polygon to be regular, i.e., the vertices of the polygon will
     /// lie on a circle whose center is the center of the node.
     fn regular(&mut self, regular: bool) -> &mut Self {
-        self.add_attribute("regular", AttributeText::from(regular))
+        self.add_attribute(AttributeName::Regular, AttributeText::from(regular))
+    }
+
+    /// Specifies that the node should be used as the center of the layout.
+    /// Used by circo and twopi.
+    fn root(&mut self, root: bool) -> &mut Self {
+        self.add_attribute(AttributeName::Root, AttributeText::from(root))
     }
 
     /// Gives the number of points used for a circle/ellipse node.
     fn sample_points(&mut self, sample_points: u32) -> &mut Self {
-        self.add_attribute("samplepoints", AttributeText::from(sample_points))
+        self.add_attribute(
+            AttributeName::SamplePoints,
+            AttributeText::from(sample_points),
+        )
     }
 
     /// Sets the shape of a node.
     fn shape(&mut self, shape: Shape) -> &mut Self {
-        self.add_attribute("shape", AttributeText::from(shape))
+        self.add_attribute(AttributeName::Shape, AttributeText::from(shape))
     }
 
     /// Print guide boxes in PostScript at the beginning of routesplines if
@@ -1464,7 +2084,7 @@ pub trait NodeAttributes<'a> {
 
     /// Number of sides when shape=polygon.
     fn sides(&mut self, sides: u32) -> &mut Self {
-        self.add_attribute("sides", AttributeText::from(sides))
+        self.add_attribute(AttributeName::Sides, AttributeText::from(sides))
     }
 
     /// Skew factor for shape=polygon.
@@ -1474,7 +2094,7 @@ pub trait NodeAttributes<'a> {
         if skew < -100.0 {
             self.add_validation_error("skew", "Must be greater than or equal to -100")
         }
-        self.add_attribute("skew", AttributeText::from(skew))
+        self.add_attribute(AttributeName::Skew, AttributeText::from(skew))
     }
 
     /// If packmode indicates an array packing, sortv specifies an insertion order
@@ -1491,6 +2111,11 @@ pub trait NodeAttributes<'a> {
         self
     }
 
+    /// Set a list of styles, such as `filled,rounded`.
+    fn styles(&mut self, styles: NodeStyleList) -> &mut Self {
+        self.add_attribute(AttributeName::Style, AttributeText::from(styles))
+    }
+
     /// If the object has a URL, this attribute determines which window of the
     /// browser is used for the URL.
     fn target(&mut self, target: String) -> &mut Self {
@@ -1518,7 +2143,7 @@ pub trait NodeAttributes<'a> {
     /// Sets the coordinates of the vertices of the node’s polygon, in inches.
     /// A list of points, separated by spaces.
     fn vertices(&mut self, vertices: String) -> &mut Self {
-        self.add_attribute("vertices", AttributeText::quoted(vertices))
+        self.add_attribute(AttributeName::Vertices, AttributeText::quoted(vertices))
     }
 
     /// Width of node, in inches.
@@ -1527,7 +2152,7 @@ pub trait NodeAttributes<'a> {
     /// Otherwise, if the node label requires more width to fit, the node’s
     /// width will be increased to contain the label.
     fn width(&mut self, width: f32) -> &mut Self {
-        self.add_attribute("width", AttributeText::from(width))
+        self.add_attribute(AttributeName::Width, AttributeText::from(width))
     }
 
     /// External label for a node or edge.
@@ -1549,19 +2174,19 @@ pub trait NodeAttributes<'a> {
     }
 
     /// Add an attribute to the node.
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self;
 
     /// Add multiple attribures to the node.
-    fn add_attributes(
+    fn add_attributes<K: Into<AttributeName>>(
         &'a mut self,
-        attributes: HashMap<String, AttributeText<'a>>,
-    ) -> &mut Self;
+        attributes: HashMap<K, AttributeText<'a>>,
+    ) -> &'a mut Self;
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>>;
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>>;
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str);
 }
@@ -1570,7 +2195,7 @@ pub trait EdgeAttributes<'a> {
     /// Style of arrowhead on the head node of an edge.
     /// This will only appear if the dir attribute is forward or both.
     fn arrow_head(&mut self, arrowhead: ArrowType) -> &mut Self {
-        self.add_attribute("arrowhead", AttributeText::from(arrowhead))
+        self.add_attribute(AttributeName::ArrowHead, AttributeText::from(arrowhead))
     }
 
     /// Multiplicative scale factor for arrowheads.
@@ -1579,13 +2204,13 @@ pub trait EdgeAttributes<'a> {
         if arrow_size < 0.0 {
             self.add_validation_error("arrowsize", "Must be greater than or equal to 0")
         }
-        self.add_attribute("arrowsize", AttributeText::from(arrow_size))
+        self.add_attribute(AttributeName::ArrowSize, AttributeText::from(arrow_size))
     }
 
     /// Style of arrowhead on the tail node of an edge.
     /// This will only appear if the dir attribute is back or both.
     fn arrow_tail(&mut self, arrow_tail: ArrowType) -> &mut Self {
-        self.add_attribute("arrowtail", AttributeText::from(arrow_tail))
+        self.add_attribute(AttributeName::ArrowTail, AttributeText::from(arrow_tail))
     }
 
     /// Classnames to attach to the edge’s SVG element.
@@ -1628,19 +2253,19 @@ pub trait EdgeAttributes<'a> {
 
     /// Comments are inserted into output. Device-dependent
     fn comment(&mut self, comment: String) -> &mut Self {
-        self.add_attribute("comment", AttributeText::attr(comment));
+        self.add_attribute(AttributeName::Comment, AttributeText::attr(comment));
         self
     }
 
     /// If false, the edge is not used in ranking the nodes.
     fn constraint(&mut self, constraint: bool) -> &mut Self {
-        self.add_attribute("constraint", AttributeText::from(constraint))
+        self.add_attribute(AttributeName::Constraint, AttributeText::from(constraint))
     }
 
     /// If true, attach edge label to edge by a 2-segment polyline, underlining the label,
     /// then going to the closest point of spline.
     fn decorate(&mut self, decorate: bool) -> &mut Self {
-        self.add_attribute("decorate", AttributeText::from(decorate))
+        self.add_attribute(AttributeName::Decorate, AttributeText::from(decorate))
     }
 
     /// Edge type for drawing arrowheads.
@@ -1648,7 +2273,7 @@ pub trait EdgeAttributes<'a> {
     /// The actual style of the arrowhead can be specified using the arrowhead
     /// and arrowtail attributes.
     fn dir(&mut self, dir: Direction) -> &mut Self {
-        self.add_attribute("dir", AttributeText::from(dir))
+        self.add_attribute(AttributeName::Dir, AttributeText::from(dir))
     }
 
     /// If the edge has a URL or edgeURL attribute, edgetarget determines which window
@@ -1656,13 +2281,19 @@ pub trait EdgeAttributes<'a> {
     /// Setting edgetarget=_graphviz will open a new window if it doesn’t already exist,
     /// or reuse it if it does.
     fn edge_target(&mut self, edge_target: String) -> &mut Self {
-        self.add_attribute("edgetarget", AttributeText::escaped(edge_target))
+        self.add_attribute(
+            AttributeName::EdgeTarget,
+            AttributeText::escaped(edge_target),
+        )
     }
 
     /// Tooltip annotation attached to the non-label part of an edge.
     /// Used only if the edge has a URL or edgeURL attribute.
     fn edge_tooltip(&mut self, edge_tooltip: String) -> &mut Self {
-        self.add_attribute("edgetooltip", AttributeText::escaped(edge_tooltip))
+        self.add_attribute(
+            AttributeName::EdgeTooltip,
+            AttributeText::escaped(edge_tooltip),
+        )
     }
 
     /// The link for the non-label parts of an edge.
@@ -1670,7 +2301,7 @@ pub trait EdgeAttributes<'a> {
     /// Also, edgeURL is used near the head or tail node unless overridden by
     /// headURL or tailURL, respectively.
     fn edge_url(&mut self, edge_url: String) -> &mut Self {
-        self.add_attribute("edgeurl", AttributeText::escaped(edge_url))
+        self.add_attribute(AttributeName::EdgeUrl, AttributeText::escaped(edge_url))
     }
 
     /// Color used to fill the background of a node or cluster assuming
@@ -1712,26 +2343,26 @@ pub trait EdgeAttributes<'a> {
 
     /// Position of an edge’s head label, in points. The position indicates the center of the label.
     fn head_lp(&mut self, head_lp: Point) -> &mut Self {
-        self.add_attribute("head_lp", AttributeText::from(head_lp))
+        self.add_attribute(AttributeName::HeadLp, AttributeText::from(head_lp))
     }
 
     /// If true, the head of an edge is clipped to the boundary of the head node;
     /// otherwise, the end of the edge goes to the center of the node, or the center
     /// of a port, if applicable.
     fn head_clip(&mut self, head_clip: bool) -> &mut Self {
-        self.add_attribute("headclip", AttributeText::from(head_clip))
+        self.add_attribute(AttributeName::HeadClip, AttributeText::from(head_clip))
     }
 
     /// Text label to be placed near head of edge.
     fn head_label(&mut self, head_label: String) -> &mut Self {
-        self.add_attribute("headlabel", AttributeText::quoted(head_label))
+        self.add_attribute(AttributeName::HeadLabel, AttributeText::quoted(head_label))
     }
 
     /// Indicates where on the head node to attach the head of the edge.
     /// In the default case, the edge is aimed towards the center of the node,
     /// and then clipped at the node boundary.
     fn head_port(&mut self, head_port: PortPosition) -> &mut Self {
-        self.add_attribute("headport", AttributeText::from(head_port))
+        self.add_attribute(AttributeName::HeadPort, AttributeText::from(head_port))
     }
 
     /// If the edge has a headURL, headtarget determines which window of the browser is used for the URL.
@@ -1739,19 +2370,25 @@ pub trait EdgeAttributes<'a> {
     /// or reuse the window if it does.
     /// If undefined, the value of the target is used.
     fn head_target(&mut self, head_target: String) -> &mut Self {
-        self.add_attribute("headtarget", AttributeText::escaped(head_target))
+        self.add_attribute(
+            AttributeName::HeadTarget,
+            AttributeText::escaped(head_target),
+        )
     }
 
     /// Tooltip annotation attached to the head of an edge.
     /// Used only if the edge has a headURL attribute.
     fn head_tooltip(&mut self, head_tooltip: String) -> &mut Self {
-        self.add_attribute("headtooltip", AttributeText::escaped(head_tooltip))
+        self.add_attribute(
+            AttributeName::HeadTooltip,
+            AttributeText::escaped(head_tooltip),
+        )
     }
 
     /// If defined, headURL is output as part of the head label of the edge.
     /// Also, this value is used near the head node, overriding any URL value.
     fn head_url(&mut self, head_url: String) -> &mut Self {
-        self.add_attribute("headURL", AttributeText::escaped(head_url))
+        self.add_attribute(AttributeName::HeadUrl, AttributeText::escaped(head_url))
     }
 
     /// An escString or an HTML label.
@@ -1774,31 +2411,40 @@ pub trait EdgeAttributes<'a> {
                 "Must be greater than or equal to -180",
             )
         }
-        self.add_attribute("labelangle", AttributeText::from(label_angle))
+        self.add_attribute(AttributeName::LabelAngle, AttributeText::from(label_angle))
     }
 
     /// Multiplicative scaling factor adjusting the distance that the headlabel / taillabel is from
     /// the head / tail node.
     /// default: 1.0, minimum: 0.0
     fn label_distance(&mut self, label_distance: f32) -> &mut Self {
-        self.add_attribute("labeldistance", AttributeText::from(label_distance))
+        self.add_attribute(
+            AttributeName::LabelDistance,
+            AttributeText::from(label_distance),
+        )
     }
 
     /// If true, allows edge labels to be less constrained in position.
     /// In particular, it may appear on top of other edges.
     fn label_float(&mut self, label_float: bool) -> &mut Self {
-        self.add_attribute("labelfloat", AttributeText::from(label_float))
+        self.add_attribute(AttributeName::LabelFloat, AttributeText::from(label_float))
     }
 
     /// Color used for headlabel and taillabel.
     fn label_font_color(&mut self, label_font_color: Color<'a>) -> &mut Self {
-        self.add_attribute("labelfontcolor", AttributeText::from(label_font_color))
+        self.add_attribute(
+            AttributeName::LabelFontColor,
+            AttributeText::from(label_font_color),
+        )
     }
 
     /// Font used for headlabel and taillabel.
     /// If not set, defaults to edge’s fontname.
     fn label_font_name(&mut self, label_font_name: String) -> &mut Self {
-        self.add_attribute("labelfontname", AttributeText::attr(label_font_name))
+        self.add_attribute(
+            AttributeName::LabelFontName,
+            AttributeText::attr(label_font_name),
+        )
     }
 
     /// Font size, in points, used for headlabel and taillabel.
@@ -1811,34 +2457,50 @@ pub trait EdgeAttributes<'a> {
                 "Must be greater than or equal to 1",
             )
         }
-        self.add_attribute("labelfontsize", AttributeText::from(label_font_size))
+        self.add_attribute(
+            AttributeName::LabelFontSize,
+            AttributeText::from(label_font_size),
+        )
     }
 
     /// If the edge has a URL or labelURL attribute, this attribute determines
     ///  which window of the browser is used for the URL attached to the label.
     fn label_target(&mut self, label_target: String) -> &mut Self {
-        self.add_attribute("labeltarget", AttributeText::escaped(label_target))
+        self.add_attribute(
+            AttributeName::LabelTarget,
+            AttributeText::escaped(label_target),
+        )
     }
 
     /// Tooltip annotation attached to label of an edge.
     /// Used only if the edge has a URL or labelURL attribute.
     fn label_tooltip(&mut self, label_tooltip: String) -> &mut Self {
-        self.add_attribute("labeltooltip", AttributeText::escaped(label_tooltip))
+        self.add_attribute(
+            AttributeName::LabelTooltip,
+            AttributeText::escaped(label_tooltip),
+        )
     }
 
     /// If defined, labelURL is the link used for the label of an edge.
     /// labelURL overrides any URL defined for the edge.
     fn label_url(&mut self, label_url: String) -> &mut Self {
-        self.add_attribute("labelurl", AttributeText::escaped(label_url))
+        self.add_attribute(AttributeName::LabelUrl, AttributeText::escaped(label_url))
     }
 
-    fn layer(&mut self, layer: String) -> &mut Self {
+    fn layer(&mut self, layer: LayerRange) -> &mut Self {
         Attributes::layer(self.get_attributes_mut(), layer);
         self
     }
 
+    /// Preferred edge length, in inches.
+    /// Used by fdp and neato.
+    /// default: 1.0 for neato, 0.3 for fdp
+    fn len(&mut self, len: f32) -> &mut Self {
+        self.add_attribute(AttributeName::Len, AttributeText::from(len))
+    }
+
     fn lhead(&mut self, lhead: String) -> &mut Self {
-        self.add_attribute("lhead", AttributeText::quoted(lhead))
+        self.add_attribute(AttributeName::LHead, AttributeText::quoted(lhead))
     }
 
     /// Label position
@@ -1852,16 +2514,16 @@ pub trait EdgeAttributes<'a> {
     /// When compound=true, if ltail is defined and is the name of a cluster
     /// containing the real tail, the edge is clipped to the boundary of the cluster.
     fn ltail(&mut self, ltail: String) -> &mut Self {
-        self.add_attribute("ltail", AttributeText::quoted(ltail))
+        self.add_attribute(AttributeName::LTail, AttributeText::quoted(ltail))
     }
 
     /// Minimum edge length (rank difference between head and tail).
     fn min_len(&mut self, min_len: u32) -> &mut Self {
-        self.add_attribute("minlen", AttributeText::from(min_len))
+        self.add_attribute(AttributeName::MinLen, AttributeText::from(min_len))
     }
 
     fn no_justify(&mut self, no_justify: bool) -> &mut Self {
-        self.add_attribute("nojustify", AttributeText::from(no_justify))
+        self.add_attribute(AttributeName::NoJustify, AttributeText::from(no_justify))
     }
 
     fn pen_width(&mut self, pen_width: f32) -> &mut Self {
@@ -1878,12 +2540,12 @@ pub trait EdgeAttributes<'a> {
 
     /// Edges with the same head and the same samehead value are aimed at the same point on the head.
     fn same_head(&mut self, same_head: String) -> &mut Self {
-        self.add_attribute("samehead", AttributeText::quoted(same_head))
+        self.add_attribute(AttributeName::SameHead, AttributeText::quoted(same_head))
     }
 
     /// Edges with the same tail and the same sametail value are aimed at the same point on the tail.
     fn same_tail(&mut self, same_tail: String) -> &mut Self {
-        self.add_attribute("sametail", AttributeText::quoted(same_tail))
+        self.add_attribute(AttributeName::SameTail, AttributeText::quoted(same_tail))
     }
 
     /// Print guide boxes in PostScript at the beginning of routesplines if showboxes=1, or at the
@@ -1901,47 +2563,58 @@ pub trait EdgeAttributes<'a> {
         self
     }
 
+    /// Set a list of styles, such as `filled,rounded`.
+    fn styles(&mut self, styles: EdgeStyleList) -> &mut Self {
+        self.add_attribute(AttributeName::Style, AttributeText::from(styles))
+    }
+
     /// Position of an edge’s tail label, in points.
     /// The position indicates the center of the label.
     fn tail_lp(&mut self, tail_lp: Point) -> &mut Self {
-        self.add_attribute("tail_lp", AttributeText::from(tail_lp))
+        self.add_attribute(AttributeName::TailLp, AttributeText::from(tail_lp))
     }
 
     /// If true, the tail of an edge is clipped to the boundary of the tail node; otherwise,
     /// the end of the edge goes to the center of the node, or the center of a port, if applicable.
     fn tail_clip(&mut self, tail_clip: bool) -> &mut Self {
-        self.add_attribute("tailclip", AttributeText::from(tail_clip))
+        self.add_attribute(AttributeName::TailClip, AttributeText::from(tail_clip))
     }
 
     /// Text label to be placed near tail of edge.
     fn tail_label(&mut self, tail_label: String) -> &mut Self {
-        self.add_attribute("taillabel", AttributeText::quoted(tail_label))
+        self.add_attribute(AttributeName::TailLabel, AttributeText::quoted(tail_label))
     }
 
     /// Indicates where on the tail node to attach the tail of the edge.
     fn tail_port(&mut self, tail_port: PortPosition) -> &mut Self {
-        self.add_attribute("tailport", AttributeText::from(tail_port))
+        self.add_attribute(AttributeName::TailPort, AttributeText::from(tail_port))
     }
 
     /// If the edge has a tailURL, tailtarget determines which window of the browser is used for the URL.
     fn tail_target(&mut self, tail_target: String) -> &mut Self {
-        self.add_attribute("tailtarget", AttributeText::escaped(tail_target))
+        self.add_attribute(
+            AttributeName::TailTarget,
+            AttributeText::escaped(tail_target),
+        )
     }
 
     /// Tooltip annotation attached to the tail of an edge.
     fn tail_tooltip(&mut self, tail_tooltip: String) -> &mut Self {
-        self.add_attribute("tailtooltip", AttributeText::escaped(tail_tooltip))
+        self.add_attribute(
+            AttributeName::TailTooltip,
+            AttributeText::escaped(tail_tooltip),
+        )
     }
 
     /// If defined, tailURL is output as part of the tail label of the edge.
     /// Also, this value is used near the tail node, overriding any URL value.
     fn tail_url(&mut self, tail_url: String) -> &mut Self {
-        self.add_attribute("tailURL", AttributeText::escaped(tail_url))
+        self.add_attribute(AttributeName::TailUrl, AttributeText::escaped(tail_url))
     }
 
     /// If the object has a URL, this attribute determines which window of the browser is used for the URL.
     fn target(&mut self, target: String) -> &mut Self {
-        self.add_attribute("target", AttributeText::escaped(target))
+        self.add_attribute(AttributeName::Target, AttributeText::escaped(target))
     }
 
     /// Tooltip annotation attached to the node or edge.
@@ -1964,7 +2637,7 @@ pub trait EdgeAttributes<'a> {
     /// The heavier the weight, the shorter, straighter and more vertical the edge is.
     /// default: 1, minimum: 0
     fn weight(&mut self, weight: u32) -> &mut Self {
-        self.add_attribute("weight", AttributeText::from(weight))
+        self.add_attribute(AttributeName::Weight, AttributeText::from(weight))
     }
 
     /// External label for a node or edge.
@@ -1985,13 +2658,13 @@ pub trait EdgeAttributes<'a> {
         self
     }
 
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self;
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>>;
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>>;
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str);
 }
@@ -2000,28 +2673,37 @@ pub trait EdgeAttributes<'a> {
 // non-alphanumerical characters, as escape double quotes within those strings.
 // This probably needs to be more robust but I think for now it fixes a but around double-quoted
 // strings
-fn escape_double_quotes(val: &String) -> String {
-    format!("{}", val.chars().map(
-        |c| if c == '"' {
-            format!("\\{}", c)
-        } else {
-            format!("{}", c)
-        }).collect::<String>()
-    )
+fn escape_double_quotes(val: &str) -> String {
+    val.chars()
+        .map(|c| {
+            if c == '"' {
+                format!("\\{}", c)
+            } else {
+                format!("{}", c)
+            }
+        })
+        .collect::<String>()
 }
 
-fn is_alphanum(val: &String) -> bool {
+fn is_alphanum(val: &str) -> bool {
     for byte in val.bytes() {
-        if !((byte >= b'a' && byte <= b'z') || (byte >= b'A' && byte <= b'Z') ||
-            (byte >= b'0' && byte <= b'9') || byte == b'_' || byte >= 128)
-        {
+        if !(byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 128) {
             return false;
         }
     }
     true
 }
 
-pub(crate) fn fmt_attributes(attributes: &IndexMap<String, AttributeText>) -> String {
+/// DOT keywords, which can only be used as ids when quoted.
+fn is_keyword(val: &str) -> bool {
+    ["node", "edge", "graph", "digraph", "subgraph", "strict"]
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(val))
+}
+
+pub(crate) fn fmt_attributes(
+    attributes: &IndexMap<AttributeName, AttributeText>,
+) -> String {
     let mut dot_string = String::from("");
     if !attributes.is_empty() {
         dot_string.push_str(" [");
@@ -2032,7 +2714,7 @@ pub(crate) fn fmt_attributes(attributes: &IndexMap<String, AttributeText>) -> St
             dot_string.push_str(", ");
             dot_string.push_str(format!("{}={}", key, value.dot_string()).as_str());
         }
-        dot_string.push_str("]");
+        dot_string.push(']');
     }
 
     dot_string
@@ -2041,8 +2723,8 @@ pub(crate) fn fmt_attributes(attributes: &IndexMap<String, AttributeText>) -> St
 #[cfg(test)]
 mod test {
     use crate::attributes::{
-        fmt_attributes, AttributeText, Color, GraphAttributeStatementBuilder,
-        GraphAttributes,
+        fmt_attributes, AttributeName, AttributeText, Color,
+        GraphAttributeStatementBuilder, GraphAttributes,
     };
     use indexmap::map::IndexMap;
 
@@ -2055,7 +2737,10 @@ mod test {
             ])
             .build()
             .unwrap();
-        println!("{}", graph_attributes.get("fillcolor").unwrap().dot_string());
+        println!(
+            "{}",
+            graph_attributes.get("fillcolor").unwrap().dot_string()
+        );
         assert_eq!(
             graph_attributes.get("fillcolor").unwrap().dot_string(),
             "\"yellow;0.3:blue\""
@@ -2070,7 +2755,7 @@ mod test {
     #[test]
     fn fmt_attributes_with_single_attribute() {
         let mut attributes = IndexMap::new();
-        attributes.insert("color".to_string(), AttributeText::attr("red"));
+        attributes.insert(AttributeName::Color, AttributeText::attr("red"));
 
         assert_eq!(fmt_attributes(&attributes), " [color=red]");
     }
@@ -2078,8 +2763,8 @@ mod test {
     #[test]
     fn fmt_attributes_with_attributes() {
         let mut attributes = IndexMap::new();
-        attributes.insert("color".to_string(), AttributeText::attr("red"));
-        attributes.insert("size".to_string(), AttributeText::attr("2"));
+        attributes.insert(AttributeName::Color, AttributeText::attr("red"));
+        attributes.insert(AttributeName::Size, AttributeText::attr("2"));
 
         assert_eq!(fmt_attributes(&attributes), " [color=red, size=2]");
     }
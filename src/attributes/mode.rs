@@ -0,0 +1,56 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Technique for optimizing the layout.
+///
+/// Used by neato and sfdp.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Mode {
+    /// Stress majorization. The default for neato.
+    Major,
+    /// Gradient descent, as used by Kamada-Kawai.
+    KK,
+    /// Stochastic gradient descent.
+    Sgd,
+    /// Adds a top-down directionality similar to the layout used in dot. neato only.
+    Hier,
+    /// Like `Hier` but uses quadratic optimization to enforce directionality. neato only.
+    Ipsep,
+    /// Spring-electrical model. The default for sfdp.
+    Spring,
+    /// Stress majorization with a maximum entropy term. sfdp only.
+    Maxent,
+}
+
+impl<'a> DotString<'a> for Mode {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            Mode::Major => "major".into(),
+            Mode::KK => "KK".into(),
+            Mode::Sgd => "sgd".into(),
+            Mode::Hier => "hier".into(),
+            Mode::Ipsep => "ipsep".into(),
+            Mode::Spring => "spring".into(),
+            Mode::Maxent => "maxent".into(),
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "major" => Ok(Mode::Major),
+            "KK" => Ok(Mode::KK),
+            "sgd" => Ok(Mode::Sgd),
+            "hier" => Ok(Mode::Hier),
+            "ipsep" => Ok(Mode::Ipsep),
+            "spring" => Ok(Mode::Spring),
+            "maxent" => Ok(Mode::Maxent),
+            _ => Err(ParseAttributeError::new("Mode", s)),
+        }
+    }
+}
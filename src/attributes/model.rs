@@ -0,0 +1,43 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// How neato computes the distance matrix used as the ideal node distances.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Model {
+    /// Shortest path lengths between nodes. This is the default.
+    ShortPath,
+    /// Uses the circuit resistance model. This is useful for graphs with many small cycles.
+    Circuit,
+    /// Uses the subset model, which sets the edge length to be the number of nodes
+    /// that are neighbors of exactly one of the end points.
+    Subset,
+    /// Uses edge lengths given by the len attribute and otherwise the default (1.0).
+    Mds,
+}
+
+impl<'a> DotString<'a> for Model {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            Model::ShortPath => "shortpath".into(),
+            Model::Circuit => "circuit".into(),
+            Model::Subset => "subset".into(),
+            Model::Mds => "mds".into(),
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shortpath" => Ok(Model::ShortPath),
+            "circuit" => Ok(Model::Circuit),
+            "subset" => Ok(Model::Subset),
+            "mds" => Ok(Model::Mds),
+            _ => Err(ParseAttributeError::new("Model", s)),
+        }
+    }
+}
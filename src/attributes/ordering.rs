@@ -1,5 +1,7 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// If out, then the outedges of a node, that is, edges with the node as its tail node,
 /// must appear left-to-right in the same order in which they are defined in the input.
@@ -11,6 +13,7 @@ use std::borrow::Cow;
 /// or subgraph.
 ///
 /// Note that the graph attribute takes precedence over the node attribute.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Ordering {
     In,
     Out,
@@ -24,3 +27,15 @@ impl<'a> DotString<'a> for Ordering {
         }
     }
 }
+
+impl FromStr for Ordering {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "in" => Ok(Ordering::In),
+            "out" => Ok(Ordering::Out),
+            _ => Err(ParseAttributeError::new("Ordering", s)),
+        }
+    }
+}
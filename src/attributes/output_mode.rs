@@ -1,5 +1,7 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// These specify the order in which nodes and edges are drawn in concrete output.
 ///
@@ -12,6 +14,7 @@ use std::borrow::Cow;
 /// On the other hand, usually for aesthetic reasons, it may be desirable that all edges appear
 /// beneath nodes, even if the resulting drawing is ambiguous.
 /// This can be achieved by choosing "edgesfirst".
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum OutputMode {
     BreadthFirst,
     NodesFirst,
@@ -27,3 +30,16 @@ impl<'a> DotString<'a> for OutputMode {
         }
     }
 }
+
+impl FromStr for OutputMode {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "breadthfirst" => Ok(OutputMode::BreadthFirst),
+            "nodesfirst" => Ok(OutputMode::NodesFirst),
+            "edgesfirst" => Ok(OutputMode::EdgesFirst),
+            _ => Err(ParseAttributeError::new("OutputMode", s)),
+        }
+    }
+}
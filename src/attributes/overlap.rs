@@ -0,0 +1,113 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Determines if and how node overlaps should be removed.
+/// Nodes are first enlarged using the sep attribute.
+///
+/// Not used by dot.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Overlap {
+    /// Overlaps are retained.
+    Retain,
+    /// Overlaps are removed using the default technique, the Prism algorithm
+    /// when available and Voronoi otherwise.
+    Remove,
+    /// Voronoi-based technique.
+    Voronoi,
+    /// Prism, a proximity graph-based algorithm.
+    /// The optional value gives the number of attempts to remove overlaps; 0 runs only
+    /// the proximity graph scaling phase.
+    Prism(Option<u32>),
+    /// Overlaps are removed by uniformly scaling in x and y.
+    Scale,
+    /// x and y are separately scaled to remove overlaps.
+    ScaleXY,
+    /// Scales the layout down as much as possible without introducing any overlaps,
+    /// obviously assuming there are none to begin with.
+    Compress,
+    /// Uses quadratic optimization to minimize node displacement while removing overlaps.
+    Vpsc,
+    /// Uses vpsc with separation constraints. neato only.
+    Ipsep,
+    /// Orthogonal ordering constraints are preserved while removing node overlaps.
+    Ortho,
+    /// As with `Ortho` but initially only moves nodes in the x direction.
+    OrthoXY,
+    /// As with `Ortho` but initially only moves nodes in the y direction.
+    OrthoYX,
+    /// Pseudo-orthogonal ordering constraints are preserved.
+    POrtho,
+    /// As with `POrtho` but initially only moves nodes in the y direction.
+    POrthoYX,
+}
+
+impl<'a> DotString<'a> for Overlap {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            Overlap::Retain => "true".into(),
+            Overlap::Remove => "false".into(),
+            Overlap::Voronoi => "voronoi".into(),
+            Overlap::Prism(None) => "prism".into(),
+            Overlap::Prism(Some(attempts)) => format!("prism{}", attempts).into(),
+            Overlap::Scale => "scale".into(),
+            Overlap::ScaleXY => "scalexy".into(),
+            Overlap::Compress => "compress".into(),
+            Overlap::Vpsc => "vpsc".into(),
+            Overlap::Ipsep => "ipsep".into(),
+            Overlap::Ortho => "ortho".into(),
+            Overlap::OrthoXY => "orthoxy".into(),
+            Overlap::OrthoYX => "orthoyx".into(),
+            Overlap::POrtho => "portho".into(),
+            Overlap::POrthoYX => "porthoyx".into(),
+        }
+    }
+}
+
+impl FromStr for Overlap {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "true" => Ok(Overlap::Retain),
+            "false" => Ok(Overlap::Remove),
+            "voronoi" => Ok(Overlap::Voronoi),
+            "prism" => Ok(Overlap::Prism(None)),
+            "scale" => Ok(Overlap::Scale),
+            "scalexy" => Ok(Overlap::ScaleXY),
+            "compress" => Ok(Overlap::Compress),
+            "vpsc" => Ok(Overlap::Vpsc),
+            "ipsep" => Ok(Overlap::Ipsep),
+            "ortho" => Ok(Overlap::Ortho),
+            "orthoxy" => Ok(Overlap::OrthoXY),
+            "orthoyx" => Ok(Overlap::OrthoYX),
+            "portho" => Ok(Overlap::POrtho),
+            "porthoyx" => Ok(Overlap::POrthoYX),
+            _ => s
+                .strip_prefix("prism")
+                .and_then(|attempts| attempts.parse().ok())
+                .map(|attempts| Overlap::Prism(Some(attempts)))
+                .ok_or_else(|| ParseAttributeError::new("Overlap", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::Overlap;
+    use crate::DotString;
+
+    #[test]
+    fn prism_dot_string() {
+        assert_eq!("prism", Overlap::Prism(None).dot_string());
+        assert_eq!("prism1000", Overlap::Prism(Some(1000)).dot_string());
+    }
+
+    #[test]
+    fn prism_from_str() {
+        assert_eq!(Ok(Overlap::Prism(None)), "prism".parse());
+        assert_eq!(Ok(Overlap::Prism(Some(1000))), "prism1000".parse());
+        assert!("prismx".parse::<Overlap>().is_err());
+    }
+}
@@ -1,8 +1,11 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// The modes "node", "clust" or "graph" specify that the components should be packed together
 /// tightly, using the specified granularity.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum PackMode {
     /// causes packing at the node and edge level, with no overlapping of these objects.
     /// This produces a layout with the least area, but it also allows interleaving,
@@ -28,3 +31,16 @@ impl<'a> DotString<'a> for PackMode {
         }
     }
 }
+
+impl FromStr for PackMode {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "node" => Ok(PackMode::Node),
+            "clust" => Ok(PackMode::Cluster),
+            "graph" => Ok(PackMode::Graph),
+            _ => Err(ParseAttributeError::new("PackMode", s)),
+        }
+    }
+}
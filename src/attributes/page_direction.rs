@@ -1,11 +1,14 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// These specify the 8 row or column major orders for traversing a rectangular array,
 /// the first character corresponding to the major order and the second to the minor order.
 /// Thus, for “BL”, the major order is from bottom to top, and the minor order is from left to right.
 /// This means the bottom row is traversed first, from left to right, then the next row up,
 /// from left to right, and so on, until the topmost row is traversed
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum PageDirection {
     BottomLeft,
     BottomRight,
@@ -31,3 +34,21 @@ impl<'a> DotString<'a> for PageDirection {
         }
     }
 }
+
+impl FromStr for PageDirection {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BL" => Ok(PageDirection::BottomLeft),
+            "BR" => Ok(PageDirection::BottomRight),
+            "TL" => Ok(PageDirection::TopLeft),
+            "TR" => Ok(PageDirection::TopRight),
+            "RB" => Ok(PageDirection::RightBottom),
+            "RT" => Ok(PageDirection::RightTop),
+            "LB" => Ok(PageDirection::LeftBottom),
+            "LT" => Ok(PageDirection::LeftTop),
+            _ => Err(ParseAttributeError::new("PageDirection", s)),
+        }
+    }
+}
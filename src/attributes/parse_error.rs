@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Error returned when an attribute value can not be parsed into its typed representation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseAttributeError {
+    /// Name of the type the value was parsed as.
+    pub type_name: &'static str,
+
+    /// The value which failed to parse.
+    pub value: String,
+}
+
+impl ParseAttributeError {
+    pub fn new<S: Into<String>>(type_name: &'static str, value: S) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+        }
+    }
+}
+
+impl Display for ParseAttributeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid {} value \"{}\"", self.type_name, self.value)
+    }
+}
+
+impl Error for ParseAttributeError {}
@@ -1,6 +1,9 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -27,16 +30,38 @@ impl Point {
 impl<'a> DotString<'a> for Point {
     fn dot_string(&self) -> Cow<'a, str> {
         let mut slice = format!("{:.1},{:.1}", self.x, self.y);
-        if self.z.is_some() {
-            slice.push_str(format!(",{:.1}", self.z.unwrap()).as_str());
+        if let Some(z) = self.z {
+            slice.push_str(format!(",{:.1}", z).as_str());
         }
         if self.force_pos {
-            slice.push_str("!")
+            slice.push('!')
         }
         slice.into()
     }
 }
 
+impl FromStr for Point {
+    type Err = ParseAttributeError;
+
+    /// Parses `x,y` or `x,y,z`, optionally followed by `!`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAttributeError::new("Point", s);
+        let (coordinates, force_pos) = match s.strip_suffix('!') {
+            Some(coordinates) => (coordinates, true),
+            None => (s, false),
+        };
+        let coordinates = coordinates
+            .split(',')
+            .map(|c| c.trim().parse::<f32>().map_err(|_| error()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match coordinates[..] {
+            [x, y] => Ok(Point::new(x, y, None, force_pos)),
+            [x, y, z] => Ok(Point::new(x, y, Some(z), force_pos)),
+            _ => Err(error()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::attributes::Point;
@@ -52,4 +77,13 @@ mod test {
             Point::new(1.0, 2.0, Some(0.0), true).dot_string()
         );
     }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(Point::new_2d(1.0, 2.0)), "1,2".parse());
+        assert_eq!(Ok(Point::new_3d(1.0, 2.0, 0.5)), "1,2,0.5".parse());
+        assert_eq!(Ok(Point::new(1.0, 2.0, None, true)), "1.0,2.0!".parse());
+        assert!("1".parse::<Point>().is_err());
+        assert!("1,2,3,4".parse::<Point>().is_err());
+    }
 }
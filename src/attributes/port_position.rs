@@ -1,6 +1,8 @@
 use crate::attributes::compass_point::CompassPoint;
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// Modifier indicating where on a node an edge should be aimed.
 /// If Port is used, the corresponding node must either have record shape with one of its
@@ -30,7 +32,35 @@ impl<'a> DotString<'a> for PortPosition {
                 }
                 dot_string.into()
             }
-            PortPosition::Compass(p) => p.dot_string().into(),
+            PortPosition::Compass(p) => p.dot_string(),
+        }
+    }
+}
+
+impl FromStr for PortPosition {
+    type Err = ParseAttributeError;
+
+    /// Compass point names are parsed as compass points, anything else as a port name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseAttributeError::new("PortPosition", s));
+        }
+        if let Ok(compass_point) = s.parse() {
+            return Ok(PortPosition::Compass(compass_point));
+        }
+        match s.rsplit_once(':') {
+            Some((port_name, compass_point))
+                if compass_point.parse::<CompassPoint>().is_ok() =>
+            {
+                Ok(PortPosition::Port {
+                    port_name: port_name.to_string(),
+                    compass_point: compass_point.parse().ok(),
+                })
+            }
+            _ => Ok(PortPosition::Port {
+                port_name: s.to_string(),
+                compass_point: None,
+            }),
         }
     }
 }
@@ -64,4 +94,26 @@ mod test {
     fn compass_dot_string() {
         assert_eq!("ne", PortPosition::Compass(CompassPoint::NE).dot_string());
     }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            Ok(PortPosition::Compass(CompassPoint::NE)),
+            "ne".parse::<PortPosition>()
+        );
+        assert_eq!(
+            Ok(PortPosition::Port {
+                port_name: "port_0".to_string(),
+                compass_point: Some(CompassPoint::NE)
+            }),
+            "port_0:ne".parse::<PortPosition>()
+        );
+        assert_eq!(
+            Ok(PortPosition::Port {
+                port_name: "port_0".to_string(),
+                compass_point: None
+            }),
+            "port_0".parse::<PortPosition>()
+        );
+    }
 }
@@ -0,0 +1,38 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Quadtree scheme used by sfdp to approximate repulsive forces.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum QuadType {
+    /// The default. Gives better quality layouts.
+    Normal,
+    /// Faster but lower quality.
+    Fast,
+    /// Disables the quadtree scheme.
+    None,
+}
+
+impl<'a> DotString<'a> for QuadType {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            QuadType::Normal => "normal".into(),
+            QuadType::Fast => "fast".into(),
+            QuadType::None => "none".into(),
+        }
+    }
+}
+
+impl FromStr for QuadType {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(QuadType::Normal),
+            "fast" => Ok(QuadType::Fast),
+            "none" => Ok(QuadType::None),
+            _ => Err(ParseAttributeError::new("QuadType", s)),
+        }
+    }
+}
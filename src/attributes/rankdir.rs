@@ -1,8 +1,11 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// Corresponding to directed graphs drawn from top to bottom, from left to right,
 /// from bottom to top, and from right to left, respectively.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum RankDir {
     TopBottom,
     LeftRight,
@@ -20,3 +23,17 @@ impl<'a> DotString<'a> for RankDir {
         }
     }
 }
+
+impl FromStr for RankDir {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TB" => Ok(RankDir::TopBottom),
+            "LR" => Ok(RankDir::LeftRight),
+            "BT" => Ok(RankDir::BottomTop),
+            "RL" => Ok(RankDir::RightLeft),
+            _ => Err(ParseAttributeError::new("RankDir", s)),
+        }
+    }
+}
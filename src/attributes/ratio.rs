@@ -1,10 +1,13 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// Sets the aspect ratio (drawing height/drawing width) for the drawing.
 /// Note that this is adjusted before the size attribute constraints are enforced.
 /// In addition, the calculations usually ignore the node sizes, so the final drawing size may only
 /// approximate what is desired.
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Ratio {
     /// Ratio is taken as the desired aspect ratio.
     /// Then, if the actual aspect ratio is less than the desired ratio, the drawing height is
@@ -48,3 +51,20 @@ impl<'a> DotString<'a> for Ratio {
         }
     }
 }
+
+impl FromStr for Ratio {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fill" => Ok(Ratio::Fill),
+            "compress" => Ok(Ratio::Compress),
+            "expand" => Ok(Ratio::Expand),
+            "auto" => Ok(Ratio::Auto),
+            _ => s
+                .parse()
+                .map(Ratio::Aspect)
+                .map_err(|_| ParseAttributeError::new("Ratio", s)),
+        }
+    }
+}
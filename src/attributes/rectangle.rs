@@ -1,7 +1,10 @@
 use crate::attributes::point::Point;
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Rectangle {
     lower_left: Point,
     upper_right: Point,
@@ -17,6 +20,26 @@ impl<'a> DotString<'a> for Rectangle {
     }
 }
 
+impl FromStr for Rectangle {
+    type Err = ParseAttributeError;
+
+    /// Parses `llx,lly,urx,ury`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s
+            .split(',')
+            .map(|c| c.trim().parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseAttributeError::new("Rectangle", s))?;
+        match coordinates[..] {
+            [llx, lly, urx, ury] => Ok(Rectangle {
+                lower_left: Point::new_2d(llx, lly),
+                upper_right: Point::new_2d(urx, ury),
+            }),
+            _ => Err(ParseAttributeError::new("Rectangle", s)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::attributes::{Point, Rectangle};
@@ -33,4 +56,11 @@ mod test {
             .dot_string()
         );
     }
+
+    #[test]
+    fn from_str() {
+        let rectangle: Rectangle = "0,0,1,1".parse().unwrap();
+        assert_eq!("0.0,0.0,1.0,1.0", rectangle.dot_string());
+        assert!("0,0,1".parse::<Rectangle>().is_err());
+    }
 }
@@ -1,6 +1,9 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Shape {
     Box,
     Polygon,
@@ -51,17 +54,45 @@ pub enum Shape {
     FivePoverHang,
     ThreePoverHang,
     NoverHang,
-    Assemply,
+    Assembly,
     Signature,
     Insulator,
     Ribosite,
     Rnastab,
     Proteasesite,
     Proteinstab,
-    Rpromotor,
+    Rpromoter,
     Rarrow,
     Larrow,
-    Lpromotor,
+    Lpromoter,
+}
+
+#[allow(non_upper_case_globals)]
+impl Shape {
+    #[deprecated(note = "misspelling of `Shape::Assembly`")]
+    pub const Assemply: Shape = Shape::Assembly;
+
+    #[deprecated(note = "misspelling of `Shape::Rpromoter`")]
+    pub const Rpromotor: Shape = Shape::Rpromoter;
+
+    #[deprecated(note = "misspelling of `Shape::Lpromoter`")]
+    pub const Lpromotor: Shape = Shape::Lpromoter;
+
+    /// Whether the shape is elliptical, as required by the wedged style.
+    pub fn is_elliptical(&self) -> bool {
+        matches!(
+            self,
+            Shape::Ellipse | Shape::Oval | Shape::Circle | Shape::DoubleCircle
+        )
+    }
+
+    /// Whether the shape is rectangular, as required by the striped style.
+    pub fn is_rectangular(&self) -> bool {
+        matches!(
+            self,
+            Shape::Box | Shape::Rect | Shape::Rectangle | Shape::Square
+        )
+    }
 }
 
 impl<'a> DotString<'a> for Shape {
@@ -87,7 +118,7 @@ impl<'a> DotString<'a> for Shape {
             Shape::Octagon => "octagon".into(),
             Shape::DoubleCircle => "doublecircle".into(),
             Shape::DoubleOctagon => "doubleoctagon".into(),
-            Shape::TripleOctagon => "tripleocctagon".into(),
+            Shape::TripleOctagon => "tripleoctagon".into(),
             Shape::Invtriangle => "invtriangle".into(),
             Shape::Invtrapezium => "invtrapezium".into(),
             Shape::Invhouse => "invhouse".into(),
@@ -116,17 +147,87 @@ impl<'a> DotString<'a> for Shape {
             Shape::FivePoverHang => "fivepoverhang".into(),
             Shape::ThreePoverHang => "threepoverhang".into(),
             Shape::NoverHang => "noverhang".into(),
-            Shape::Assemply => "assemply".into(),
+            Shape::Assembly => "assembly".into(),
             Shape::Signature => "signature".into(),
             Shape::Insulator => "insulator".into(),
             Shape::Ribosite => "ribosite".into(),
             Shape::Rnastab => "rnastab".into(),
             Shape::Proteasesite => "proteasesite".into(),
             Shape::Proteinstab => "proteinstab".into(),
-            Shape::Rpromotor => "rpromotor".into(),
+            Shape::Rpromoter => "rpromoter".into(),
             Shape::Rarrow => "rarrow".into(),
             Shape::Larrow => "larrow".into(),
-            Shape::Lpromotor => "lpromotor".into(),
+            Shape::Lpromoter => "lpromoter".into(),
+        }
+    }
+}
+
+impl FromStr for Shape {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "box" => Ok(Shape::Box),
+            "polygon" => Ok(Shape::Polygon),
+            "ellipse" => Ok(Shape::Ellipse),
+            "oval" => Ok(Shape::Oval),
+            "circle" => Ok(Shape::Circle),
+            "point" => Ok(Shape::Point),
+            "egg" => Ok(Shape::Egg),
+            "triangle" => Ok(Shape::Triangle),
+            "plaintext" => Ok(Shape::Plaintext),
+            "plain" => Ok(Shape::Plain),
+            "diamond" => Ok(Shape::Diamond),
+            "trapezium" => Ok(Shape::Trapezium),
+            "parallelogram" => Ok(Shape::Parallelogram),
+            "house" => Ok(Shape::House),
+            "pentagon" => Ok(Shape::Pentagon),
+            "hexagon" => Ok(Shape::Hexagon),
+            "septagon" => Ok(Shape::Septagon),
+            "octagon" => Ok(Shape::Octagon),
+            "doublecircle" => Ok(Shape::DoubleCircle),
+            "doubleoctagon" => Ok(Shape::DoubleOctagon),
+            "tripleoctagon" => Ok(Shape::TripleOctagon),
+            "invtriangle" => Ok(Shape::Invtriangle),
+            "invtrapezium" => Ok(Shape::Invtrapezium),
+            "invhouse" => Ok(Shape::Invhouse),
+            "Mdiamond" => Ok(Shape::Mdiamond),
+            "Msquare" => Ok(Shape::Msquare),
+            "Mcircle" => Ok(Shape::Mcircle),
+            "record" => Ok(Shape::Record),
+            "rect" => Ok(Shape::Rect),
+            "rectangle" => Ok(Shape::Rectangle),
+            "square" => Ok(Shape::Square),
+            "star" => Ok(Shape::Star),
+            "none" => Ok(Shape::None),
+            "underline" => Ok(Shape::Underline),
+            "cylinder" => Ok(Shape::Cylinder),
+            "note" => Ok(Shape::Note),
+            "tab" => Ok(Shape::Tab),
+            "folder" => Ok(Shape::Folder),
+            "box3d" => Ok(Shape::Box3D),
+            "component" => Ok(Shape::Component),
+            "promoter" => Ok(Shape::Promoter),
+            "cds" => Ok(Shape::Cds),
+            "terminator" => Ok(Shape::Terminator),
+            "utr" => Ok(Shape::Utr),
+            "primersite" => Ok(Shape::Primersite),
+            "restrictionsite" => Ok(Shape::Restrictionsite),
+            "fivepoverhang" => Ok(Shape::FivePoverHang),
+            "threepoverhang" => Ok(Shape::ThreePoverHang),
+            "noverhang" => Ok(Shape::NoverHang),
+            "assembly" => Ok(Shape::Assembly),
+            "signature" => Ok(Shape::Signature),
+            "insulator" => Ok(Shape::Insulator),
+            "ribosite" => Ok(Shape::Ribosite),
+            "rnastab" => Ok(Shape::Rnastab),
+            "proteasesite" => Ok(Shape::Proteasesite),
+            "proteinstab" => Ok(Shape::Proteinstab),
+            "rpromoter" => Ok(Shape::Rpromoter),
+            "rarrow" => Ok(Shape::Rarrow),
+            "larrow" => Ok(Shape::Larrow),
+            "lpromoter" => Ok(Shape::Lpromoter),
+            _ => Err(ParseAttributeError::new("Shape", s)),
         }
     }
 }
@@ -0,0 +1,48 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Post-processing used by sfdp to adjust the final layout to reduce the effect
+/// of the spring-electrical model on node distribution.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SmoothType {
+    None,
+    AvgDist,
+    GraphDist,
+    PowerDist,
+    Rng,
+    Spring,
+    Triangle,
+}
+
+impl<'a> DotString<'a> for SmoothType {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            SmoothType::None => "none".into(),
+            SmoothType::AvgDist => "avg_dist".into(),
+            SmoothType::GraphDist => "graph_dist".into(),
+            SmoothType::PowerDist => "power_dist".into(),
+            SmoothType::Rng => "rng".into(),
+            SmoothType::Spring => "spring".into(),
+            SmoothType::Triangle => "triangle".into(),
+        }
+    }
+}
+
+impl FromStr for SmoothType {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(SmoothType::None),
+            "avg_dist" => Ok(SmoothType::AvgDist),
+            "graph_dist" => Ok(SmoothType::GraphDist),
+            "power_dist" => Ok(SmoothType::PowerDist),
+            "rng" => Ok(SmoothType::Rng),
+            "spring" => Ok(SmoothType::Spring),
+            "triangle" => Ok(SmoothType::Triangle),
+            _ => Err(ParseAttributeError::new("SmoothType", s)),
+        }
+    }
+}
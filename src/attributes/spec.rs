@@ -0,0 +1,769 @@
+use crate::attributes::esc_string::validate_substitutions;
+use crate::attributes::LayoutEngine;
+use crate::attributes::{AttributeName, AttributeText};
+use crate::validation::{ErrorCode, Severity, ValidationError};
+
+/// The kind of graph component an attribute is set on.
+/// Corresponds to the G, S, C, N and E columns of the Graphviz attribute table.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AttributeContext {
+    /// The root graph.
+    Graph,
+    /// A subgraph which is not a cluster.
+    SubGraph,
+    /// A cluster subgraph, one whose id starts with cluster.
+    Cluster,
+    Node,
+    Edge,
+}
+
+impl AttributeContext {
+    fn code(&self) -> char {
+        match self {
+            AttributeContext::Graph => 'G',
+            AttributeContext::SubGraph => 'S',
+            AttributeContext::Cluster => 'C',
+            AttributeContext::Node => 'N',
+            AttributeContext::Edge => 'E',
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            AttributeContext::Graph => "graph",
+            AttributeContext::SubGraph => "subgraph",
+            AttributeContext::Cluster => "cluster",
+            AttributeContext::Node => "node",
+            AttributeContext::Edge => "edge",
+        }
+    }
+
+    /// The context for the graph attributes of a subgraph with the given id.
+    pub fn for_sub_graph(id: Option<&str>) -> AttributeContext {
+        match id {
+            Some(id) if id.starts_with("cluster") => AttributeContext::Cluster,
+            _ => AttributeContext::SubGraph,
+        }
+    }
+}
+
+/// Attribute value types as named in the Graphviz attribute table.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ValueType {
+    AddDouble,
+    AddPoint,
+    ArrowType,
+    Bool,
+    ClusterMode,
+    Color,
+    ColorList,
+    DirType,
+    Double,
+    DoubleList,
+    EscString,
+    Int,
+    LabelString,
+    LayerList,
+    LayerRange,
+    OutputMode,
+    PackMode,
+    PageDir,
+    Point,
+    PointList,
+    PortPos,
+    QuadType,
+    RankDir,
+    RankType,
+    Rect,
+    Shape,
+    SmoothType,
+    SplineType,
+    StartType,
+    String,
+    Style,
+    ViewPort,
+}
+
+impl ValueType {
+    /// The name of the type as used by the Graphviz documentation.
+    pub fn name(&self) -> &'static str {
+        use ValueType::*;
+        match self {
+            AddDouble => "addDouble",
+            AddPoint => "addPoint",
+            ArrowType => "arrowType",
+            Bool => "bool",
+            ClusterMode => "clusterMode",
+            Color => "color",
+            ColorList => "colorList",
+            DirType => "dirType",
+            Double => "double",
+            DoubleList => "doubleList",
+            EscString => "escString",
+            Int => "int",
+            LabelString => "lblString",
+            LayerList => "layerList",
+            LayerRange => "layerRange",
+            OutputMode => "outputMode",
+            PackMode => "packMode",
+            PageDir => "pagedir",
+            Point => "point",
+            PointList => "pointList",
+            PortPos => "portPos",
+            QuadType => "quadType",
+            RankDir => "rankdir",
+            RankType => "rankType",
+            Rect => "rect",
+            Shape => "shape",
+            SmoothType => "smoothType",
+            SplineType => "splineType",
+            StartType => "startType",
+            String => "string",
+            Style => "style",
+            ViewPort => "viewPort",
+        }
+    }
+
+    /// Whether `value` is a syntactically valid value of this type.
+    /// Free-form types such as strings, colors and styles accept any value.
+    pub fn accepts(&self, value: &str) -> bool {
+        use ValueType::*;
+        match self {
+            AddDouble => is_double(value.strip_prefix('+').unwrap_or(value)),
+            AddPoint => is_point(value.strip_prefix('+').unwrap_or(value)),
+            ArrowType => is_arrow_type(value),
+            Bool => {
+                ["true", "false", "yes", "no"]
+                    .iter()
+                    .any(|b| b.eq_ignore_ascii_case(value))
+                    || value.parse::<i64>().is_ok()
+            }
+            ClusterMode => is_keyword(value, &["local", "global", "none"]),
+            DirType => is_keyword(value, &["forward", "back", "both", "none"]),
+            Double => is_double(value),
+            DoubleList => value.split(':').all(is_double),
+            Int => value.parse::<i64>().is_ok(),
+            OutputMode => {
+                is_keyword(value, &["breadthfirst", "nodesfirst", "edgesfirst"])
+            }
+            PackMode => {
+                is_keyword(value, &["node", "clust", "graph"])
+                    || value.starts_with("array")
+            }
+            PageDir => {
+                is_keyword(value, &["BL", "BR", "TL", "TR", "RB", "RT", "LB", "LT"])
+            }
+            Point => is_point(value),
+            PointList => value.split_whitespace().all(is_point),
+            QuadType => is_keyword(value, &["normal", "fast", "none"]),
+            RankDir => is_keyword(value, &["TB", "LR", "BT", "RL"]),
+            RankType => is_keyword(value, &["same", "min", "source", "max", "sink"]),
+            Rect => {
+                let parts: Vec<&str> = value.split(',').collect();
+                parts.len() == 4 && parts.iter().all(|p| is_double(p))
+            }
+            Shape => is_keyword(value, SHAPES),
+            SmoothType => is_keyword(
+                value,
+                &[
+                    "none",
+                    "avg_dist",
+                    "graph_dist",
+                    "power_dist",
+                    "rng",
+                    "spring",
+                    "triangle",
+                ],
+            ),
+            StartType => {
+                let seed = ["regular", "self", "random"]
+                    .iter()
+                    .find(|s| value.starts_with(*s))
+                    .map_or(value, |s| &value[s.len()..]);
+                seed.is_empty() || seed.parse::<u64>().is_ok()
+            }
+            Color | ColorList | EscString | LabelString | LayerList | LayerRange
+            | PortPos | SplineType | String | Style | ViewPort => true,
+        }
+    }
+}
+
+/// Metadata for a single Graphviz attribute.
+/// See <https://graphviz.org/doc/info/attrs.html>
+#[derive(Debug, PartialEq)]
+pub struct AttributeSpec {
+    /// Name of the attribute, as it appears in DOT.
+    pub name: &'static str,
+
+    /// Components the attribute may be set on, using the letters of the Graphviz attribute
+    /// table. See [`AttributeSpec::applies_to`].
+    pub used_by: &'static str,
+
+    /// The types a value may take. A value is valid if it matches any of them.
+    pub value_types: &'static [ValueType],
+
+    /// Default value, when it is a constant.
+    pub default: Option<&'static str>,
+
+    /// Minimum value for numeric attributes.
+    pub minimum: Option<f64>,
+
+    /// Layout engines the attribute affects, or `None` if used by all engines.
+    pub engines: Option<&'static [LayoutEngine]>,
+}
+
+impl AttributeSpec {
+    /// Looks up the spec of the attribute with the given name.
+    /// Attribute names are case sensitive.
+    pub fn find(name: &str) -> Option<&'static AttributeSpec> {
+        table::ATTRIBUTE_SPECS.iter().find(|spec| spec.name == name)
+    }
+
+    /// Every attribute known to dotavious.
+    pub fn all() -> &'static [AttributeSpec] {
+        table::ATTRIBUTE_SPECS
+    }
+
+    /// Finds the known attribute closest to `name`, for use in "did you mean" suggestions.
+    /// Only names within a small edit distance are suggested, one edit for short names.
+    pub fn suggest(name: &str) -> Option<&'static str> {
+        if let Some(spec) = table::ATTRIBUTE_SPECS
+            .iter()
+            .find(|spec| spec.name.eq_ignore_ascii_case(name))
+        {
+            return Some(spec.name);
+        }
+
+        let lower = name.to_lowercase();
+        let max_distance = if name.chars().count() < 6 { 1 } else { 2 };
+        table::ATTRIBUTE_SPECS
+            .iter()
+            .map(|spec| (spec.name, edit_distance(&lower, &spec.name.to_lowercase())))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(name, _)| name)
+    }
+
+    pub fn applies_to(&self, context: AttributeContext) -> bool {
+        self.used_by.contains(context.code())
+    }
+
+    /// Checks `value` against the value types and minimum of the attribute.
+    pub fn validate_value(&self, value: &str) -> Result<(), String> {
+        self.check_value(value).map_err(|(_, message)| message)
+    }
+
+    fn check_value(&self, value: &str) -> Result<(), (ErrorCode, String)> {
+        if !self.value_types.iter().any(|t| t.accepts(value)) {
+            let types: Vec<&str> = self.value_types.iter().map(|t| t.name()).collect();
+            return Err((
+                ErrorCode::InvalidValue,
+                format!("Must be a value of type {}", types.join(" or ")),
+            ));
+        }
+
+        if let (Some(minimum), Ok(number)) = (self.minimum, value.parse::<f64>()) {
+            if number < minimum {
+                return Err((
+                    ErrorCode::OutOfRange,
+                    format!("Must be greater than or equal to {}", minimum),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates an attribute against the registry, pushing at most one error for it.
+/// The attribute may be set on any of `contexts`.
+///
+/// Unknown attributes are only warnings, as Graphviz only warns about them and they
+/// are often meant for other tools reading the graph.
+///
+/// Nothing is reported for attributes which already have an error,
+/// such as those raised by the typed attribute functions.
+pub(crate) fn validate_attribute(
+    errors: &mut Vec<ValidationError>,
+    contexts: &[AttributeContext],
+    key: &AttributeName,
+    value: &AttributeText,
+) {
+    if errors.iter().any(|e| e.field == key.as_str()) {
+        return;
+    }
+
+    let (code, message) = match key.spec() {
+        None => match AttributeSpec::suggest(key.as_str()) {
+            Some(suggestion) => (
+                ErrorCode::UnknownAttribute,
+                format!("Unknown attribute. Did you mean \"{}\"?", suggestion),
+            ),
+            None => (
+                ErrorCode::UnknownAttribute,
+                String::from("Unknown attribute"),
+            ),
+        },
+        Some(spec) if !contexts.iter().any(|c| spec.applies_to(*c)) => (
+            ErrorCode::InvalidContext,
+            format!(
+                "Not a valid {} attribute",
+                contexts.first().map_or("", |c| c.description())
+            ),
+        ),
+        Some(spec) => match value {
+            AttributeText::HtmlStr(_) => {
+                if spec.value_types.contains(&ValueType::LabelString) {
+                    return;
+                }
+                (
+                    ErrorCode::HtmlNotAllowed,
+                    String::from("HTML strings are only valid for labels"),
+                )
+            }
+            AttributeText::EscStr(text)
+                if spec.value_types.contains(&ValueType::EscString)
+                    || spec.value_types.contains(&ValueType::LabelString) =>
+            {
+                let is_label = *key == AttributeName::Label;
+                match validate_substitutions(text, contexts, is_label) {
+                    Ok(()) => return,
+                    Err(message) => (ErrorCode::InvalidSubstitution, message),
+                }
+            }
+            _ => match spec.check_value(value.as_str()) {
+                Ok(()) => return,
+                Err(error) => error,
+            },
+        },
+    };
+
+    let severity = match code {
+        ErrorCode::UnknownAttribute => Severity::Warning,
+        _ => Severity::Error,
+    };
+    errors.push(
+        ValidationError::new(code, key.to_string(), message)
+            .with_severity(severity)
+            .with_value(value.as_str()),
+    );
+}
+
+fn is_double(value: &str) -> bool {
+    !value.is_empty() && value.parse::<f64>().is_ok()
+}
+
+fn is_point(value: &str) -> bool {
+    let value = value.strip_suffix('!').unwrap_or(value);
+    let parts: Vec<&str> = value.split(',').collect();
+    (parts.len() == 2 || parts.len() == 3) && parts.iter().all(|p| is_double(p))
+}
+
+fn is_keyword(value: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| k.eq_ignore_ascii_case(value))
+}
+
+const ARROW_SHAPES: &[&str] = &[
+    "box", "crow", "curve", "icurve", "diamond", "dot", "inv", "none", "normal", "tee",
+    "vee",
+];
+
+const LEGACY_ARROW_NAMES: &[&str] = &[
+    "ediamond", "open", "halfopen", "empty", "invempty", "invdot", "invodot",
+];
+
+/// Arrow types are made of up to four shapes, each optionally modified by `o` and `l` or `r`.
+fn is_arrow_type(value: &str) -> bool {
+    if LEGACY_ARROW_NAMES.contains(&value) {
+        return true;
+    }
+
+    let mut rest = value;
+    let mut shapes = 0;
+    while !rest.is_empty() {
+        if let Some(shape) = ARROW_SHAPES.iter().find(|s| rest.starts_with(*s)) {
+            rest = &rest[shape.len()..];
+        } else {
+            let unmodified = rest.strip_prefix('o').unwrap_or(rest);
+            let unmodified = unmodified
+                .strip_prefix('l')
+                .or_else(|| unmodified.strip_prefix('r'))
+                .unwrap_or(unmodified);
+            match ARROW_SHAPES.iter().find(|s| unmodified.starts_with(*s)) {
+                Some(shape) if unmodified != rest => rest = &unmodified[shape.len()..],
+                _ => return false,
+            }
+        }
+        shapes += 1;
+    }
+    (1..=4).contains(&shapes)
+}
+
+const SHAPES: &[&str] = &[
+    "box",
+    "polygon",
+    "ellipse",
+    "oval",
+    "circle",
+    "point",
+    "egg",
+    "triangle",
+    "plaintext",
+    "plain",
+    "diamond",
+    "trapezium",
+    "parallelogram",
+    "house",
+    "pentagon",
+    "hexagon",
+    "septagon",
+    "octagon",
+    "doublecircle",
+    "doubleoctagon",
+    "tripleoctagon",
+    "invtriangle",
+    "invtrapezium",
+    "invhouse",
+    "Mdiamond",
+    "Msquare",
+    "Mcircle",
+    "rect",
+    "rectangle",
+    "square",
+    "star",
+    "none",
+    "underline",
+    "cylinder",
+    "note",
+    "tab",
+    "folder",
+    "box3d",
+    "component",
+    "promoter",
+    "cds",
+    "terminator",
+    "utr",
+    "primersite",
+    "restrictionsite",
+    "fivepoverhang",
+    "threepoverhang",
+    "noverhang",
+    "assembly",
+    "signature",
+    "insulator",
+    "ribosite",
+    "rnastab",
+    "proteasesite",
+    "proteinstab",
+    "rpromoter",
+    "rarrow",
+    "larrow",
+    "lpromoter",
+    "record",
+    "Mrecord",
+    "epsf",
+    "custom",
+];
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// The attribute table lives in its own module so that the value type variants can be
+// imported unqualified without shadowing std types.
+mod table {
+    use super::ValueType::*;
+    use super::{AttributeSpec, ValueType};
+    use crate::attributes::LayoutEngine::{
+        self, Circo, Dot, Fdp, Neato, Osage, Patchwork, Sfdp, Twopi,
+    };
+
+    const DOT: Option<&[LayoutEngine]> = Some(&[Dot]);
+    const NEATO: Option<&[LayoutEngine]> = Some(&[Neato]);
+    const SFDP: Option<&[LayoutEngine]> = Some(&[Sfdp]);
+    const CIRCO: Option<&[LayoutEngine]> = Some(&[Circo]);
+    const TWOPI: Option<&[LayoutEngine]> = Some(&[Twopi]);
+    const PATCHWORK: Option<&[LayoutEngine]> = Some(&[Patchwork]);
+    const FDP_NEATO: Option<&[LayoutEngine]> = Some(&[Fdp, Neato]);
+    const FDP_SFDP: Option<&[LayoutEngine]> = Some(&[Fdp, Sfdp]);
+    const NEATO_SFDP: Option<&[LayoutEngine]> = Some(&[Neato, Sfdp]);
+    const FDP_NEATO_SFDP: Option<&[LayoutEngine]> = Some(&[Fdp, Neato, Sfdp]);
+    const CIRCO_TWOPI: Option<&[LayoutEngine]> = Some(&[Circo, Twopi]);
+    const DOT_TWOPI: Option<&[LayoutEngine]> = Some(&[Dot, Twopi]);
+    const NOT_DOT: Option<&[LayoutEngine]> =
+        Some(&[Neato, Fdp, Sfdp, Circo, Twopi, Osage, Patchwork]);
+    const ALL: Option<&[LayoutEngine]> = None;
+
+    const fn spec(
+        name: &'static str,
+        used_by: &'static str,
+        value_types: &'static [ValueType],
+        default: Option<&'static str>,
+        minimum: Option<f64>,
+        engines: Option<&'static [LayoutEngine]>,
+    ) -> AttributeSpec {
+        AttributeSpec {
+            name,
+            used_by,
+            value_types,
+            default,
+            minimum,
+            engines,
+        }
+    }
+
+    // Mirrors the table at https://graphviz.org/doc/info/attrs.html
+    // Deprecated attributes are left out.
+    #[rustfmt::skip]
+    pub(super) static ATTRIBUTE_SPECS: &[AttributeSpec] = &[
+        spec("_background", "G", &[String], None, None, ALL),
+        spec("area", "NC", &[Double], Some("1.0"), None, PATCHWORK),
+        spec("arrowhead", "E", &[ArrowType], Some("normal"), None, ALL),
+        spec("arrowsize", "E", &[Double], Some("1.0"), Some(0.0), ALL),
+        spec("arrowtail", "E", &[ArrowType], Some("normal"), None, ALL),
+        spec("bb", "GC", &[Rect], None, None, ALL),
+        spec("beautify", "G", &[Bool], Some("false"), None, SFDP),
+        spec("bgcolor", "GC", &[Color, ColorList], None, None, ALL),
+        spec("center", "G", &[Bool], Some("false"), None, ALL),
+        spec("charset", "G", &[String], Some("UTF-8"), None, ALL),
+        spec("class", "GCNE", &[String], Some(""), None, ALL),
+        spec("cluster", "GCS", &[Bool], Some("false"), None, ALL),
+        spec("clusterrank", "G", &[ClusterMode], Some("local"), None, DOT),
+        spec("color", "ENC", &[Color, ColorList], Some("black"), None, ALL),
+        spec("colorscheme", "ENCG", &[String], Some(""), None, ALL),
+        spec("comment", "ENG", &[String], Some(""), None, ALL),
+        spec("compound", "G", &[Bool], Some("false"), None, DOT),
+        spec("concentrate", "G", &[Bool], Some("false"), None, ALL),
+        spec("constraint", "E", &[Bool], Some("true"), None, DOT),
+        spec("Damping", "G", &[Double], Some("0.99"), Some(0.0), NEATO),
+        spec("decorate", "E", &[Bool], Some("false"), None, ALL),
+        spec("defaultdist", "G", &[Double], None, None, NEATO),
+        spec("dim", "G", &[Int], Some("2"), Some(2.0), FDP_NEATO_SFDP),
+        spec("dimen", "G", &[Int], Some("2"), Some(2.0), FDP_NEATO_SFDP),
+        spec("dir", "E", &[DirType], None, None, ALL),
+        spec("diredgeconstraints", "G", &[String, Bool], Some("false"), None, NEATO),
+        spec("distortion", "N", &[Double], Some("0.0"), Some(-100.0), ALL),
+        spec("dpi", "G", &[Double], Some("96.0"), None, ALL),
+        spec("edgehref", "E", &[EscString], Some(""), None, ALL),
+        spec("edgetarget", "E", &[EscString], None, None, ALL),
+        spec("edgetooltip", "E", &[EscString], Some(""), None, ALL),
+        spec("edgeURL", "E", &[EscString], Some(""), None, ALL),
+        spec("epsilon", "G", &[Double], None, None, NEATO),
+        spec("esep", "G", &[AddDouble, AddPoint], Some("+3"), None, NOT_DOT),
+        spec("fillcolor", "NEC", &[Color, ColorList], None, None, ALL),
+        spec("fixedsize", "N", &[Bool, String], Some("false"), None, ALL),
+        spec("fontcolor", "ENGC", &[Color], Some("black"), None, ALL),
+        spec("fontname", "ENGC", &[String], Some("Times-Roman"), None, ALL),
+        spec("fontnames", "G", &[String], Some(""), None, ALL),
+        spec("fontpath", "G", &[String], None, None, ALL),
+        spec("fontsize", "ENGC", &[Double], Some("14.0"), Some(1.0), ALL),
+        spec("forcelabels", "G", &[Bool], Some("true"), None, ALL),
+        spec("gradientangle", "NCG", &[Int], Some(""), None, ALL),
+        spec("group", "N", &[String], Some(""), None, DOT),
+        spec("head_lp", "E", &[Point], None, None, ALL),
+        spec("headclip", "E", &[Bool], Some("true"), None, ALL),
+        spec("headhref", "E", &[EscString], Some(""), None, ALL),
+        spec("headlabel", "E", &[LabelString], Some(""), None, ALL),
+        spec("headport", "E", &[PortPos], Some("center"), None, ALL),
+        spec("headtarget", "E", &[EscString], None, None, ALL),
+        spec("headtooltip", "E", &[EscString], Some(""), None, ALL),
+        spec("headURL", "E", &[EscString], Some(""), None, ALL),
+        spec("height", "N", &[Double], Some("0.5"), Some(0.02), ALL),
+        spec("href", "GCNE", &[EscString], Some(""), None, ALL),
+        spec("id", "GCNE", &[EscString], Some(""), None, ALL),
+        spec("image", "N", &[String], Some(""), None, ALL),
+        spec("imagepath", "G", &[String], Some(""), None, ALL),
+        spec("imagepos", "N", &[String], Some("mc"), None, ALL),
+        spec("imagescale", "N", &[Bool, String], Some("false"), None, ALL),
+        spec("inputscale", "G", &[Double], None, None, FDP_NEATO),
+        spec("K", "GC", &[Double], Some("0.3"), Some(0.0), FDP_SFDP),
+        spec("label", "ENGC", &[LabelString], None, None, ALL),
+        spec("label_scheme", "G", &[Int], Some("0"), Some(0.0), SFDP),
+        spec("labelangle", "E", &[Double], Some("-25.0"), Some(-180.0), ALL),
+        spec("labeldistance", "E", &[Double], Some("1.0"), Some(0.0), ALL),
+        spec("labelfloat", "E", &[Bool], Some("false"), None, ALL),
+        spec("labelfontcolor", "E", &[Color], Some("black"), None, ALL),
+        spec("labelfontname", "E", &[String], Some("Times-Roman"), None, ALL),
+        spec("labelfontsize", "E", &[Double], Some("14.0"), Some(1.0), ALL),
+        spec("labelhref", "E", &[EscString], Some(""), None, ALL),
+        spec("labeljust", "GC", &[String], Some("c"), None, ALL),
+        spec("labelloc", "NGC", &[String], None, None, ALL),
+        spec("labeltarget", "E", &[EscString], None, None, ALL),
+        spec("labeltooltip", "E", &[EscString], Some(""), None, ALL),
+        spec("labelURL", "E", &[EscString], Some(""), None, ALL),
+        spec("landscape", "G", &[Bool], Some("false"), None, ALL),
+        spec("layer", "ENC", &[LayerRange], Some(""), None, ALL),
+        spec("layerlistsep", "G", &[String], Some(","), None, ALL),
+        spec("layers", "G", &[LayerList], Some(""), None, ALL),
+        spec("layerselect", "G", &[LayerRange], Some(""), None, ALL),
+        spec("layersep", "G", &[String], Some(":\t "), None, ALL),
+        spec("layout", "G", &[String], Some(""), None, ALL),
+        spec("len", "E", &[Double], None, None, FDP_NEATO),
+        spec("levels", "G", &[Int], None, Some(0.0), SFDP),
+        spec("levelsgap", "G", &[Double], Some("0.0"), None, NEATO),
+        spec("lhead", "E", &[String], Some(""), None, DOT),
+        spec("lheight", "GC", &[Double], None, None, ALL),
+        spec("linelength", "G", &[Int], Some("128"), Some(60.0), ALL),
+        spec("lp", "EGC", &[Point], None, None, ALL),
+        spec("ltail", "E", &[String], Some(""), None, DOT),
+        spec("lwidth", "GC", &[Double], None, None, ALL),
+        spec("margin", "NCG", &[Double, Point], None, None, ALL),
+        spec("maxiter", "G", &[Int], None, None, FDP_NEATO),
+        spec("mclimit", "G", &[Double], Some("1.0"), None, DOT),
+        spec("mindist", "G", &[Double], Some("1.0"), Some(0.0), CIRCO),
+        spec("minlen", "E", &[Int], Some("1"), Some(0.0), DOT),
+        spec("mode", "G", &[String], Some("major"), None, NEATO_SFDP),
+        spec("model", "G", &[String], Some("shortpath"), None, NEATO),
+        spec("mosek", "G", &[Bool], Some("false"), None, NEATO),
+        spec("newrank", "G", &[Bool], Some("false"), None, DOT),
+        spec("nodesep", "G", &[Double], Some("0.25"), Some(0.02), DOT),
+        spec("nojustify", "GCNE", &[Bool], Some("false"), None, ALL),
+        spec("normalize", "G", &[Double, Bool], Some("false"), None, NOT_DOT),
+        spec("notranslate", "G", &[Bool], Some("false"), None, NEATO),
+        spec("nslimit", "G", &[Double], None, None, DOT),
+        spec("nslimit1", "G", &[Double], None, None, DOT),
+        spec("oneblock", "G", &[Bool], Some("false"), None, CIRCO),
+        spec("ordering", "GN", &[String], Some(""), None, DOT),
+        spec("orientation", "NG", &[Double, String], None, None, ALL),
+        spec("outputorder", "G", &[OutputMode], Some("breadthfirst"), None, ALL),
+        spec("overlap", "G", &[String, Bool], Some("true"), None, NOT_DOT),
+        spec("overlap_scaling", "G", &[Double], Some("-4"), Some(-1.0e10), NOT_DOT),
+        spec("overlap_shrink", "G", &[Bool], Some("true"), None, NOT_DOT),
+        spec("pack", "G", &[Bool, Int], Some("false"), None, ALL),
+        spec("packmode", "G", &[PackMode], Some("node"), None, ALL),
+        spec("pad", "G", &[Double, Point], Some("0.0555"), None, ALL),
+        spec("page", "G", &[Double, Point], None, None, ALL),
+        spec("pagedir", "G", &[PageDir], Some("BL"), None, ALL),
+        spec("pencolor", "C", &[Color], Some("black"), None, ALL),
+        spec("penwidth", "CNE", &[Double], Some("1.0"), Some(0.0), ALL),
+        spec("peripheries", "NC", &[Int], None, Some(0.0), ALL),
+        spec("pin", "N", &[Bool], Some("false"), None, FDP_NEATO),
+        spec("pos", "EN", &[Point, SplineType], None, None, ALL),
+        spec("quadtree", "G", &[QuadType, Bool], Some("normal"), None, SFDP),
+        spec("quantum", "G", &[Double], Some("0.0"), Some(0.0), ALL),
+        spec("rank", "S", &[RankType], None, None, DOT),
+        spec("rankdir", "G", &[RankDir], Some("TB"), None, DOT),
+        spec("ranksep", "G", &[Double, DoubleList, String], None, Some(0.02), DOT_TWOPI),
+        spec("ratio", "G", &[Double, String], None, None, ALL),
+        spec("rects", "N", &[Rect], None, None, ALL),
+        spec("regular", "N", &[Bool], Some("false"), None, ALL),
+        spec("remincross", "G", &[Bool], Some("true"), None, DOT),
+        spec("repulsiveforce", "G", &[Double], Some("1.0"), Some(0.0), SFDP),
+        spec("resolution", "G", &[Double], Some("96.0"), None, ALL),
+        spec("root", "GN", &[String, Bool], None, None, CIRCO_TWOPI),
+        spec("rotate", "G", &[Int], Some("0"), None, ALL),
+        spec("rotation", "G", &[Double], Some("0"), None, SFDP),
+        spec("samehead", "E", &[String], Some(""), None, DOT),
+        spec("sametail", "E", &[String], Some(""), None, DOT),
+        spec("samplepoints", "N", &[Int], None, None, ALL),
+        spec("scale", "G", &[Double, Point], None, None, TWOPI),
+        spec("searchsize", "G", &[Int], Some("30"), None, DOT),
+        spec("sep", "G", &[AddDouble, AddPoint], Some("+4"), None, NOT_DOT),
+        spec("shape", "N", &[Shape], Some("ellipse"), None, ALL),
+        spec("shapefile", "N", &[String], Some(""), None, ALL),
+        spec("showboxes", "ENG", &[Int], Some("0"), Some(0.0), DOT),
+        spec("sides", "N", &[Int], Some("4"), Some(0.0), ALL),
+        spec("size", "G", &[Double, Point], None, None, ALL),
+        spec("skew", "N", &[Double], Some("0.0"), Some(-100.0), ALL),
+        spec("smoothing", "G", &[SmoothType], Some("none"), None, SFDP),
+        spec("sortv", "GCN", &[Int], Some("0"), Some(0.0), ALL),
+        spec("splines", "G", &[Bool, String], None, None, ALL),
+        spec("start", "G", &[StartType], Some(""), None, FDP_NEATO),
+        spec("style", "ENCG", &[Style], Some(""), None, ALL),
+        spec("stylesheet", "G", &[String], Some(""), None, ALL),
+        spec("tail_lp", "E", &[Point], None, None, ALL),
+        spec("tailclip", "E", &[Bool], Some("true"), None, ALL),
+        spec("tailhref", "E", &[EscString], Some(""), None, ALL),
+        spec("taillabel", "E", &[LabelString], Some(""), None, ALL),
+        spec("tailport", "E", &[PortPos], Some("center"), None, ALL),
+        spec("tailtarget", "E", &[EscString], None, None, ALL),
+        spec("tailtooltip", "E", &[EscString], Some(""), None, ALL),
+        spec("tailURL", "E", &[EscString], Some(""), None, ALL),
+        spec("target", "ENGC", &[EscString, String], None, None, ALL),
+        spec("TBbalance", "G", &[String], Some(""), None, DOT),
+        spec("tooltip", "NEC", &[EscString], Some(""), None, ALL),
+        spec("truecolor", "G", &[Bool], None, None, ALL),
+        spec("URL", "ENGC", &[EscString], None, None, ALL),
+        spec("vertices", "N", &[PointList], None, None, ALL),
+        spec("viewport", "G", &[ViewPort], Some(""), None, ALL),
+        spec("voro_margin", "G", &[Double], Some("0.05"), Some(0.0), NOT_DOT),
+        spec("weight", "E", &[Int, Double], Some("1"), Some(0.0), ALL),
+        spec("width", "N", &[Double], Some("0.75"), Some(0.01), ALL),
+        spec("xdotversion", "G", &[String], None, None, ALL),
+        spec("xlabel", "EN", &[LabelString], Some(""), None, ALL),
+        spec("xlp", "NE", &[Point], None, None, ALL),
+    ];
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::spec::{AttributeContext, AttributeSpec, ValueType};
+
+    #[test]
+    fn find_is_case_sensitive() {
+        assert!(AttributeSpec::find("URL").is_some());
+        assert!(AttributeSpec::find("url").is_none());
+    }
+
+    #[test]
+    fn suggest_similar_name() {
+        assert_eq!(Some("fillcolor"), AttributeSpec::suggest("fillcolour"));
+        assert_eq!(Some("URL"), AttributeSpec::suggest("url"));
+        assert_eq!(None, AttributeSpec::suggest("completely_unknown"));
+        assert_eq!(None, AttributeSpec::suggest("foo"));
+    }
+
+    #[test]
+    fn applies_to() {
+        let shape = AttributeSpec::find("shape").unwrap();
+        assert!(shape.applies_to(AttributeContext::Node));
+        assert!(!shape.applies_to(AttributeContext::Edge));
+    }
+
+    #[test]
+    fn validate_value() {
+        let font_size = AttributeSpec::find("fontsize").unwrap();
+        assert!(font_size.validate_value("12").is_ok());
+        assert_eq!(
+            Err(String::from("Must be greater than or equal to 1")),
+            font_size.validate_value("0.5")
+        );
+        assert_eq!(
+            Err(String::from("Must be a value of type double")),
+            font_size.validate_value("large")
+        );
+    }
+
+    #[test]
+    fn arrow_types() {
+        assert!(ValueType::ArrowType.accepts("normal"));
+        assert!(ValueType::ArrowType.accepts("lteeoldiamond"));
+        assert!(ValueType::ArrowType.accepts("obox"));
+        assert!(ValueType::ArrowType.accepts("invodot"));
+        assert!(!ValueType::ArrowType.accepts("arrow"));
+        assert!(!ValueType::ArrowType.accepts("onormalx"));
+    }
+
+    #[test]
+    fn specs_are_sorted_and_unique() {
+        let names: Vec<String> = AttributeSpec::all()
+            .iter()
+            .map(|s| s.name.to_lowercase())
+            .collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, names);
+    }
+}
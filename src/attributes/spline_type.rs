@@ -1,9 +1,12 @@
 use crate::attributes::point::Point;
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// The number of points in the list must be equivalent to 1 mod 3; note that this is not checked.
 /// TODO: should we check?
+#[derive(Clone, PartialEq, Debug)]
 pub struct SplineType {
     pub start: Option<Point>,
     pub end: Option<Point>,
@@ -26,7 +29,7 @@ impl<'a> DotString<'a> for SplineType {
         let first = iter.next().unwrap();
         dot_string.push_str(format!("{}", first.dot_string()).as_str());
         for point in iter {
-            dot_string.push_str(" ");
+            dot_string.push(' ');
             dot_string.push_str(format!("{}", point.dot_string()).as_str());
         }
 
@@ -34,6 +37,35 @@ impl<'a> DotString<'a> for SplineType {
     }
 }
 
+impl FromStr for SplineType {
+    type Err = ParseAttributeError;
+
+    /// Parses a single spline, e.g. `e,2,0 s,-1,0 0,0 1,1 1,-1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAttributeError::new("SplineType", s);
+        let mut spline_type = SplineType {
+            start: None,
+            end: None,
+            spline_points: Vec::new(),
+        };
+        for part in s.split_whitespace() {
+            if let Some(end) = part.strip_prefix("e,") {
+                spline_type.end = Some(end.parse().map_err(|_| error())?);
+            } else if let Some(start) = part.strip_prefix("s,") {
+                spline_type.start = Some(start.parse().map_err(|_| error())?);
+            } else {
+                spline_type
+                    .spline_points
+                    .push(part.parse().map_err(|_| error())?);
+            }
+        }
+        if spline_type.spline_points.is_empty() {
+            return Err(error());
+        }
+        Ok(spline_type)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::attributes::{Point, SplineType};
@@ -107,4 +139,15 @@ mod test {
             spline_type.dot_string()
         );
     }
+
+    #[test]
+    fn from_str() {
+        let spline_type: SplineType = "e,2.0,0.0 s,-1.0,0.0 0.0,0.0 1.0,1.0 1.0,-1.0"
+            .parse()
+            .unwrap();
+        assert_eq!(Some(Point::new_2d(2.0, 0.0)), spline_type.end);
+        assert_eq!(Some(Point::new_2d(-1.0, 0.0)), spline_type.start);
+        assert_eq!(3, spline_type.spline_points.len());
+        assert!("e,2.0,0.0".parse::<SplineType>().is_err());
+    }
 }
@@ -1,5 +1,7 @@
+use crate::attributes::ParseAttributeError;
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
 /// Spline, edges are drawn as splines routed around nodes
 /// Line, edges are drawn as line segments
@@ -8,6 +10,7 @@ use std::borrow::Cow;
 /// Curved, specifies edges should be drawn as curved arcs.
 /// splines=line and splines=spline can be used as synonyms for
 /// splines=false and splines=true, respectively.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Splines {
     Line,
     Spline,
@@ -29,3 +32,19 @@ impl<'a> DotString<'a> for Splines {
         }
     }
 }
+
+impl FromStr for Splines {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "line" => Ok(Splines::Line),
+            "spline" => Ok(Splines::Spline),
+            "none" => Ok(Splines::None),
+            "curved" => Ok(Splines::Curved),
+            "polyline" => Ok(Splines::Polyline),
+            "ortho" => Ok(Splines::Ortho),
+            _ => Err(ParseAttributeError::new("Splines", s)),
+        }
+    }
+}
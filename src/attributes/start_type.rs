@@ -0,0 +1,75 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Parameter used to determine the initial layout of nodes by fdp and neato.
+///
+/// By default, nodes are randomly placed in a unit square.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum StartType {
+    /// Nodes are placed regularly about a circle.
+    Regular,
+    /// For neato, an abbreviated version of neato is run to obtain the initial layout.
+    /// Rendered as `self`.
+    Current,
+    /// Nodes are placed randomly, optionally seeding the random number generator.
+    Random(Option<u32>),
+    /// Seed for the random number generator.
+    Seed(u32),
+}
+
+impl<'a> DotString<'a> for StartType {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            StartType::Regular => "regular".into(),
+            StartType::Current => "self".into(),
+            StartType::Random(None) => "random".into(),
+            StartType::Random(Some(seed)) => format!("random{}", seed).into(),
+            StartType::Seed(seed) => seed.to_string().into(),
+        }
+    }
+}
+
+impl FromStr for StartType {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAttributeError::new("StartType", s);
+        match s {
+            "regular" => Ok(StartType::Regular),
+            "self" => Ok(StartType::Current),
+            "random" => Ok(StartType::Random(None)),
+            _ => match s.strip_prefix("random") {
+                Some(seed) => seed
+                    .parse()
+                    .map(|seed| StartType::Random(Some(seed)))
+                    .map_err(|_| error()),
+                None => s.parse().map(StartType::Seed).map_err(|_| error()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::StartType;
+    use crate::DotString;
+
+    #[test]
+    fn dot_string() {
+        assert_eq!("self", StartType::Current.dot_string());
+        assert_eq!("random", StartType::Random(None).dot_string());
+        assert_eq!("random7", StartType::Random(Some(7)).dot_string());
+        assert_eq!("7", StartType::Seed(7).dot_string());
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(StartType::Current), "self".parse());
+        assert_eq!(Ok(StartType::Random(None)), "random".parse());
+        assert_eq!(Ok(StartType::Random(Some(7))), "random7".parse());
+        assert_eq!(Ok(StartType::Seed(7)), "7".parse());
+        assert!("seven".parse::<StartType>().is_err());
+    }
+}
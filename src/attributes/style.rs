@@ -1,6 +1,10 @@
+use crate::attributes::{AttributeText, ParseAttributeError};
 use crate::dot::DotString;
 use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum NodeStyle {
     Bold,
     Dashed,
@@ -8,13 +12,24 @@ pub enum NodeStyle {
     Dotted,
     Filled,
     Invisible,
+    Radial,
     Rounded,
     Solid,
-    Stripped,
-    Radical,
+    /// Only valid for rectangularly-shaped nodes.
+    Striped,
+    /// Only valid for elliptically-shaped nodes.
     Wedged,
 }
 
+#[allow(non_upper_case_globals)]
+impl NodeStyle {
+    #[deprecated(note = "misspelling of `NodeStyle::Radial`")]
+    pub const Radical: NodeStyle = NodeStyle::Radial;
+
+    #[deprecated(note = "misspelling of `NodeStyle::Striped`")]
+    pub const Stripped: NodeStyle = NodeStyle::Striped;
+}
+
 impl<'a> DotString<'a> for NodeStyle {
     fn dot_string(&self) -> Cow<'a, str> {
         match self {
@@ -23,16 +38,38 @@ impl<'a> DotString<'a> for NodeStyle {
             NodeStyle::Diagonals => "diagonals".into(),
             NodeStyle::Dotted => "dotted".into(),
             NodeStyle::Filled => "filled".into(),
-            NodeStyle::Invisible => "invisible".into(),
+            NodeStyle::Invisible => "invis".into(),
+            NodeStyle::Radial => "radial".into(),
             NodeStyle::Rounded => "rounded".into(),
             NodeStyle::Solid => "solid".into(),
-            NodeStyle::Stripped => "stripped".into(),
-            NodeStyle::Radical => "radical".into(),
+            NodeStyle::Striped => "striped".into(),
             NodeStyle::Wedged => "wedged".into(),
         }
     }
 }
 
+impl FromStr for NodeStyle {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bold" => Ok(NodeStyle::Bold),
+            "dashed" => Ok(NodeStyle::Dashed),
+            "diagonals" => Ok(NodeStyle::Diagonals),
+            "dotted" => Ok(NodeStyle::Dotted),
+            "filled" => Ok(NodeStyle::Filled),
+            "invis" | "invisible" => Ok(NodeStyle::Invisible),
+            "radial" => Ok(NodeStyle::Radial),
+            "rounded" => Ok(NodeStyle::Rounded),
+            "solid" => Ok(NodeStyle::Solid),
+            "striped" => Ok(NodeStyle::Striped),
+            "wedged" => Ok(NodeStyle::Wedged),
+            _ => Err(ParseAttributeError::new("NodeStyle", s)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum EdgeStyle {
     Bold,
     Dashed,
@@ -48,33 +85,252 @@ impl<'a> DotString<'a> for EdgeStyle {
             EdgeStyle::Bold => "bold".into(),
             EdgeStyle::Dashed => "dashed".into(),
             EdgeStyle::Dotted => "dotted".into(),
-            EdgeStyle::Invisible => "invisible".into(),
+            EdgeStyle::Invisible => "invis".into(),
             EdgeStyle::Solid => "solid".into(),
             EdgeStyle::Tapered => "tapered".into(),
         }
     }
 }
 
+impl FromStr for EdgeStyle {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bold" => Ok(EdgeStyle::Bold),
+            "dashed" => Ok(EdgeStyle::Dashed),
+            "dotted" => Ok(EdgeStyle::Dotted),
+            "invis" | "invisible" => Ok(EdgeStyle::Invisible),
+            "solid" => Ok(EdgeStyle::Solid),
+            "tapered" => Ok(EdgeStyle::Tapered),
+            _ => Err(ParseAttributeError::new("EdgeStyle", s)),
+        }
+    }
+}
+
+/// Styles of clusters.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum GraphStyle {
+    Bold,
+    Dashed,
+    Dotted,
     Filled,
-    Radical,
+    Invisible,
+    Radial,
     Rounded,
+    Solid,
     Striped,
 }
 
+#[allow(non_upper_case_globals)]
+impl GraphStyle {
+    #[deprecated(note = "misspelling of `GraphStyle::Radial`")]
+    pub const Radical: GraphStyle = GraphStyle::Radial;
+}
+
 impl<'a> DotString<'a> for GraphStyle {
     fn dot_string(&self) -> Cow<'a, str> {
         match self {
+            GraphStyle::Bold => "bold".into(),
+            GraphStyle::Dashed => "dashed".into(),
+            GraphStyle::Dotted => "dotted".into(),
             GraphStyle::Filled => "filled".into(),
-            GraphStyle::Radical => "radical".into(),
+            GraphStyle::Invisible => "invis".into(),
+            GraphStyle::Radial => "radial".into(),
             GraphStyle::Rounded => "rounded".into(),
+            GraphStyle::Solid => "solid".into(),
             GraphStyle::Striped => "striped".into(),
         }
     }
 }
 
+impl FromStr for GraphStyle {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bold" => Ok(GraphStyle::Bold),
+            "dashed" => Ok(GraphStyle::Dashed),
+            "dotted" => Ok(GraphStyle::Dotted),
+            "filled" => Ok(GraphStyle::Filled),
+            "invis" | "invisible" => Ok(GraphStyle::Invisible),
+            "radial" => Ok(GraphStyle::Radial),
+            "rounded" => Ok(GraphStyle::Rounded),
+            "solid" => Ok(GraphStyle::Solid),
+            "striped" => Ok(GraphStyle::Striped),
+            _ => Err(ParseAttributeError::new("GraphStyle", s)),
+        }
+    }
+}
+
+/// A single entry of a style list.
+#[derive(Clone, PartialEq, Debug)]
+pub enum StyleItem<S> {
+    Style(S),
+
+    /// Sets the width of lines, in points. Prefer the penwidth attribute instead.
+    SetLineWidth(f32),
+
+    /// A style not known to Graphviz, such as one defined in a PostScript prologue,
+    /// with its arguments.
+    Custom(String, Vec<String>),
+}
+
+impl<S> StyleItem<S> {
+    pub fn custom<N: Into<String>>(name: N) -> Self {
+        StyleItem::Custom(name.into(), Vec::new())
+    }
+}
+
+impl<'a, S: DotString<'a>> DotString<'a> for StyleItem<S> {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            StyleItem::Style(style) => style.dot_string(),
+            StyleItem::SetLineWidth(width) => format!("setlinewidth({})", width).into(),
+            StyleItem::Custom(name, args) if args.is_empty() => name.clone().into(),
+            StyleItem::Custom(name, args) => {
+                format!("{}({})", name, args.join(",")).into()
+            }
+        }
+    }
+}
+
+impl<S: FromStr> FromStr for StyleItem<S> {
+    type Err = ParseAttributeError;
+
+    /// Parses `name` or `name(arg1,arg2)`, falling back to a custom style for names
+    /// which are not styles of `S`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, args) = match s.find('(') {
+            Some(open) => {
+                let args = s[open + 1..]
+                    .strip_suffix(')')
+                    .ok_or_else(|| ParseAttributeError::new("StyleItem", s))?;
+                (s[..open].trim(), Some(args))
+            }
+            None => (s, None),
+        };
+        if name.is_empty() || name.contains(|c: char| c == ')' || c.is_whitespace()) {
+            return Err(ParseAttributeError::new("StyleItem", s));
+        }
+
+        match args {
+            None => Ok(name
+                .parse()
+                .map(StyleItem::Style)
+                .unwrap_or_else(|_| StyleItem::custom(name))),
+            Some(args) if name == "setlinewidth" => args
+                .trim()
+                .parse()
+                .map(StyleItem::SetLineWidth)
+                .map_err(|_| ParseAttributeError::new("StyleItem", s)),
+            Some(args) => Ok(StyleItem::Custom(
+                name.to_string(),
+                args.split(',').map(|a| a.trim().to_string()).collect(),
+            )),
+        }
+    }
+}
+
+/// A comma separated list of styles, such as `filled,rounded,dashed`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StyleList<S> {
+    pub items: Vec<StyleItem<S>>,
+}
+
+pub type NodeStyleList = StyleList<NodeStyle>;
+pub type EdgeStyleList = StyleList<EdgeStyle>;
+pub type GraphStyleList = StyleList<GraphStyle>;
+
+impl<S> Default for StyleList<S> {
+    fn default() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+
+impl<S: PartialEq> StyleList<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn style(mut self, style: S) -> Self {
+        self.items.push(StyleItem::Style(style));
+        self
+    }
+
+    pub fn set_line_width(mut self, width: f32) -> Self {
+        self.items.push(StyleItem::SetLineWidth(width));
+        self
+    }
+
+    pub fn custom<N: Into<String>>(mut self, name: N, args: Vec<String>) -> Self {
+        self.items.push(StyleItem::Custom(name.into(), args));
+        self
+    }
+
+    pub fn contains(&self, style: &S) -> bool {
+        self.items.iter().any(|item| match item {
+            StyleItem::Style(s) => s == style,
+            _ => false,
+        })
+    }
+}
+
+impl<S> From<Vec<S>> for StyleList<S> {
+    fn from(styles: Vec<S>) -> Self {
+        Self {
+            items: styles.into_iter().map(StyleItem::Style).collect(),
+        }
+    }
+}
+
+impl<'a, S: DotString<'a>> DotString<'a> for StyleList<S> {
+    fn dot_string(&self) -> Cow<'a, str> {
+        self.items
+            .iter()
+            .map(|item| item.dot_string())
+            .collect::<Vec<_>>()
+            .join(",")
+            .into()
+    }
+}
+
+impl<S: FromStr> FromStr for StyleList<S> {
+    type Err = ParseAttributeError;
+
+    /// Splits on commas outside of parentheses.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut items = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    items.push(s[start..i].parse()?);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        items.push(s[start..].parse()?);
+        Ok(Self { items })
+    }
+}
+
+impl<S: FromStr> TryFrom<&AttributeText<'_>> for StyleList<S> {
+    type Error = ParseAttributeError;
+
+    fn try_from(text: &AttributeText<'_>) -> Result<Self, Self::Error> {
+        text.as_str().parse()
+    }
+}
+
 // TODO: this might be a bit much to in order to avoid some duplication
 // probably not worth it but is pattern is cool nonetheless
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Styles {
     Edge(EdgeStyle),
     Node(NodeStyle),
@@ -90,3 +346,71 @@ impl<'a> DotString<'a> for Styles {
         }
     }
 }
+
+impl FromStr for Styles {
+    type Err = ParseAttributeError;
+
+    /// Styles shared by nodes, edges and graphs are parsed as node styles first,
+    /// then edge styles.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(Styles::Node)
+            .or_else(|_| s.parse().map(Styles::Edge))
+            .or_else(|_| s.parse().map(Styles::Graph))
+            .map_err(|_| ParseAttributeError::new("Styles", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes::{
+        EdgeStyle, EdgeStyleList, GraphStyle, NodeStyle, NodeStyleList, StyleItem,
+    };
+    use crate::DotString;
+
+    #[test]
+    fn style_list_dot_string() {
+        assert_eq!(
+            "filled,rounded,setlinewidth(2)",
+            NodeStyleList::new()
+                .style(NodeStyle::Filled)
+                .style(NodeStyle::Rounded)
+                .set_line_width(2.0)
+                .dot_string()
+        );
+        assert_eq!(
+            "invis,fancy(1,2)",
+            EdgeStyleList::new()
+                .style(EdgeStyle::Invisible)
+                .custom("fancy", vec!["1".to_string(), "2".to_string()])
+                .dot_string()
+        );
+        assert_eq!("striped", GraphStyle::Striped.dot_string());
+    }
+
+    #[test]
+    fn style_list_from_str() {
+        let styles: NodeStyleList =
+            "filled, striped,setlinewidth(1.5),shiny,glow(red, 2)"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            vec![
+                StyleItem::Style(NodeStyle::Filled),
+                StyleItem::Style(NodeStyle::Striped),
+                StyleItem::SetLineWidth(1.5),
+                StyleItem::custom("shiny"),
+                StyleItem::Custom(
+                    "glow".to_string(),
+                    vec!["red".to_string(), "2".to_string()]
+                ),
+            ],
+            styles.items
+        );
+        assert!(styles.contains(&NodeStyle::Striped));
+        assert_eq!(Ok(EdgeStyle::Invisible), "invisible".parse());
+        assert!("setlinewidth(wide)".parse::<EdgeStyleList>().is_err());
+        assert!("filled,".parse::<NodeStyleList>().is_err());
+        assert!("glow(red".parse::<NodeStyleList>().is_err());
+    }
+}
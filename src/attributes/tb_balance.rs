@@ -0,0 +1,33 @@
+use crate::attributes::ParseAttributeError;
+use crate::dot::DotString;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Which rank to move floating (loose) nodes to.
+/// Nodes with no in or out edges are otherwise left on the rank dot assigns them.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TBBalance {
+    Min,
+    Max,
+}
+
+impl<'a> DotString<'a> for TBBalance {
+    fn dot_string(&self) -> Cow<'a, str> {
+        match self {
+            TBBalance::Min => "min".into(),
+            TBBalance::Max => "max".into(),
+        }
+    }
+}
+
+impl FromStr for TBBalance {
+    type Err = ParseAttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min" => Ok(TBBalance::Min),
+            "max" => Ok(TBBalance::Max),
+            _ => Err(ParseAttributeError::new("TBBalance", s)),
+        }
+    }
+}
@@ -1,7 +1,9 @@
-use crate::attributes::Point;
+use crate::attributes::{ParseAttributeError, Point};
 use crate::DotString;
 use std::borrow::Cow;
+use std::str::FromStr;
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct ViewPort {
     pub width: f32,
     pub height: f32,
@@ -14,7 +16,7 @@ impl ViewPort {
         Self {
             width,
             height,
-            zoom: zoom.unwrap_or(1 as f32),
+            zoom: zoom.unwrap_or(1.0),
             focus
         }
     }
@@ -23,7 +25,7 @@ impl ViewPort {
         Self {
             width,
             height,
-            zoom: zoom.unwrap_or(1 as f32),
+            zoom: zoom.unwrap_or(1.0),
             focus: Some(FocusType::Point(Point::new_2d(x, y)))
         }
     }
@@ -32,7 +34,7 @@ impl ViewPort {
         Self {
             width,
             height,
-            zoom: zoom.unwrap_or(1 as f32),
+            zoom: zoom.unwrap_or(1.0),
             focus: Some(FocusType::Node(node))
         }
     }
@@ -61,11 +63,49 @@ impl<'a> DotString<'a> for ViewPort {
     }
 }
 
+#[derive(Clone, PartialEq, Debug)]
 pub enum FocusType {
     Point(Point),
     Node(String)
 }
 
+impl FromStr for ViewPort {
+    type Err = ParseAttributeError;
+
+    /// Parses `W,H[,Z[,x,y]]` or `W,H,Z,N` where N is the name of a node,
+    /// optionally in single quotes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAttributeError::new("ViewPort", s);
+        let parts: Vec<&str> = s.splitn(4, ',').collect();
+        if parts.len() < 2 {
+            return Err(error());
+        }
+
+        let number = |part: &str| part.trim().parse::<f32>().map_err(|_| error());
+        let width = number(parts[0])?;
+        let height = number(parts[1])?;
+        let zoom = match parts.get(2) {
+            Some(zoom) => Some(number(zoom)?),
+            None => None,
+        };
+        let focus = match parts.get(3) {
+            None => None,
+            Some(focus) => match focus.parse::<Point>() {
+                Ok(point) => Some(FocusType::Point(point)),
+                Err(_) => {
+                    let node = focus.trim_matches('\'');
+                    if node.is_empty() {
+                        return Err(error());
+                    }
+                    Some(FocusType::Node(node.to_string()))
+                }
+            },
+        };
+
+        Ok(ViewPort::new(width, height, zoom, focus))
+    }
+}
+
 
 #[cfg(test)]
 mod test {
@@ -105,4 +145,21 @@ mod test {
             ).dot_string()
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn viewport_from_str() {
+        assert_eq!(
+            Ok(ViewPort::new(1.0, 2.0, None, None)),
+            "1,2".parse::<ViewPort>()
+        );
+        assert_eq!(
+            Ok(ViewPort::new_point(1.0, 2.0, Some(3.0), 5.0, 10.0)),
+            "1,2,3,5,10".parse::<ViewPort>()
+        );
+        assert_eq!(
+            Ok(ViewPort::new_node(1.0, 2.0, Some(3.0), String::from("2.8 BSD"))),
+            "1,2,3,'2.8 BSD'".parse::<ViewPort>()
+        );
+        assert!("1".parse::<ViewPort>().is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Folding of clusters into summary nodes, for coarser views of large graphs.
+
+use crate::attributes::{AttributeContext, AttributeName, AttributeText, Shape};
+use crate::dot::{Edge, Graph, Node, SubGraph};
+use crate::mutation::{is_parallel, MutationError};
+use indexmap::{IndexMap, IndexSet};
+use std::iter;
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+
+impl<'a> Graph<'a> {
+    /// Replaces the subgraph at `path` and everything in it by a single summary node in
+    /// its parent, returning the id of the summary node.
+    ///
+    /// The summary node is named after the subgraph, with a suffix if another node
+    /// already has that id, and is labelled with the subgraph's label or id.
+    /// Edges between nodes of the subgraph are removed. Edges crossing its boundary are
+    /// rerouted to the summary node, keeping the subgraph's edge defaults, and those
+    /// ending up between the same nodes are deduplicated into one edge whose label is
+    /// followed by their count, such as `HTTP (2)`, or is the count if it has none.
+    /// `lhead` and `ltail` referring to the removed clusters are dropped.
+    pub fn collapse_cluster(&mut self, path: &[usize]) -> Result<String, MutationError> {
+        let (position, parent) = match (path.split_last(), self.sub_graph(path)) {
+            (Some((position, parent)), Some(_)) => (*position, parent),
+            _ => return Err(MutationError::SubGraphNotFound(path.to_vec())),
+        };
+
+        let index = self.index();
+        let inside: IndexSet<String> = index
+            .nodes_in_sub_graph(path)
+            .iter()
+            .map(|node| node.id.to_string())
+            .collect();
+        let sub_graph = self.sub_graph(path).unwrap();
+        let name = sub_graph
+            .id
+            .clone()
+            .unwrap_or_else(|| "cluster".to_string());
+        let summary = iter::once(name.clone())
+            .chain((1..).map(|n| format!("{}_{}", name, n)))
+            .find(|id| inside.contains(id) || index.node(id).is_none())
+            .unwrap();
+        let mut summary_node = Node::new(summary.clone());
+        summary_node.attributes.insert(
+            AttributeName::Label,
+            sub_graph
+                .get(AttributeName::Label)
+                .cloned()
+                .unwrap_or_else(|| AttributeText::quoted(name)),
+        );
+        summary_node
+            .attributes
+            .insert(AttributeName::Shape, AttributeText::from(Shape::Box3D));
+
+        let removed = match self.sub_graph_mut(parent) {
+            Some(parent) => parent.sub_graphs.remove(position),
+            None => self.sub_graphs.remove(position),
+        };
+        let mut clusters = IndexSet::new();
+        let mut crossing = Vec::new();
+        take_edges(removed, &IndexMap::new(), &mut clusters, &mut crossing);
+        match self.sub_graph_mut(parent) {
+            Some(parent) => parent.edges.extend(crossing),
+            None => self.edges.extend(crossing),
+        }
+
+        let is_directed = self.is_directed;
+        let remove_compound = |attributes: &mut Attributes<'a>| {
+            for key in &[AttributeName::LHead, AttributeName::LTail] {
+                if attributes
+                    .get(key)
+                    .is_some_and(|cluster| clusters.contains(cluster.as_str()))
+                {
+                    attributes.shift_remove(key);
+                }
+            }
+        };
+        self.for_each_scope_mut(&mut |scope| {
+            scope
+                .nodes
+                .retain(|node| !inside.contains(node.id.as_str()));
+            remove_compound(scope.edge_attributes);
+
+            let mut kept: Vec<Edge<'a>> = Vec::new();
+            let mut counts: Vec<usize> = Vec::new();
+            for mut edge in scope.edges.drain(..) {
+                let source = inside.contains(&edge.source);
+                let target = inside.contains(&edge.target);
+                if source && target {
+                    continue;
+                }
+                if source {
+                    edge.source = summary.clone();
+                    edge.source_port_position = None;
+                }
+                if target {
+                    edge.target = summary.clone();
+                    edge.target_port_position = None;
+                }
+                remove_compound(&mut edge.attributes);
+                let duplicate = if source || target {
+                    kept.iter().position(|k| {
+                        (k.source == summary || k.target == summary)
+                            && is_parallel(k, &edge, is_directed)
+                    })
+                } else {
+                    None
+                };
+                match duplicate {
+                    Some(index) => counts[index] += 1,
+                    None => {
+                        kept.push(edge);
+                        counts.push(1);
+                    }
+                }
+            }
+            for (edge, count) in kept.iter_mut().zip(counts) {
+                if count > 1 {
+                    let label = edge
+                        .attributes
+                        .get(&AttributeName::Label)
+                        .or_else(|| scope.edge_attributes.get(&AttributeName::Label));
+                    let label = counted_label(label, count);
+                    edge.attributes.insert(AttributeName::Label, label);
+                }
+            }
+            *scope.edges = kept;
+        });
+
+        match self.sub_graph_mut(parent) {
+            Some(parent) => parent.nodes.push(summary_node),
+            None => self.nodes.push(summary_node),
+        }
+        Ok(summary)
+    }
+
+    /// Collapses every cluster nested within exactly `depth` other clusters, so that
+    /// a depth of zero leaves only the top-level clusters as summary nodes.
+    ///
+    /// Returns the ids of the summary nodes, see [`Graph::collapse_cluster`].
+    pub fn collapse_to_depth(&mut self, depth: usize) -> Vec<String> {
+        let mut paths: Vec<Vec<usize>> = Vec::new();
+        for (path, _) in self.all_sub_graphs() {
+            let enclosing = (1..path.len())
+                .filter(|len| is_cluster(self.sub_graph(&path[..*len]).unwrap()))
+                .count();
+            if enclosing == depth && is_cluster(self.sub_graph(&path).unwrap()) {
+                paths.push(path);
+            }
+        }
+
+        // Collapsing later subgraphs first keeps the paths of earlier ones valid.
+        let mut summaries: Vec<String> = paths
+            .iter()
+            .rev()
+            .map(|path| {
+                self.collapse_cluster(path)
+                    .expect("paths of clusters not yet collapsed are unchanged")
+            })
+            .collect();
+        summaries.reverse();
+        summaries
+    }
+}
+
+/// Label of an edge standing for `count` parallel edges, keeping the kind of text of
+/// the label they share.
+fn counted_label<'a>(
+    label: Option<&AttributeText<'a>>,
+    count: usize,
+) -> AttributeText<'a> {
+    match label {
+        Some(AttributeText::HtmlStr(html)) => {
+            AttributeText::html(format!("{} ({})", html, count))
+        }
+        Some(AttributeText::EscStr(text)) => {
+            AttributeText::escaped(format!("{} ({})", text, count))
+        }
+        Some(label) => AttributeText::quoted(format!("{} ({})", label.as_str(), count)),
+        None => AttributeText::from(count.to_string()),
+    }
+}
+
+fn is_cluster(sub_graph: &SubGraph) -> bool {
+    AttributeContext::for_sub_graph(sub_graph.id.as_deref()) == AttributeContext::Cluster
+}
+
+/// Moves the edges of the subgraph and its subgraphs into `edges`, adding the edge
+/// defaults in effect within them, and collects the ids of the clusters.
+fn take_edges<'a>(
+    sub_graph: SubGraph<'a>,
+    defaults: &Attributes<'a>,
+    clusters: &mut IndexSet<String>,
+    edges: &mut Vec<Edge<'a>>,
+) {
+    if is_cluster(&sub_graph) {
+        clusters.insert(sub_graph.id.clone().unwrap());
+    }
+    let mut defaults = defaults.clone();
+    defaults.extend(sub_graph.edge_attributes);
+    for child in sub_graph.sub_graphs {
+        take_edges(child, &defaults, clusters, edges);
+    }
+    for mut edge in sub_graph.edges {
+        for (key, value) in &defaults {
+            edge.attributes
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        edges.push(edge);
+    }
+}
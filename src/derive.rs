@@ -0,0 +1,174 @@
+//! Turning values of Rust types into nodes and their outgoing edges.
+//!
+//! [`DotNode`] can be implemented by hand, or derived with the `derive` feature:
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use dotavious::attributes::{Color, Shape};
+//! use dotavious::derive::DotNode;
+//! use dotavious::{Dot, GraphBuilder};
+//!
+//! #[derive(DotNode)]
+//! struct Service {
+//!     #[dot(id)]
+//!     name: String,
+//!     #[dot(label)]
+//!     title: String,
+//!     #[dot(shape)]
+//!     shape: Shape,
+//!     #[dot(attr = "fillcolor")]
+//!     color: Option<Color<'static>>,
+//!     #[dot(edge_to(label = "calls"))]
+//!     dependencies: Vec<String>,
+//! }
+//!
+//! let service = Service {
+//!     name: "api".to_string(),
+//!     title: "Public API".to_string(),
+//!     shape: Shape::Box,
+//!     color: None,
+//!     dependencies: vec!["db".to_string()],
+//! };
+//!
+//! let mut builder = GraphBuilder::new_directed();
+//! builder.add_node(service.node().unwrap());
+//! for edge in service.edges().unwrap() {
+//!     builder.add_edge(edge);
+//! }
+//! let graph = builder.build().unwrap();
+//! assert_eq!(
+//!     Dot { graph }.to_string(),
+//!     "digraph {\n    api [label=\"Public API\", shape=box];\n    api -> db [label=\"calls\"];\n}\n"
+//! );
+//! # }
+//! ```
+
+use crate::attributes::AttributeText;
+use crate::dot::{Edge, EdgeBuilder, Node, NodeBuilder};
+use crate::macros::IntoAttributeText;
+use crate::validation::ValidationResult;
+
+#[cfg(feature = "derive")]
+pub use dotavious_derive::DotNode;
+
+/// A value represented by a node, with edges to the nodes of other values.
+///
+/// Deriving it requires the `derive` feature. Fields are mapped with `#[dot(...)]`:
+///
+/// - `id`: the id of the node, of any type implementing `ToString`. Required.
+/// - `attr = "name"`: the attribute with that name, set to the field's value unless it
+///   is `None`. Values are anything convertible to an [`AttributeText`].
+/// - any other name, such as `label` or `shape`: the [`NodeAttributes`] setter with that
+///   name, called with the field's value so its type is checked against the setter.
+/// - `edge_to`: edges to the ids in the field, which can be an id, an `Option` or a
+///   `Vec` or slice of ids. Written as `edge_to(setter = value, ...)`, the
+///   [`EdgeAttributes`] setters with those names are called on each edge with the
+///   values, converted with `Into`, such as `edge_to(label = "calls", pen_width = 2.0)`.
+///
+/// [`NodeAttributes`]: crate::attributes::NodeAttributes
+/// [`EdgeAttributes`]: crate::attributes::EdgeAttributes
+pub trait DotNode {
+    fn node_id(&self) -> String;
+
+    /// A builder of the node with the attributes of the value.
+    fn node_builder<'a>(&self) -> NodeBuilder<'a>;
+
+    /// Builders of the edges from the node.
+    fn edge_builders<'a>(&self) -> Vec<EdgeBuilder<'a>>;
+
+    fn node<'a>(&self) -> ValidationResult<Node<'a>> {
+        self.node_builder().build()
+    }
+
+    /// The edges from the node, or the errors of all invalid edges.
+    fn edges<'a>(&self) -> ValidationResult<Vec<Edge<'a>>> {
+        let mut edges = Vec::new();
+        let mut errors = Vec::new();
+        for builder in self.edge_builders() {
+            match builder.build() {
+                Ok(edge) => edges.push(edge),
+                Err(mut edge_errors) => errors.append(&mut edge_errors),
+            }
+        }
+        if errors.is_empty() {
+            Ok(edges)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Values of fields mapped to attributes by name, which are skipped when `None`.
+#[doc(hidden)]
+pub trait AttributeValue<'a> {
+    fn attribute_value(self) -> Option<AttributeText<'a>>;
+}
+
+impl<'a, T: IntoAttributeText<'a>> AttributeValue<'a> for T {
+    fn attribute_value(self) -> Option<AttributeText<'a>> {
+        Some(self.into_attribute_text())
+    }
+}
+
+impl<'a, T: IntoAttributeText<'a>> AttributeValue<'a> for Option<T> {
+    fn attribute_value(self) -> Option<AttributeText<'a>> {
+        self.map(IntoAttributeText::into_attribute_text)
+    }
+}
+
+/// Ids of the targets of the edges of `edge_to` fields.
+#[doc(hidden)]
+pub trait EdgeTargets {
+    fn edge_targets(&self) -> Vec<String>;
+}
+
+impl EdgeTargets for str {
+    fn edge_targets(&self) -> Vec<String> {
+        vec![self.to_string()]
+    }
+}
+
+impl EdgeTargets for String {
+    fn edge_targets(&self) -> Vec<String> {
+        vec![self.clone()]
+    }
+}
+
+macro_rules! numeric_edge_targets {
+    ($($t:ty),*) => {
+        $(
+            impl EdgeTargets for $t {
+                fn edge_targets(&self) -> Vec<String> {
+                    vec![self.to_string()]
+                }
+            }
+        )*
+    };
+}
+
+numeric_edge_targets!(i32, i64, u32, u64, usize);
+
+impl<T: EdgeTargets + ?Sized> EdgeTargets for &T {
+    fn edge_targets(&self) -> Vec<String> {
+        (**self).edge_targets()
+    }
+}
+
+impl<T: EdgeTargets> EdgeTargets for Option<T> {
+    fn edge_targets(&self) -> Vec<String> {
+        self.iter().flat_map(EdgeTargets::edge_targets).collect()
+    }
+}
+
+impl<T: EdgeTargets> EdgeTargets for [T] {
+    fn edge_targets(&self) -> Vec<String> {
+        self.iter().flat_map(EdgeTargets::edge_targets).collect()
+    }
+}
+
+impl<T: EdgeTargets> EdgeTargets for Vec<T> {
+    fn edge_targets(&self) -> Vec<String> {
+        self.as_slice().edge_targets()
+    }
+}
@@ -0,0 +1,434 @@
+//! Differences between two versions of a graph, such as before and after a change to
+//! an architecture diagram.
+//!
+//! Nodes are matched by id, subgraphs by id and edges by their endpoints and ports.
+//! Anonymous subgraphs have no identity across versions and are not compared.
+
+use crate::attributes::{
+    AttributeName, AttributeText, Color, EdgeStyle, GraphStyle, NodeStyle,
+};
+use crate::dot::{DotString, Edge, Graph, Node, SubGraph};
+use crate::index::GraphIndex;
+use crate::mutation::{is_parallel, statement_attributes};
+use indexmap::IndexMap;
+use std::fmt::{Display, Formatter};
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+
+/// Color of elements only in the new graph in a [`visual_diff`].
+pub const ADDED_COLOR: Color<'static> = Color::Named("green");
+
+/// Color of elements only in the old graph in a [`visual_diff`].
+pub const REMOVED_COLOR: Color<'static> = Color::Named("red");
+
+/// Color of elements whose attributes changed in a [`visual_diff`].
+pub const CHANGED_COLOR: Color<'static> = Color::Named("orange");
+
+/// An attribute which was added, removed or set to a different value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeChange<'a> {
+    pub name: AttributeName,
+
+    /// Value in the old graph, `None` if the attribute was added.
+    pub old: Option<AttributeText<'a>>,
+
+    /// Value in the new graph, `None` if the attribute was removed.
+    pub new: Option<AttributeText<'a>>,
+}
+
+/// A node or subgraph in both graphs with different attributes.
+/// Node attributes combine all statements declaring the node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementChange<'a> {
+    pub id: String,
+    pub attributes: Vec<AttributeChange<'a>>,
+}
+
+/// An edge in both graphs with different attributes.
+#[derive(Clone, Debug)]
+pub struct EdgeChange<'a> {
+    /// The edge of the new graph.
+    pub edge: Edge<'a>,
+    pub attributes: Vec<AttributeChange<'a>>,
+}
+
+/// Differences between an old and a new graph, see [`diff`].
+#[derive(Clone, Debug, Default)]
+pub struct GraphDiff<'a> {
+    /// Whether the new graph is directed, which determines how edges are displayed.
+    pub is_directed: bool,
+    pub graph_attributes: Vec<AttributeChange<'a>>,
+    pub added_nodes: Vec<String>,
+    pub removed_nodes: Vec<String>,
+    pub changed_nodes: Vec<ElementChange<'a>>,
+    pub added_sub_graphs: Vec<String>,
+    pub removed_sub_graphs: Vec<String>,
+    pub changed_sub_graphs: Vec<ElementChange<'a>>,
+    pub added_edges: Vec<Edge<'a>>,
+    pub removed_edges: Vec<Edge<'a>>,
+    pub changed_edges: Vec<EdgeChange<'a>>,
+}
+
+impl<'a> GraphDiff<'a> {
+    /// Whether the graphs have the same nodes, edges, subgraphs and attributes.
+    pub fn is_empty(&self) -> bool {
+        self.graph_attributes.is_empty()
+            && self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_sub_graphs.is_empty()
+            && self.removed_sub_graphs.is_empty()
+            && self.changed_sub_graphs.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+/// One line per difference, prefixed by `+` for added, `-` for removed and `~` for
+/// changed elements.
+impl<'a> Display for GraphDiff<'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for change in &self.graph_attributes {
+            writeln!(f, "~ graph {}", fmt_change(change))?;
+        }
+        for id in &self.added_sub_graphs {
+            writeln!(f, "+ subgraph {}", id)?;
+        }
+        for id in &self.removed_sub_graphs {
+            writeln!(f, "- subgraph {}", id)?;
+        }
+        for change in &self.changed_sub_graphs {
+            for attribute in &change.attributes {
+                writeln!(f, "~ subgraph {} {}", change.id, fmt_change(attribute))?;
+            }
+        }
+        for id in &self.added_nodes {
+            writeln!(f, "+ node {}", id)?;
+        }
+        for id in &self.removed_nodes {
+            writeln!(f, "- node {}", id)?;
+        }
+        for change in &self.changed_nodes {
+            for attribute in &change.attributes {
+                writeln!(f, "~ node {} {}", change.id, fmt_change(attribute))?;
+            }
+        }
+        for edge in &self.added_edges {
+            writeln!(f, "+ edge {}", fmt_edge(edge, self.is_directed))?;
+        }
+        for edge in &self.removed_edges {
+            writeln!(f, "- edge {}", fmt_edge(edge, self.is_directed))?;
+        }
+        for change in &self.changed_edges {
+            for attribute in &change.attributes {
+                writeln!(
+                    f,
+                    "~ edge {} {}",
+                    fmt_edge(&change.edge, self.is_directed),
+                    fmt_change(attribute)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn fmt_change(change: &AttributeChange) -> String {
+    let value = |text: &Option<AttributeText>| {
+        text.as_ref()
+            .map_or_else(|| String::from("(unset)"), |text| text.dot_string())
+    };
+    format!(
+        "{}: {} -> {}",
+        change.name,
+        value(&change.old),
+        value(&change.new)
+    )
+}
+
+fn fmt_edge(edge: &Edge, is_directed: bool) -> String {
+    let end = |id: &str, port: &Option<_>| match port {
+        Some(port) => format!("{}:{}", id, DotString::dot_string(port)),
+        None => id.to_string(),
+    };
+    format!(
+        "{} {} {}",
+        end(&edge.source, &edge.source_port_position),
+        if is_directed { "->" } else { "--" },
+        end(&edge.target, &edge.target_port_position)
+    )
+}
+
+/// Compares the old graph with the new one.
+///
+/// Edges between the same endpoints and ports are matched in the order they are
+/// rendered, so of three parallel edges replaced by two the last one is removed.
+/// In undirected graphs edges match regardless of their direction.
+pub fn diff<'a>(old: &Graph<'a>, new: &Graph<'a>) -> GraphDiff<'a> {
+    let old_index = old.index();
+    let new_index = new.index();
+    let mut diff = GraphDiff {
+        is_directed: new.is_directed,
+        graph_attributes: attribute_changes(
+            &old.graph_attributes,
+            &new.graph_attributes,
+        ),
+        ..GraphDiff::default()
+    };
+
+    for node in new_index.nodes() {
+        match old_index.node(node.id) {
+            Some(old_node) => {
+                let attributes = attribute_changes(
+                    &statement_attributes(&old_node.statements),
+                    &statement_attributes(&node.statements),
+                );
+                if !attributes.is_empty() {
+                    diff.changed_nodes.push(ElementChange {
+                        id: node.id.to_string(),
+                        attributes,
+                    });
+                }
+            }
+            None => diff.added_nodes.push(node.id.to_string()),
+        }
+    }
+    diff.removed_nodes = old_index
+        .nodes()
+        .filter(|node| new_index.node(node.id).is_none())
+        .map(|node| node.id.to_string())
+        .collect();
+
+    let old_sub_graphs = named_sub_graphs(old);
+    let new_sub_graphs = named_sub_graphs(new);
+    for (id, sub_graph) in &new_sub_graphs {
+        match old_sub_graphs.get(id) {
+            Some(old_sub_graph) => {
+                let attributes = attribute_changes(
+                    &old_sub_graph.graph_attributes,
+                    &sub_graph.graph_attributes,
+                );
+                if !attributes.is_empty() {
+                    diff.changed_sub_graphs.push(ElementChange {
+                        id: id.to_string(),
+                        attributes,
+                    });
+                }
+            }
+            None => diff.added_sub_graphs.push(id.to_string()),
+        }
+    }
+    diff.removed_sub_graphs = old_sub_graphs
+        .keys()
+        .filter(|id| !new_sub_graphs.contains_key(*id))
+        .map(|id| id.to_string())
+        .collect();
+
+    let matches = match_edges(&old_index, &new_index, new.is_directed);
+    let new_edges = new_index.edges();
+    let old_edges = old_index.edges();
+    for (edge, matched) in new_edges.iter().zip(&matches) {
+        match matched {
+            Some(old_edge) => {
+                let attributes = attribute_changes(
+                    &old_edges[*old_edge].edge.attributes,
+                    &edge.edge.attributes,
+                );
+                if !attributes.is_empty() {
+                    diff.changed_edges.push(EdgeChange {
+                        edge: edge.edge.clone(),
+                        attributes,
+                    });
+                }
+            }
+            None => diff.added_edges.push(edge.edge.clone()),
+        }
+    }
+    diff.removed_edges = old_edges
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !matches.contains(&Some(*index)))
+        .map(|(_, edge)| edge.edge.clone())
+        .collect();
+    diff
+}
+
+/// Merges the old and new graph into one showing their differences.
+///
+/// The new graph is extended with the nodes, edges and clusters removed from the old
+/// one, drawn dashed in [`REMOVED_COLOR`]. Removed subgraphs and edges are added to the
+/// root graph and removed nodes to the removed subgraph they were declared in, if any. Added
+/// elements are colored [`ADDED_COLOR`] and changed ones [`CHANGED_COLOR`], with
+/// changed edges drawn bold.
+pub fn visual_diff<'a>(old: &Graph<'a>, new: &Graph<'a>) -> Graph<'a> {
+    let diff = diff(old, new);
+    let old_index = old.index();
+    let matches = match_edges(&old_index, &new.index(), new.is_directed);
+    let mut graph = new.clone();
+
+    let old_edges = old_index.edges();
+    let mut position = 0;
+    graph.for_each_scope_mut(&mut |scope| {
+        for edge in scope.edges.iter_mut() {
+            match matches[position] {
+                None => {
+                    edge.attributes
+                        .insert(AttributeName::Color, AttributeText::from(ADDED_COLOR));
+                }
+                Some(old_edge)
+                    if old_edges[old_edge].edge.attributes != edge.attributes =>
+                {
+                    edge.attributes.insert(
+                        AttributeName::Color,
+                        AttributeText::from(CHANGED_COLOR),
+                    );
+                    edge.attributes.insert(
+                        AttributeName::Style,
+                        AttributeText::from(EdgeStyle::Bold),
+                    );
+                }
+                Some(_) => {}
+            }
+            position += 1;
+        }
+    });
+    graph.highlight_nodes(&diff.added_nodes, ADDED_COLOR);
+    let changed: Vec<&str> = diff.changed_nodes.iter().map(|c| c.id.as_str()).collect();
+    graph.highlight_nodes(&changed, CHANGED_COLOR);
+
+    let paths: Vec<(Vec<usize>, String)> = graph
+        .all_sub_graphs()
+        .into_iter()
+        .filter_map(|(path, sub_graph)| Some((path, sub_graph.id.clone()?)))
+        .collect();
+    for (path, id) in paths {
+        let color = if diff.added_sub_graphs.contains(&id) {
+            ADDED_COLOR
+        } else if diff.changed_sub_graphs.iter().any(|c| c.id == id) {
+            CHANGED_COLOR
+        } else {
+            continue;
+        };
+        if let Some(sub_graph) = graph.sub_graph_mut(&path) {
+            sub_graph
+                .graph_attributes
+                .insert(AttributeName::Color, AttributeText::from(color));
+        }
+    }
+
+    let old_sub_graphs = named_sub_graphs(old);
+    let mut removed_sub_graphs: IndexMap<&str, SubGraph<'a>> = IndexMap::new();
+    for id in &diff.removed_sub_graphs {
+        let mut graph_attributes = old_sub_graphs[id.as_str()].graph_attributes.clone();
+        graph_attributes
+            .insert(AttributeName::Color, AttributeText::from(REMOVED_COLOR));
+        graph_attributes.insert(
+            AttributeName::Style,
+            AttributeText::from(GraphStyle::Dashed),
+        );
+        removed_sub_graphs.insert(
+            id,
+            SubGraph::new(
+                Some(id.clone()),
+                graph_attributes,
+                IndexMap::new(),
+                IndexMap::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            ),
+        );
+    }
+    for id in &diff.removed_nodes {
+        let node = old_index.node(id).unwrap();
+        let mut statement = Node::new(id.clone());
+        statement.attributes = statement_attributes(&node.statements);
+        statement
+            .attributes
+            .insert(AttributeName::Color, AttributeText::from(REMOVED_COLOR));
+        statement
+            .attributes
+            .insert(AttributeName::Style, AttributeText::from(NodeStyle::Dashed));
+        let declared_in = old
+            .sub_graph(&node.sub_graphs[0])
+            .and_then(|sub_graph| sub_graph.id.as_deref())
+            .and_then(|id| removed_sub_graphs.get_mut(id));
+        match declared_in {
+            Some(sub_graph) => sub_graph.nodes.push(statement),
+            None => graph.nodes.push(statement),
+        }
+    }
+    graph
+        .sub_graphs
+        .extend(removed_sub_graphs.into_iter().map(|(_, s)| s));
+
+    for mut edge in diff.removed_edges {
+        edge.attributes
+            .insert(AttributeName::Color, AttributeText::from(REMOVED_COLOR));
+        edge.attributes
+            .insert(AttributeName::Style, AttributeText::from(EdgeStyle::Dashed));
+        graph.edges.push(edge);
+    }
+    graph
+}
+
+/// For every edge of the new graph, the position of the matching edge of the old graph
+/// in the order edges are rendered.
+fn match_edges(
+    old: &GraphIndex,
+    new: &GraphIndex,
+    is_directed: bool,
+) -> Vec<Option<usize>> {
+    let mut matched = vec![false; old.edges().len()];
+    new.edges()
+        .iter()
+        .map(|edge| {
+            let position = (0..matched.len()).find(|index| {
+                !matched[*index]
+                    && is_parallel(old.edges()[*index].edge, edge.edge, is_directed)
+            })?;
+            matched[position] = true;
+            Some(position)
+        })
+        .collect()
+}
+
+/// Changes from the old to the new attributes, in the order of the old attributes
+/// followed by those only in the new ones.
+fn attribute_changes<'a>(
+    old: &Attributes<'a>,
+    new: &Attributes<'a>,
+) -> Vec<AttributeChange<'a>> {
+    let mut changes = Vec::new();
+    for (name, value) in old {
+        let new_value = new.get(name);
+        if new_value != Some(value) {
+            changes.push(AttributeChange {
+                name: name.clone(),
+                old: Some(value.clone()),
+                new: new_value.cloned(),
+            });
+        }
+    }
+    for (name, value) in new {
+        if !old.contains_key(name) {
+            changes.push(AttributeChange {
+                name: name.clone(),
+                old: None,
+                new: Some(value.clone()),
+            });
+        }
+    }
+    changes
+}
+
+fn named_sub_graphs<'g, 'a>(
+    graph: &'g Graph<'a>,
+) -> IndexMap<&'g str, &'g SubGraph<'a>> {
+    graph
+        .all_sub_graphs()
+        .into_iter()
+        .filter_map(|(_, sub_graph)| Some((sub_graph.id.as_deref()?, sub_graph)))
+        .collect()
+}
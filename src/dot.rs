@@ -1,26 +1,28 @@
 // TODO: docs
 
 use crate::attributes::{
-    fmt_attributes, AttributeText, AttributeType, EdgeAttributes, NodeAttributes,
-    PortPosition,
+    fmt_attributes, get_typed, validate_attribute, ArrowType, AttributeContext,
+    AttributeName, AttributeText, AttributeType, Color, Direction, EdgeAttributes,
+    EdgeStyle, EdgeStyleList, GraphStyle, GraphStyleList, LayoutEngine, NodeAttributes,
+    NodeStyle, NodeStyleList, Overlap, Point, PortPosition, RankDir, Ratio, Shape,
+    Splines,
+};
+use crate::theme::{apply_classes, Theme};
+use crate::validation::{
+    locate_errors, validate_attributes, validate_layers, validate_layout_engine,
+    validate_structure, validate_styles, ElementPath, ErrorCode, PathSegment,
+    UndeclaredNodePolicy,
 };
 use indexmap::IndexMap;
 use std::borrow::Cow;
-use std::borrow::Cow::Borrowed;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::io;
 use std::io::prelude::*;
 
 static INDENT: &str = "    ";
 
-pub type ValidationResult<T> = std::result::Result<T, Vec<ValidationError>>;
-
-#[derive(Debug, PartialEq, Clone)]
-pub struct ValidationError {
-    pub message: Cow<'static, str>,
-    pub field: Cow<'static, str>,
-}
+pub use crate::validation::{ValidationError, ValidationResult};
 
 pub trait DotString<'a> {
     fn dot_string(&self) -> Cow<'a, str>;
@@ -59,27 +61,27 @@ impl<'a> Dot<'a> {
         writeln!(w, " {{")?;
 
         if !&graph.graph_attributes.is_empty() {
-            write!(
+            writeln!(
                 w,
-                "{}graph{};\n",
+                "{}graph{};",
                 INDENT,
                 fmt_attributes(&graph.graph_attributes)
             )?;
         }
 
         if !&graph.node_attributes.is_empty() {
-            write!(
+            writeln!(
                 w,
-                "{}node{};\n",
+                "{}node{};",
                 INDENT,
                 fmt_attributes(&graph.node_attributes)
             )?;
         }
 
         if !&graph.edge_attributes.is_empty() {
-            write!(
+            writeln!(
                 w,
-                "{}edge{};\n",
+                "{}edge{};",
                 INDENT,
                 fmt_attributes(&graph.edge_attributes)
             )?;
@@ -112,7 +114,7 @@ impl<'a> Dot<'a> {
     {
         write!(w, "{}subgraph", get_indentation(indentation_level))?;
         if let Some(id) = &sub_graph.id {
-            write!(w, " {}", id)?;
+            write!(w, " {}", AttributeText::from(id.as_str()).dot_string())?;
         }
 
         writeln!(w, " {{")?;
@@ -120,27 +122,27 @@ impl<'a> Dot<'a> {
         let indent = get_indentation(indentation_level + 1);
 
         if !&sub_graph.graph_attributes.is_empty() {
-            write!(
+            writeln!(
                 w,
-                "{}graph{};\n",
+                "{}graph{};",
                 indent,
                 fmt_attributes(&sub_graph.graph_attributes)
             )?;
         }
 
         if !&sub_graph.node_attributes.is_empty() {
-            write!(
+            writeln!(
                 w,
-                "{}node{};\n",
+                "{}node{};",
                 indent,
                 fmt_attributes(&sub_graph.node_attributes)
             )?;
         }
 
         if !&sub_graph.edge_attributes.is_empty() {
-            write!(
+            writeln!(
                 w,
-                "{}edge{};\n",
+                "{}edge{};",
                 indent,
                 fmt_attributes(&sub_graph.edge_attributes)
             )?;
@@ -171,13 +173,14 @@ impl<'a> Dot<'a> {
     where
         W: Write,
     {
-        let mut edge_source = edge.source.to_owned();
+        // Ports follow the quoted node id, quoting them with it would name another node
+        let mut edge_source = AttributeText::from(edge.source.to_owned()).dot_string();
         if let Some(source_port_position) = &edge.source_port_position {
             edge_source
                 .push_str(format!(":{}", source_port_position.dot_string()).as_str())
         }
 
-        let mut edge_target = edge.target.to_owned();
+        let mut edge_target = AttributeText::from(edge.target.to_owned()).dot_string();
         if let Some(target_port_position) = &edge.target_port_position {
             edge_target
                 .push_str(format!(":{}", target_port_position.dot_string()).as_str())
@@ -187,9 +190,9 @@ impl<'a> Dot<'a> {
             w,
             "{}{} {} {}",
             get_indentation(indentation_level),
-            AttributeText::from(edge_source).dot_string(),
+            edge_source,
             edge_op,
-            AttributeText::from(edge_target).dot_string(),
+            edge_target,
         )?;
         write!(w, "{}", fmt_attributes(&edge.attributes))?;
         writeln!(w, ";")
@@ -233,11 +236,11 @@ pub struct Graph<'a> {
     /// Comment added to the first line of the source.
     pub comment: Option<String>,
 
-    pub graph_attributes: IndexMap<String, AttributeText<'a>>,
+    pub graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    pub node_attributes: IndexMap<String, AttributeText<'a>>,
+    pub node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    pub edge_attributes: IndexMap<String, AttributeText<'a>>,
+    pub edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
     pub sub_graphs: Vec<SubGraph<'a>>,
 
@@ -247,14 +250,15 @@ pub struct Graph<'a> {
 }
 
 impl<'a> Graph<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: Option<AttributeText<'a>>,
         is_directed: bool,
         strict: bool,
         comment: Option<String>,
-        graph_attributes: IndexMap<String, AttributeText<'a>>,
-        node_attributes: IndexMap<String, AttributeText<'a>>,
-        edge_attributes: IndexMap<String, AttributeText<'a>>,
+        graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
+        node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
+        edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
         sub_graphs: Vec<SubGraph<'a>>,
         nodes: Vec<Node<'a>>,
         edges: Vec<Edge<'a>>,
@@ -273,6 +277,82 @@ impl<'a> Graph<'a> {
         }
     }
 
+    /// Returns the value of a graph attribute set on the root graph.
+    pub fn get<N: Into<AttributeName>>(&self, name: N) -> Option<&AttributeText<'a>> {
+        self.graph_attributes.get(&name.into())
+    }
+
+    /// Direction used for rank layout.
+    pub fn rank_dir(&self) -> Option<RankDir> {
+        get_typed(&self.graph_attributes, AttributeName::RankDir)
+    }
+
+    /// Layout engine the graph should be laid out with.
+    pub fn layout(&self) -> Option<LayoutEngine> {
+        get_typed(&self.graph_attributes, AttributeName::Layout)
+    }
+
+    /// How edges are represented.
+    pub fn splines(&self) -> Option<Splines> {
+        get_typed(&self.graph_attributes, AttributeName::Splines)
+    }
+
+    /// How node overlaps are removed.
+    pub fn overlap(&self) -> Option<Overlap> {
+        get_typed(&self.graph_attributes, AttributeName::Overlap)
+    }
+
+    /// Aspect ratio of the drawing.
+    pub fn ratio(&self) -> Option<Ratio> {
+        get_typed(&self.graph_attributes, AttributeName::Ratio)
+    }
+
+    /// Color used as the background of the canvas.
+    pub fn background_color(&self) -> Option<Color<'_>> {
+        get_typed(&self.graph_attributes, AttributeName::BackgroundColor)
+    }
+
+    /// Font size, in points, of the graph label.
+    pub fn font_size(&self) -> Option<f32> {
+        get_typed(&self.graph_attributes, AttributeName::FontSize)
+    }
+
+    /// Minimum space between two adjacent nodes in the same rank, in inches.
+    pub fn nodesep(&self) -> Option<f32> {
+        get_typed(&self.graph_attributes, AttributeName::NodeSep)
+    }
+
+    /// Style of the graph.
+    pub fn style(&self) -> Option<GraphStyle> {
+        get_typed(&self.graph_attributes, AttributeName::Style)
+    }
+
+    /// Styles of the graph, including parameterized and custom styles.
+    pub fn styles(&self) -> Option<GraphStyleList> {
+        get_typed(&self.graph_attributes, AttributeName::Style)
+    }
+
+    /// Label of the graph.
+    pub fn label(&self) -> Option<&str> {
+        self.graph_attributes
+            .get(&AttributeName::Label)
+            .map(AttributeText::as_str)
+    }
+
+    /// Font used for the graph label text.
+    pub fn font_name(&self) -> Option<&str> {
+        self.graph_attributes
+            .get(&AttributeName::FontName)
+            .map(AttributeText::as_str)
+    }
+
+    /// Separation between ranks.
+    pub fn rank_sep(&self) -> Option<&str> {
+        self.graph_attributes
+            .get(&AttributeName::RankSep)
+            .map(AttributeText::as_str)
+    }
+
     pub fn graph_type(&self) -> &'static str {
         if self.is_directed {
             "digraph"
@@ -297,11 +377,11 @@ pub struct GraphBuilder<'a> {
 
     strict: bool,
 
-    graph_attributes: IndexMap<String, AttributeText<'a>>,
+    graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    node_attributes: IndexMap<String, AttributeText<'a>>,
+    node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    edge_attributes: IndexMap<String, AttributeText<'a>>,
+    edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
     sub_graphs: Vec<SubGraph<'a>>,
 
@@ -311,6 +391,17 @@ pub struct GraphBuilder<'a> {
 
     comment: Option<String>,
 
+    undeclared_nodes: UndeclaredNodePolicy,
+
+    /// Ids of the clusters generated by `cluster_by`, by their key.
+    cluster_ids: IndexMap<String, String>,
+
+    /// Graph attributes of generated clusters, by their key.
+    cluster_templates: IndexMap<String, IndexMap<AttributeName, AttributeText<'a>>>,
+
+    /// Node attributes of the classes of the theme, by class name.
+    classes: IndexMap<String, IndexMap<AttributeName, AttributeText<'a>>>,
+
     errors: Vec<ValidationError>,
 }
 
@@ -343,6 +434,10 @@ impl<'a> GraphBuilder<'a> {
             nodes: Vec::new(),
             edges: Vec::new(),
             comment: None,
+            undeclared_nodes: UndeclaredNodePolicy::default(),
+            cluster_ids: IndexMap::new(),
+            cluster_templates: IndexMap::new(),
+            classes: IndexMap::new(),
             errors: Vec::new(),
         }
     }
@@ -354,7 +449,7 @@ impl<'a> GraphBuilder<'a> {
 
     pub fn add_graph_attributes(
         &mut self,
-        attributes: IndexMap<String, AttributeText<'a>>,
+        attributes: IndexMap<AttributeName, AttributeText<'a>>,
     ) -> &mut Self {
         self.graph_attributes.extend(attributes);
         self
@@ -362,7 +457,7 @@ impl<'a> GraphBuilder<'a> {
 
     pub fn add_node_attributes(
         &mut self,
-        node_attributes: IndexMap<String, AttributeText<'a>>,
+        node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
     ) -> &mut Self {
         self.node_attributes.extend(node_attributes);
         self
@@ -370,36 +465,37 @@ impl<'a> GraphBuilder<'a> {
 
     pub fn add_edge_attributes(
         &mut self,
-        edge_attributes: IndexMap<String, AttributeText<'a>>,
+        edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
     ) -> &mut Self {
         self.edge_attributes.extend(edge_attributes);
         self
     }
 
-    pub fn add_attribute<S: Into<String>>(
+    pub fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         attribute_type: AttributeType,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
+        let key = key.into();
+        let context = attribute_context(attribute_type, AttributeContext::Graph);
+        validate_attribute(&mut self.errors, &[context], &key, &value);
         match attribute_type {
-            AttributeType::Graph => self.graph_attributes.insert(key.into(), value),
-            AttributeType::Edge => self.edge_attributes.insert(key.into(), value),
-            AttributeType::Node => self.node_attributes.insert(key.into(), value),
+            AttributeType::Graph => self.graph_attributes.insert(key, value),
+            AttributeType::Edge => self.edge_attributes.insert(key, value),
+            AttributeType::Node => self.node_attributes.insert(key, value),
         };
         self
     }
 
-    pub fn extend_with_attributes(
+    pub fn extend_with_attributes<K: Into<AttributeName>>(
         &mut self,
         attribute_type: AttributeType,
-        attributes: HashMap<String, AttributeText<'a>>,
+        attributes: HashMap<K, AttributeText<'a>>,
     ) -> &mut Self {
-        match attribute_type {
-            AttributeType::Graph => self.graph_attributes.extend(attributes),
-            AttributeType::Edge => self.edge_attributes.extend(attributes),
-            AttributeType::Node => self.node_attributes.extend(attributes),
-        };
+        for (key, value) in attributes {
+            self.add_attribute(attribute_type, key, value);
+        }
         self
     }
 
@@ -423,16 +519,170 @@ impl<'a> GraphBuilder<'a> {
         self
     }
 
+    /// Adds the graph, node and edge defaults of the theme, and applies its classes to
+    /// the nodes given them with [`NodeBuilder::with_class`] when building.
+    /// Attributes set on a node take precedence over those of its classes.
+    pub fn with_theme(&mut self, theme: &Theme<'a>) -> &mut Self {
+        self.graph_attributes.extend(theme.graph_attributes.clone());
+        self.node_attributes.extend(theme.node_attributes.clone());
+        self.edge_attributes.extend(theme.edge_attributes.clone());
+        for (name, attributes) in &theme.classes {
+            self.classes
+                .entry(name.clone())
+                .or_default()
+                .extend(attributes.clone());
+        }
+        self
+    }
+
+    /// Moves the nodes added so far into generated clusters, grouped by the key returned
+    /// for each of them. Nodes without a key stay in the graph.
+    ///
+    /// Keys are split on `/` into nested clusters, so nodes with the keys `a/b` and `a/c`
+    /// end up in two clusters within the cluster for `a`. Clusters are labelled with the
+    /// last part of their key and get ids such as `cluster_a_b`, with characters which
+    /// are not valid in ids replaced by `_` and a number appended when the id is taken.
+    /// Repeated calls add to the clusters already generated for a key.
+    pub fn cluster_by<F>(&mut self, mut key: F) -> &mut Self
+    where
+        F: FnMut(&Node<'a>) -> Option<String>,
+    {
+        for node in std::mem::take(&mut self.nodes) {
+            let parts = key(&node).map_or_else(Vec::new, |key| cluster_key_parts(&key));
+            if parts.is_empty() {
+                self.nodes.push(node);
+                continue;
+            }
+            let id = self.generated_cluster(&parts);
+            find_sub_graph_mut(&mut self.sub_graphs, &id)
+                .unwrap()
+                .nodes
+                .push(node);
+        }
+        self
+    }
+
+    /// Graph attributes for the cluster generated by [`GraphBuilder::cluster_by`] for
+    /// the key, whether it has already been generated or not.
+    pub fn cluster_template<S: AsRef<str>>(
+        &mut self,
+        key: S,
+        attributes: IndexMap<AttributeName, AttributeText<'a>>,
+    ) -> &mut Self {
+        let key = cluster_key_parts(key.as_ref()).join("/");
+        if let Some(id) = self.cluster_ids.get(&key) {
+            if let Some(sub_graph) = find_sub_graph_mut(&mut self.sub_graphs, id) {
+                sub_graph.graph_attributes.extend(attributes.clone());
+            }
+        }
+        self.cluster_templates
+            .entry(key)
+            .or_default()
+            .extend(attributes);
+        self
+    }
+
+    /// The id of the cluster generated for the key parts, generating it and the clusters
+    /// for its parent keys if needed.
+    fn generated_cluster(&mut self, parts: &[String]) -> String {
+        let key = parts.join("/");
+        if let Some(id) = self.cluster_ids.get(&key) {
+            return id.clone();
+        }
+        let parent = match parts.len() {
+            1 => None,
+            len => Some(self.generated_cluster(&parts[..len - 1])),
+        };
+
+        let name: Vec<String> = parts
+            .iter()
+            .map(|part| {
+                part.chars()
+                    .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                    .collect()
+            })
+            .collect();
+        let name = format!("cluster_{}", name.join("_"));
+        let mut taken = HashSet::new();
+        collect_sub_graph_ids(&self.sub_graphs, &mut taken);
+        let id = std::iter::once(name.clone())
+            .chain((2..).map(|n| format!("{}_{}", name, n)))
+            .find(|id| !taken.contains(id.as_str()))
+            .unwrap();
+
+        let mut graph_attributes = IndexMap::new();
+        graph_attributes.insert(
+            AttributeName::Label,
+            AttributeText::quoted(parts[parts.len() - 1].clone()),
+        );
+        if let Some(template) = self.cluster_templates.get(&key) {
+            graph_attributes.extend(template.clone());
+        }
+        let sub_graph = SubGraph::new(
+            Some(id.clone()),
+            graph_attributes,
+            IndexMap::new(),
+            IndexMap::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+        let parent = parent.and_then(|id| find_sub_graph_mut(&mut self.sub_graphs, &id));
+        match parent {
+            Some(parent) => parent.sub_graphs.push(sub_graph),
+            None => self.sub_graphs.push(sub_graph),
+        }
+        self.cluster_ids.insert(key, id.clone());
+        id
+    }
+
+    /// How edges referencing nodes without a node statement are reported when building.
+    /// By default such nodes are created implicitly.
+    pub fn undeclared_nodes(&mut self, policy: UndeclaredNodePolicy) -> &mut Self {
+        self.undeclared_nodes = policy;
+        self
+    }
+
     pub fn build(&self) -> ValidationResult<Graph<'a>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+        self.build_with_warnings().map(|(graph, _)| graph)
+    }
+
+    /// Builds the graph, returning warnings such as attributes which have no effect
+    /// with the graph's layout engine alongside it.
+    /// Fails only if there are validation errors of [`Severity::Error`].
+    ///
+    /// [`Severity::Error`]: crate::validation::Severity::Error
+    pub fn build_with_warnings(
+        &self,
+    ) -> ValidationResult<(Graph<'a>, Vec<ValidationError>)> {
+        let graph = self.build_ignore_validation();
+        let path = ElementPath::new(PathSegment::Graph(
+            self.id.as_ref().map(|id| id.as_str().to_string()),
+        ));
+        let mut errors = locate_errors(&self.errors, &path, &self.graph_attributes);
+        // Builders of nodes, edges and subgraphs only fail on errors, so their warnings,
+        // such as unknown attributes, are found again in the built graph
+        for warning in validate_attributes(&graph) {
+            if !warning.is_error() && !errors.contains(&warning) {
+                errors.push(warning);
+            }
         }
-        Ok(self.build_ignore_validation())
+        errors.extend(validate_structure(&graph, self.undeclared_nodes));
+        errors.extend(validate_layers(&graph));
+        errors.extend(validate_styles(&graph));
+        errors.extend(validate_layout_engine(&graph));
+
+        let (errors, warnings): (Vec<_>, Vec<_>) =
+            errors.into_iter().partition(ValidationError::is_error);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok((graph, warnings))
     }
 
     pub fn build_ignore_validation(&self) -> Graph<'a> {
-        Graph {
-            id: self.id.to_owned().into(),
+        let mut graph = Graph {
+            id: self.id.to_owned(),
             is_directed: self.is_directed,
             strict: self.strict,
             comment: self.comment.clone(), // TODO: is clone the only option here?
@@ -442,19 +692,51 @@ impl<'a> GraphBuilder<'a> {
             sub_graphs: self.sub_graphs.clone(),
             nodes: self.nodes.clone(), // TODO: is clone the only option here?
             edges: self.edges.clone(), // TODO: is clone the only option here?
+        };
+        apply_classes(&mut graph, &self.classes);
+        graph
+    }
+}
+
+/// The parts of a `cluster_by` key, ignoring empty parts.
+fn cluster_key_parts(key: &str) -> Vec<String> {
+    key.split('/')
+        .filter(|part| !part.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn find_sub_graph_mut<'g, 'a>(
+    sub_graphs: &'g mut [SubGraph<'a>],
+    id: &str,
+) -> Option<&'g mut SubGraph<'a>> {
+    for sub_graph in sub_graphs {
+        if sub_graph.id.as_deref() == Some(id) {
+            return Some(sub_graph);
+        }
+        if let Some(found) = find_sub_graph_mut(&mut sub_graph.sub_graphs, id) {
+            return Some(found);
         }
     }
+    None
+}
+
+fn collect_sub_graph_ids<'g>(sub_graphs: &'g [SubGraph], ids: &mut HashSet<&'g str>) {
+    for sub_graph in sub_graphs {
+        ids.extend(sub_graph.id.as_deref());
+        collect_sub_graph_ids(&sub_graph.sub_graphs, ids);
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct SubGraph<'a> {
     pub id: Option<String>,
 
-    pub graph_attributes: IndexMap<String, AttributeText<'a>>,
+    pub graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    pub node_attributes: IndexMap<String, AttributeText<'a>>,
+    pub node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    pub edge_attributes: IndexMap<String, AttributeText<'a>>,
+    pub edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
     pub sub_graphs: Vec<SubGraph<'a>>,
 
     pub nodes: Vec<Node<'a>>,
@@ -465,9 +747,9 @@ pub struct SubGraph<'a> {
 impl<'a> SubGraph<'a> {
     pub fn new(
         id: Option<String>,
-        graph_attributes: IndexMap<String, AttributeText<'a>>,
-        node_attributes: IndexMap<String, AttributeText<'a>>,
-        edge_attributes: IndexMap<String, AttributeText<'a>>,
+        graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
+        node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
+        edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
         sub_graphs: Vec<SubGraph<'a>>,
         nodes: Vec<Node<'a>>,
         edges: Vec<Edge<'a>>,
@@ -482,16 +764,58 @@ impl<'a> SubGraph<'a> {
             edges,
         }
     }
+
+    /// Returns the value of a graph attribute set on the subgraph.
+    pub fn get<N: Into<AttributeName>>(&self, name: N) -> Option<&AttributeText<'a>> {
+        self.graph_attributes.get(&name.into())
+    }
+
+    /// Style of the subgraph.
+    pub fn style(&self) -> Option<GraphStyle> {
+        get_typed(&self.graph_attributes, AttributeName::Style)
+    }
+
+    /// Styles of the subgraph, including parameterized and custom styles.
+    pub fn styles(&self) -> Option<GraphStyleList> {
+        get_typed(&self.graph_attributes, AttributeName::Style)
+    }
+
+    /// Basic drawing color of the cluster.
+    pub fn color(&self) -> Option<Color<'_>> {
+        get_typed(&self.graph_attributes, AttributeName::Color)
+    }
+
+    /// Color used to fill the cluster.
+    pub fn fill_color(&self) -> Option<Color<'_>> {
+        get_typed(&self.graph_attributes, AttributeName::FillColor)
+    }
+
+    /// Color used to draw the bounding box of the cluster.
+    pub fn pen_color(&self) -> Option<Color<'_>> {
+        get_typed(&self.graph_attributes, AttributeName::PenColor)
+    }
+
+    /// Font size, in points, of the subgraph label.
+    pub fn font_size(&self) -> Option<f32> {
+        get_typed(&self.graph_attributes, AttributeName::FontSize)
+    }
+
+    /// Label of the subgraph.
+    pub fn label(&self) -> Option<&str> {
+        self.graph_attributes
+            .get(&AttributeName::Label)
+            .map(AttributeText::as_str)
+    }
 }
 
 pub struct SubGraphBuilder<'a> {
     id: Option<String>,
 
-    graph_attributes: IndexMap<String, AttributeText<'a>>,
+    graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    node_attributes: IndexMap<String, AttributeText<'a>>,
+    node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
-    edge_attributes: IndexMap<String, AttributeText<'a>>,
+    edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
 
     sub_graphs: Vec<SubGraph<'a>>,
 
@@ -502,6 +826,12 @@ pub struct SubGraphBuilder<'a> {
     errors: Vec<ValidationError>,
 }
 
+impl<'a> Default for SubGraphBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> SubGraphBuilder<'a> {
     pub fn new() -> Self {
         Self::new_inner(None)
@@ -526,7 +856,7 @@ impl<'a> SubGraphBuilder<'a> {
 
     pub fn add_graph_attributes(
         &mut self,
-        graph_attributes: IndexMap<String, AttributeText<'a>>,
+        graph_attributes: IndexMap<AttributeName, AttributeText<'a>>,
     ) -> &mut Self {
         self.graph_attributes.extend(graph_attributes);
         self
@@ -534,7 +864,7 @@ impl<'a> SubGraphBuilder<'a> {
 
     pub fn add_node_attributes(
         &mut self,
-        node_attributes: IndexMap<String, AttributeText<'a>>,
+        node_attributes: IndexMap<AttributeName, AttributeText<'a>>,
     ) -> &mut Self {
         self.node_attributes.extend(node_attributes);
         self
@@ -542,7 +872,7 @@ impl<'a> SubGraphBuilder<'a> {
 
     pub fn add_edge_attributes(
         &mut self,
-        edge_attributes: IndexMap<String, AttributeText<'a>>,
+        edge_attributes: IndexMap<AttributeName, AttributeText<'a>>,
     ) -> &mut Self {
         self.edge_attributes.extend(edge_attributes);
         self
@@ -567,12 +897,16 @@ impl<'a> SubGraphBuilder<'a> {
     //     self
     // }
 
-    pub fn add_attribute(
+    pub fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         attribute_type: AttributeType,
-        key: String,
+        key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
+        let key = key.into();
+        let graph_context = AttributeContext::for_sub_graph(self.id.as_deref());
+        let context = attribute_context(attribute_type, graph_context);
+        validate_attribute(&mut self.errors, &[context], &key, &value);
         match attribute_type {
             AttributeType::Graph => {
                 self.graph_attributes.insert(key, value);
@@ -603,8 +937,9 @@ impl<'a> SubGraphBuilder<'a> {
     }
 
     pub fn build(&self) -> ValidationResult<SubGraph<'a>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+        if self.errors.iter().any(ValidationError::is_error) {
+            let path = ElementPath::new(PathSegment::SubGraph(self.id.clone()));
+            return Err(locate_errors(&self.errors, &path, &self.graph_attributes));
         }
 
         Ok(SubGraph {
@@ -622,7 +957,7 @@ impl<'a> SubGraphBuilder<'a> {
 #[derive(Clone, Debug)]
 pub struct Node<'a> {
     pub id: AttributeText<'a>,
-    pub attributes: IndexMap<String, AttributeText<'a>>,
+    pub attributes: IndexMap<AttributeName, AttributeText<'a>>,
 }
 
 impl<'a> Node<'a> {
@@ -633,51 +968,151 @@ impl<'a> Node<'a> {
             attributes: IndexMap::new(),
         }
     }
+
+    /// Returns the value of an attribute set on the node.
+    pub fn get<N: Into<AttributeName>>(&self, name: N) -> Option<&AttributeText<'a>> {
+        self.attributes.get(&name.into())
+    }
+
+    /// Shape of the node.
+    pub fn shape(&self) -> Option<Shape> {
+        get_typed(&self.attributes, AttributeName::Shape)
+    }
+
+    /// Style of the node.
+    pub fn style(&self) -> Option<NodeStyle> {
+        get_typed(&self.attributes, AttributeName::Style)
+    }
+
+    /// Styles of the node, including parameterized and custom styles.
+    pub fn styles(&self) -> Option<NodeStyleList> {
+        get_typed(&self.attributes, AttributeName::Style)
+    }
+
+    /// Basic drawing color of the node.
+    pub fn color(&self) -> Option<Color<'_>> {
+        get_typed(&self.attributes, AttributeName::Color)
+    }
+
+    /// Color used to fill the node.
+    pub fn fill_color(&self) -> Option<Color<'_>> {
+        get_typed(&self.attributes, AttributeName::FillColor)
+    }
+
+    /// Color used for the node label text.
+    pub fn font_color(&self) -> Option<Color<'_>> {
+        get_typed(&self.attributes, AttributeName::FontColor)
+    }
+
+    /// Font size, in points, of the node label.
+    pub fn font_size(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::FontSize)
+    }
+
+    /// Width of the node, in inches.
+    pub fn width(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::Width)
+    }
+
+    /// Height of the node, in inches.
+    pub fn height(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::Height)
+    }
+
+    /// Width of the pen, in points, used to draw the node.
+    pub fn pen_width(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::PenWidth)
+    }
+
+    /// Number of peripheries of the node.
+    pub fn peripheries(&self) -> Option<u32> {
+        get_typed(&self.attributes, AttributeName::Peripheries)
+    }
+
+    /// Position of the node.
+    pub fn pos(&self) -> Option<Point> {
+        get_typed(&self.attributes, AttributeName::Pos)
+    }
+
+    /// Whether the node size is fixed by its width and height.
+    pub fn fixed_size(&self) -> Option<bool> {
+        get_typed(&self.attributes, AttributeName::FixedSize)
+    }
+
+    /// Label of the node.
+    pub fn label(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::Label)
+            .map(AttributeText::as_str)
+    }
+
+    /// External label of the node.
+    pub fn xlabel(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::XLabel)
+            .map(AttributeText::as_str)
+    }
+
+    /// Font used for the node label text.
+    pub fn font_name(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::FontName)
+            .map(AttributeText::as_str)
+    }
+
+    /// Tooltip of the node.
+    pub fn tooltip(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::Tooltip)
+            .map(AttributeText::as_str)
+    }
 }
 
 impl<'a> DotString<'a> for Node<'a> {
     fn dot_string(&self) -> Cow<'a, str> {
-        let mut dot_string = format!("{}", &self.id.dot_string());
+        let mut dot_string = self.id.dot_string();
         dot_string.push_str(fmt_attributes(&self.attributes).as_str());
-        dot_string.push_str(";");
+        dot_string.push(';');
         dot_string.into()
     }
 }
 
 pub struct NodeBuilder<'a> {
     id: String,
-    attributes: IndexMap<String, AttributeText<'a>>,
+    attributes: IndexMap<AttributeName, AttributeText<'a>>,
     errors: Vec<ValidationError>,
 }
 
 impl<'a> NodeAttributes<'a> for NodeBuilder<'a> {
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
-        self.attributes.insert(key.into(), value);
+        let key = key.into();
+        validate_attribute(&mut self.errors, &[AttributeContext::Node], &key, &value);
+        self.attributes.insert(key, value);
         self
     }
 
     /// Add multiple attributes to the edge.
-    fn add_attributes(
+    fn add_attributes<K: Into<AttributeName>>(
         &'a mut self,
-        attributes: HashMap<String, AttributeText<'a>>,
-    ) -> &mut Self {
-        self.attributes.extend(attributes);
+        attributes: HashMap<K, AttributeText<'a>>,
+    ) -> &'a mut Self {
+        for (key, value) in attributes {
+            self.add_attribute(key, value);
+        }
         self
     }
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>> {
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>> {
         &mut self.attributes
     }
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str) {
-        self.errors.push(ValidationError {
-            field: Borrowed(field),
-            message: Borrowed(message),
-        })
+        self.errors
+            .push(ValidationError::new(ErrorCode::OutOfRange, field, message))
     }
 }
 
@@ -690,9 +1125,22 @@ impl<'a> NodeBuilder<'a> {
         }
     }
 
+    /// Adds a class to the node, whose attributes are applied by the theme of the graph
+    /// the node is added to, see [`GraphBuilder::with_theme`].
+    /// Classes are kept in the node's `class` attribute, so they can also be used to
+    /// style SVG output.
+    pub fn with_class<S: AsRef<str>>(&mut self, class: S) -> &mut Self {
+        let classes = match self.attributes.get(&AttributeName::Class) {
+            Some(classes) => format!("{} {}", classes.as_str(), class.as_ref()),
+            None => class.as_ref().to_string(),
+        };
+        self.class(classes)
+    }
+
     pub fn build(&self) -> ValidationResult<Node<'a>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+        if self.errors.iter().any(ValidationError::is_error) {
+            let path = ElementPath::new(PathSegment::Node(self.id.clone()));
+            return Err(locate_errors(&self.errors, &path, &self.attributes));
         }
         Ok(self.build_ignore_validation())
     }
@@ -712,14 +1160,14 @@ pub struct Edge<'a> {
     pub source_port_position: Option<PortPosition>,
     pub target: String,
     pub target_port_position: Option<PortPosition>,
-    pub attributes: IndexMap<String, AttributeText<'a>>,
+    pub attributes: IndexMap<AttributeName, AttributeText<'a>>,
 }
 
 impl<'a> Edge<'a> {
     pub fn new<S, T>(source: S, target: T) -> Self
     where
         S: Into<String>,
-        T: Into<String>
+        T: Into<String>,
     {
         Self {
             source: source.into(),
@@ -738,7 +1186,7 @@ impl<'a> Edge<'a> {
     ) -> Self
     where
         S: Into<String>,
-        T: Into<String>
+        T: Into<String>,
     {
         Self {
             source: source.into(),
@@ -748,6 +1196,126 @@ impl<'a> Edge<'a> {
             attributes: IndexMap::new(),
         }
     }
+
+    /// Returns the value of an attribute set on the edge.
+    pub fn get<N: Into<AttributeName>>(&self, name: N) -> Option<&AttributeText<'a>> {
+        self.attributes.get(&name.into())
+    }
+
+    /// Style of the arrowhead at the head end.
+    pub fn arrow_head(&self) -> Option<ArrowType> {
+        get_typed(&self.attributes, AttributeName::ArrowHead)
+    }
+
+    /// Style of the arrowhead at the tail end.
+    pub fn arrow_tail(&self) -> Option<ArrowType> {
+        get_typed(&self.attributes, AttributeName::ArrowTail)
+    }
+
+    /// Multiplicative scale factor for the arrowheads.
+    pub fn arrow_size(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::ArrowSize)
+    }
+
+    /// Basic drawing color of the edge.
+    pub fn color(&self) -> Option<Color<'_>> {
+        get_typed(&self.attributes, AttributeName::Color)
+    }
+
+    /// Color used for the edge label text.
+    pub fn font_color(&self) -> Option<Color<'_>> {
+        get_typed(&self.attributes, AttributeName::FontColor)
+    }
+
+    /// Font size, in points, of the edge label.
+    pub fn font_size(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::FontSize)
+    }
+
+    /// Edge type for drawing arrowheads.
+    pub fn dir(&self) -> Option<Direction> {
+        get_typed(&self.attributes, AttributeName::Dir)
+    }
+
+    /// Style of the edge.
+    pub fn style(&self) -> Option<EdgeStyle> {
+        get_typed(&self.attributes, AttributeName::Style)
+    }
+
+    /// Styles of the edge, including parameterized and custom styles.
+    pub fn styles(&self) -> Option<EdgeStyleList> {
+        get_typed(&self.attributes, AttributeName::Style)
+    }
+
+    /// Width of the pen, in points, used to draw the edge.
+    pub fn pen_width(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::PenWidth)
+    }
+
+    /// Weight of the edge, which may be fractional for layout engines other than dot.
+    pub fn weight(&self) -> Option<f64> {
+        get_typed(&self.attributes, AttributeName::Weight)
+    }
+
+    /// Preferred edge length, in inches.
+    pub fn len(&self) -> Option<f32> {
+        get_typed(&self.attributes, AttributeName::Len)
+    }
+
+    /// Minimum edge length in rank difference between head and tail.
+    pub fn min_len(&self) -> Option<u32> {
+        get_typed(&self.attributes, AttributeName::MinLen)
+    }
+
+    /// Whether the edge is used in ranking the nodes.
+    pub fn constraint(&self) -> Option<bool> {
+        get_typed(&self.attributes, AttributeName::Constraint)
+    }
+
+    /// Where on the head node to attach the head of the edge.
+    pub fn head_port(&self) -> Option<PortPosition> {
+        get_typed(&self.attributes, AttributeName::HeadPort)
+    }
+
+    /// Where on the tail node to attach the tail of the edge.
+    pub fn tail_port(&self) -> Option<PortPosition> {
+        get_typed(&self.attributes, AttributeName::TailPort)
+    }
+
+    /// Label of the edge.
+    pub fn label(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::Label)
+            .map(AttributeText::as_str)
+    }
+
+    /// External label of the edge.
+    pub fn xlabel(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::XLabel)
+            .map(AttributeText::as_str)
+    }
+
+    /// Label placed near the head of the edge.
+    pub fn head_label(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::HeadLabel)
+            .map(AttributeText::as_str)
+    }
+
+    /// Label placed near the tail of the edge.
+    pub fn tail_label(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::TailLabel)
+            .map(AttributeText::as_str)
+    }
+
+    /// Font used for the edge label text.
+    pub fn font_name(&self) -> Option<&str> {
+        self.attributes
+            .get(&AttributeName::FontName)
+            .map(AttributeText::as_str)
+    }
 }
 
 pub struct EdgeBuilder<'a> {
@@ -755,29 +1323,29 @@ pub struct EdgeBuilder<'a> {
     pub source_port_position: Option<PortPosition>,
     pub target: String,
     pub target_port_position: Option<PortPosition>,
-    attributes: IndexMap<String, AttributeText<'a>>,
+    attributes: IndexMap<AttributeName, AttributeText<'a>>,
     errors: Vec<ValidationError>,
 }
 
 impl<'a> EdgeAttributes<'a> for EdgeBuilder<'a> {
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
-        self.attributes.insert(key.into(), value);
+        let key = key.into();
+        validate_attribute(&mut self.errors, &[AttributeContext::Edge], &key, &value);
+        self.attributes.insert(key, value);
         self
     }
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>> {
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>> {
         &mut self.attributes
     }
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str) {
-        self.errors.push(ValidationError {
-            field: Borrowed(field),
-            message: Borrowed(message),
-        })
+        self.errors
+            .push(ValidationError::new(ErrorCode::OutOfRange, field, message))
     }
 }
 
@@ -819,27 +1387,36 @@ impl<'a> EdgeBuilder<'a> {
         self
     }
     /// Add an attribute to the edge.
-    pub fn add_attribute<S: Into<String>>(
+    pub fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
-        self.attributes.insert(key.into(), value);
+        let key = key.into();
+        validate_attribute(&mut self.errors, &[AttributeContext::Edge], &key, &value);
+        self.attributes.insert(key, value);
         self
     }
 
     /// Add multiple attributes to the edge.
-    pub fn add_attributes(
+    pub fn add_attributes<K: Into<AttributeName>>(
         &'a mut self,
-        attributes: HashMap<String, AttributeText<'a>>,
-    ) -> &mut Self {
-        self.attributes.extend(attributes);
+        attributes: HashMap<K, AttributeText<'a>>,
+    ) -> &'a mut Self {
+        for (key, value) in attributes {
+            self.add_attribute(key, value);
+        }
         self
     }
 
     pub fn build(&self) -> ValidationResult<Edge<'a>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+        if self.errors.iter().any(ValidationError::is_error) {
+            let path = ElementPath::new(PathSegment::Edge {
+                index: None,
+                source: self.source.clone(),
+                target: self.target.clone(),
+            });
+            return Err(locate_errors(&self.errors, &path, &self.attributes));
         }
         Ok(self.build_ignore_validation())
     }
@@ -857,42 +1434,50 @@ impl<'a> EdgeBuilder<'a> {
 }
 
 impl<'a> NodeAttributes<'a> for NodeAttributeStatementBuilder<'a> {
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
-        self.attributes.insert(key.into(), value);
+        let key = key.into();
+        validate_attribute(&mut self.errors, &[AttributeContext::Node], &key, &value);
+        self.attributes.insert(key, value);
         self
     }
 
     /// Add multiple attributes to the node.
-    fn add_attributes(
+    fn add_attributes<K: Into<AttributeName>>(
         &'a mut self,
-        attributes: HashMap<String, AttributeText<'a>>,
-    ) -> &mut Self {
-        self.attributes.extend(attributes);
+        attributes: HashMap<K, AttributeText<'a>>,
+    ) -> &'a mut Self {
+        for (key, value) in attributes {
+            self.add_attribute(key, value);
+        }
         self
     }
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>> {
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>> {
         &mut self.attributes
     }
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str) {
-        self.errors.push(ValidationError {
-            field: Borrowed(field),
-            message: Borrowed(message),
-        })
+        self.errors
+            .push(ValidationError::new(ErrorCode::OutOfRange, field, message))
     }
 }
 
 // I'm not a huge fan of needing this builder but having a hard time getting around &mut without it
 pub struct NodeAttributeStatementBuilder<'a> {
-    pub attributes: IndexMap<String, AttributeText<'a>>,
+    pub attributes: IndexMap<AttributeName, AttributeText<'a>>,
     errors: Vec<ValidationError>,
 }
 
+impl<'a> Default for NodeAttributeStatementBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> NodeAttributeStatementBuilder<'a> {
     pub fn new() -> Self {
         Self {
@@ -901,46 +1486,53 @@ impl<'a> NodeAttributeStatementBuilder<'a> {
         }
     }
 
-    pub fn build(&self) -> ValidationResult<IndexMap<String, AttributeText<'a>>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+    pub fn build(&self) -> ValidationResult<IndexMap<AttributeName, AttributeText<'a>>> {
+        if self.errors.iter().any(ValidationError::is_error) {
+            let path = ElementPath::default();
+            return Err(locate_errors(&self.errors, &path, &self.attributes));
         }
         Ok(self.build_ignore_validation())
     }
 
-    pub fn build_ignore_validation(&self) -> IndexMap<String, AttributeText<'a>> {
+    pub fn build_ignore_validation(&self) -> IndexMap<AttributeName, AttributeText<'a>> {
         self.attributes.clone()
     }
 }
 
 impl<'a> EdgeAttributes<'a> for EdgeAttributeStatementBuilder<'a> {
-    fn add_attribute<S: Into<String>>(
+    fn add_attribute<S: Into<AttributeName>>(
         &mut self,
         key: S,
         value: AttributeText<'a>,
     ) -> &mut Self {
-        self.attributes.insert(key.into(), value);
+        let key = key.into();
+        validate_attribute(&mut self.errors, &[AttributeContext::Edge], &key, &value);
+        self.attributes.insert(key, value);
         self
     }
 
-    fn get_attributes_mut(&mut self) -> &mut IndexMap<String, AttributeText<'a>> {
+    fn get_attributes_mut(&mut self) -> &mut IndexMap<AttributeName, AttributeText<'a>> {
         &mut self.attributes
     }
 
     fn add_validation_error(&mut self, field: &'static str, message: &'static str) {
-        self.errors.push(ValidationError {
-            field: Borrowed(field),
-            message: Borrowed(message),
-        })
+        self.errors
+            .push(ValidationError::new(ErrorCode::OutOfRange, field, message))
     }
 }
 
 // I'm not a huge fan of needing this builder but having a hard time getting around &mut without it
 pub struct EdgeAttributeStatementBuilder<'a> {
-    pub attributes: IndexMap<String, AttributeText<'a>>,
+    pub attributes: IndexMap<AttributeName, AttributeText<'a>>,
     errors: Vec<ValidationError>,
 }
 
+impl<'a> Default for EdgeAttributeStatementBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> EdgeAttributeStatementBuilder<'a> {
     pub fn new() -> Self {
         Self {
@@ -949,18 +1541,30 @@ impl<'a> EdgeAttributeStatementBuilder<'a> {
         }
     }
 
-    pub fn build(&self) -> ValidationResult<IndexMap<String, AttributeText<'a>>> {
-        if !self.errors.is_empty() {
-            return Err(self.errors.clone());
+    pub fn build(&self) -> ValidationResult<IndexMap<AttributeName, AttributeText<'a>>> {
+        if self.errors.iter().any(ValidationError::is_error) {
+            let path = ElementPath::default();
+            return Err(locate_errors(&self.errors, &path, &self.attributes));
         }
         Ok(self.build_ignore_validation())
     }
 
-    pub fn build_ignore_validation(&self) -> IndexMap<String, AttributeText<'a>> {
+    pub fn build_ignore_validation(&self) -> IndexMap<AttributeName, AttributeText<'a>> {
         self.attributes.clone()
     }
 }
 
+fn attribute_context(
+    attribute_type: AttributeType,
+    graph_context: AttributeContext,
+) -> AttributeContext {
+    match attribute_type {
+        AttributeType::Graph => graph_context,
+        AttributeType::Node => AttributeContext::Node,
+        AttributeType::Edge => AttributeContext::Edge,
+    }
+}
+
 fn get_indentation(indentation_level: usize) -> String {
     INDENT.repeat(indentation_level)
 }
@@ -0,0 +1,167 @@
+use crate::attributes::{AttributeName, AttributeText};
+use crate::dot::{Edge, Graph, Node, SubGraph};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+
+// Effective attributes are resolved the way Graphviz does when reading the rendered DOT.
+impl<'a> Graph<'a> {
+    /// The subgraph at `path`, or `None` if the path does not exist or is empty.
+    ///
+    /// Subgraphs are addressed by a path of indices, each indexing into the `sub_graphs` of
+    /// the previous subgraph starting from the root graph.
+    /// An empty path addresses the root graph.
+    pub fn sub_graph(&self, path: &[usize]) -> Option<&SubGraph<'a>> {
+        let (first, rest) = path.split_first()?;
+        let mut sub_graph = self.sub_graphs.get(*first)?;
+        for index in rest {
+            sub_graph = sub_graph.sub_graphs.get(*index)?;
+        }
+        Some(sub_graph)
+    }
+
+    /// Graph attributes in effect for the subgraph at `path`.
+    /// Subgraphs inherit the graph attributes of their parents.
+    pub fn effective_graph_attributes(&self, path: &[usize]) -> Option<Attributes<'a>> {
+        let mut attributes = self.graph_attributes.clone();
+        for sub_graph in self.sub_graph_chain(path)? {
+            extend(&mut attributes, &sub_graph.graph_attributes);
+        }
+        Some(attributes)
+    }
+
+    /// Attributes in effect for the edge at `index` within the subgraph at `path`.
+    /// These are the edge defaults of the root graph, then those of each enclosing subgraph,
+    /// then the edge's own attributes.
+    pub fn effective_edge_attributes(
+        &self,
+        path: &[usize],
+        index: usize,
+    ) -> Option<Attributes<'a>> {
+        let chain = self.sub_graph_chain(path)?;
+        let edge = match chain.last() {
+            Some(sub_graph) => sub_graph.edges.get(index)?,
+            None => self.edges.get(index)?,
+        };
+
+        let mut attributes = self.edge_attributes.clone();
+        for sub_graph in chain {
+            extend(&mut attributes, &sub_graph.edge_attributes);
+        }
+        extend(&mut attributes, &edge.attributes);
+        Some(attributes)
+    }
+
+    /// Attributes in effect for the node with the given id,
+    /// or `None` if no node statement or edge refers to it.
+    ///
+    /// A node picks up the node defaults in scope where it first appears, either in a node
+    /// statement or as an edge endpoint, in the order statements are rendered.
+    /// Attributes of every statement declaring the node are then applied in order.
+    pub fn effective_node_attributes(&self, id: &str) -> Option<Attributes<'a>> {
+        self.resolve_nodes(Some(id)).shift_remove(id)
+    }
+
+    /// Attributes in effect for every node, keyed by id in the order the nodes first
+    /// appear, resolved in a single walk of the graph as `effective_node_attributes`
+    /// would resolve each of them.
+    pub(crate) fn all_effective_node_attributes(
+        &self,
+    ) -> IndexMap<String, Attributes<'a>> {
+        self.resolve_nodes(None)
+    }
+
+    /// Attributes in effect for the node with the given id, or every node if `None`.
+    fn resolve_nodes(&self, id: Option<&str>) -> IndexMap<String, Attributes<'a>> {
+        let mut resolution = NodeResolution {
+            id,
+            defaults: IndexMap::new(),
+            attributes: HashMap::new(),
+        };
+        resolution.visit(
+            &self.node_attributes,
+            &self.sub_graphs,
+            &self.nodes,
+            &self.edges,
+        );
+
+        let mut nodes = resolution.defaults;
+        for (id, attributes) in nodes.iter_mut() {
+            if let Some(own) = resolution.attributes.get(id.as_str()) {
+                extend(attributes, own);
+            }
+        }
+        nodes
+    }
+
+    /// Subgraphs along `path`, outermost first.
+    fn sub_graph_chain(&self, path: &[usize]) -> Option<Vec<&SubGraph<'a>>> {
+        let mut chain = Vec::with_capacity(path.len());
+        let mut sub_graphs = &self.sub_graphs;
+        for index in path {
+            let sub_graph = sub_graphs.get(*index)?;
+            chain.push(sub_graph);
+            sub_graphs = &sub_graph.sub_graphs;
+        }
+        Some(chain)
+    }
+}
+
+fn extend<'a>(attributes: &mut Attributes<'a>, other: &Attributes<'a>) {
+    attributes.extend(other.iter().map(|(k, v)| (k.clone(), v.clone())));
+}
+
+/// Resolution of the attributes of nodes in the order they appear.
+struct NodeResolution<'g, 'a> {
+    /// Only the node with this id is resolved, if given.
+    id: Option<&'g str>,
+    /// Node defaults in scope where each node first appears.
+    defaults: IndexMap<String, Attributes<'a>>,
+    /// Attributes from the statements of each node.
+    attributes: HashMap<&'g str, Attributes<'a>>,
+}
+
+impl<'g, 'a> NodeResolution<'g, 'a> {
+    fn visit(
+        &mut self,
+        defaults: &Attributes<'a>,
+        sub_graphs: &'g [SubGraph<'a>],
+        nodes: &'g [Node<'a>],
+        edges: &'g [Edge<'a>],
+    ) {
+        for sub_graph in sub_graphs {
+            let mut sub_graph_defaults = defaults.clone();
+            extend(&mut sub_graph_defaults, &sub_graph.node_attributes);
+            self.visit(
+                &sub_graph_defaults,
+                &sub_graph.sub_graphs,
+                &sub_graph.nodes,
+                &sub_graph.edges,
+            );
+        }
+
+        for node in nodes {
+            let id = node.id.as_str();
+            if self.resolves(id) {
+                self.first_appearance(id, defaults);
+                extend(self.attributes.entry(id).or_default(), &node.attributes);
+            }
+        }
+
+        for edge in edges {
+            self.first_appearance(&edge.source, defaults);
+            self.first_appearance(&edge.target, defaults);
+        }
+    }
+
+    fn resolves(&self, id: &str) -> bool {
+        self.id.is_none_or(|only| only == id)
+    }
+
+    fn first_appearance(&mut self, id: &str, defaults: &Attributes<'a>) {
+        if self.resolves(id) && !self.defaults.contains_key(id) {
+            self.defaults.insert(id.to_string(), defaults.clone());
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Extraction of the part of a graph around or made up of selected nodes.
+//!
+//! Extracted graphs keep the attributes and node and edge defaults of the graph and of
+//! every subgraph which still contains nodes or edges. Subgraphs left empty are removed.
+
+use crate::attributes::{AttributeName, AttributeText, EdgeStyle, Shape};
+use crate::dot::{Edge, Graph, Node, SubGraph};
+use crate::index::{Direction, IndexedNode};
+use indexmap::IndexSet;
+use std::collections::VecDeque;
+
+/// Label of placeholder nodes standing in for the nodes of cut edges.
+pub const PLACEHOLDER_LABEL: &str = "…";
+
+/// Extracts parts of a graph, optionally marking where edges were cut.
+///
+/// ```rust
+/// use dotavious::index::Direction;
+/// use dotavious::{Edge, GraphBuilder};
+///
+/// let graph = GraphBuilder::new_directed()
+///     .add_edge(Edge::new("a", "b"))
+///     .add_edge(Edge::new("b", "c"))
+///     .build()
+///     .unwrap();
+///
+/// let around_a = graph
+///     .extract()
+///     .placeholders(true)
+///     .neighborhood("a", 1, Direction::Both);
+/// assert_eq!(2, around_a.edges.len());
+/// ```
+pub struct Extractor<'g, 'a> {
+    graph: &'g Graph<'a>,
+    placeholders: bool,
+}
+
+impl<'g, 'a> Extractor<'g, 'a> {
+    pub fn new(graph: &'g Graph<'a>) -> Self {
+        Self {
+            graph,
+            placeholders: false,
+        }
+    }
+
+    /// Whether edges to nodes which are not extracted are replaced by dashed edges to
+    /// placeholder nodes labelled "…". By default such edges are removed.
+    pub fn placeholders(&mut self, placeholders: bool) -> &mut Self {
+        self.placeholders = placeholders;
+        self
+    }
+
+    /// The node with the given id and the nodes reachable from it within `depth` edges
+    /// in the direction.
+    pub fn neighborhood(
+        &self,
+        id: &str,
+        depth: usize,
+        direction: Direction,
+    ) -> Graph<'a> {
+        let index = self.graph.index();
+        let mut ids = IndexSet::new();
+        if index.node(id).is_some() {
+            ids.insert(id);
+        }
+        let mut queue = VecDeque::from(vec![(id, 0)]);
+        while let Some((id, distance)) = queue.pop_front() {
+            if distance == depth {
+                continue;
+            }
+            for adjacent in index.adjacent(id, direction) {
+                if ids.insert(adjacent) {
+                    queue.push_back((adjacent, distance + 1));
+                }
+            }
+        }
+        self.induced_subgraph(&ids.into_iter().collect::<Vec<_>>())
+    }
+
+    /// The nodes matching the predicate.
+    pub fn filter_nodes<F>(&self, mut predicate: F) -> Graph<'a>
+    where
+        F: FnMut(&IndexedNode) -> bool,
+    {
+        let index = self.graph.index();
+        let ids: Vec<&str> = index
+            .nodes()
+            .filter(|node| predicate(node))
+            .map(|node| node.id)
+            .collect();
+        self.induced_subgraph(&ids)
+    }
+
+    /// The given nodes and the edges between them.
+    pub fn induced_subgraph<S: AsRef<str>>(&self, ids: &[S]) -> Graph<'a> {
+        let keep: IndexSet<&str> = ids.iter().map(AsRef::as_ref).collect();
+        let kept_edge = |edge: &Edge| {
+            keep.contains(edge.source.as_str()) && keep.contains(edge.target.as_str())
+        };
+        let index = self.graph.index();
+        let mut graph = self.graph.clone();
+
+        // Nodes only existing through edges which are removed need a statement where they
+        // first appeared to be kept.
+        for node in index.nodes() {
+            let lost = keep.contains(node.id)
+                && node.is_implicit()
+                && !index
+                    .incident_edges(node.id)
+                    .iter()
+                    .any(|e| kept_edge(e.edge));
+            if lost {
+                let statement = Node::new(node.id.to_string());
+                match graph.sub_graph_mut(&node.sub_graphs[0]) {
+                    Some(sub_graph) => sub_graph.nodes.push(statement),
+                    None => graph.nodes.push(statement),
+                }
+            }
+        }
+
+        graph.for_each_scope_mut(&mut |scope| {
+            scope.nodes.retain(|node| keep.contains(node.id.as_str()));
+            scope.edges.retain(|edge| kept_edge(edge));
+        });
+        remove_empty_sub_graphs(&mut graph.sub_graphs);
+
+        if self.placeholders {
+            let mut placeholder_ids = (0..).map(|n| format!("cut_{}", n));
+            let mut cut: IndexSet<(&str, bool)> = IndexSet::new();
+            for edge in index.edges().iter().map(|e| e.edge) {
+                let source = keep.contains(edge.source.as_str());
+                let target = keep.contains(edge.target.as_str());
+                if source && !target {
+                    cut.insert((edge.source.as_str(), true));
+                } else if target && !source {
+                    cut.insert((edge.target.as_str(), false));
+                }
+            }
+            for (id, outgoing) in cut {
+                let placeholder = placeholder_ids
+                    .by_ref()
+                    .find(|p| index.node(p).is_none())
+                    .unwrap();
+                graph.nodes.push(placeholder_node(&placeholder));
+                let mut edge = if outgoing {
+                    Edge::new(id, placeholder)
+                } else {
+                    Edge::new(placeholder, id)
+                };
+                edge.attributes.insert(
+                    AttributeName::Style,
+                    AttributeText::from(EdgeStyle::Dashed),
+                );
+                graph.edges.push(edge);
+            }
+        }
+        graph
+    }
+}
+
+impl<'a> Graph<'a> {
+    /// Extracts parts of the graph, see [`Extractor`].
+    pub fn extract(&self) -> Extractor<'_, 'a> {
+        Extractor::new(self)
+    }
+
+    /// The node with the given id and the nodes reachable from it within `depth` edges
+    /// in the direction, with the edges between them.
+    pub fn neighborhood(
+        &self,
+        id: &str,
+        depth: usize,
+        direction: Direction,
+    ) -> Graph<'a> {
+        self.extract().neighborhood(id, depth, direction)
+    }
+
+    /// The nodes matching the predicate with the edges between them.
+    pub fn filter_nodes<F>(&self, predicate: F) -> Graph<'a>
+    where
+        F: FnMut(&IndexedNode) -> bool,
+    {
+        self.extract().filter_nodes(predicate)
+    }
+
+    /// The given nodes with the edges between them.
+    pub fn induced_subgraph<S: AsRef<str>>(&self, ids: &[S]) -> Graph<'a> {
+        self.extract().induced_subgraph(ids)
+    }
+}
+
+fn placeholder_node<'a>(id: &str) -> Node<'a> {
+    let mut node = Node::new(id.to_string());
+    node.attributes.insert(
+        AttributeName::Label,
+        AttributeText::quoted(PLACEHOLDER_LABEL),
+    );
+    node.attributes
+        .insert(AttributeName::Shape, AttributeText::from(Shape::Plaintext));
+    node
+}
+
+fn remove_empty_sub_graphs(sub_graphs: &mut Vec<SubGraph>) {
+    for sub_graph in sub_graphs.iter_mut() {
+        remove_empty_sub_graphs(&mut sub_graph.sub_graphs);
+    }
+    sub_graphs.retain(|sub_graph| {
+        !sub_graph.nodes.is_empty()
+            || !sub_graph.edges.is_empty()
+            || !sub_graph.sub_graphs.is_empty()
+    });
+}
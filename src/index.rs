@@ -0,0 +1,321 @@
+//! Lookups and traversal over the nodes and edges of a graph.
+
+use crate::dot::{Edge, Graph, Node, SubGraph};
+use indexmap::{IndexMap, IndexSet};
+use std::collections::HashMap;
+
+impl<'a> Graph<'a> {
+    /// Builds an index of the graph for looking up nodes and their edges.
+    ///
+    /// The index borrows the graph, so it has to be rebuilt after the graph is modified.
+    pub fn index(&self) -> GraphIndex<'_, 'a> {
+        GraphIndex::new(self)
+    }
+
+    /// Every subgraph with its path, parents before their subgraphs.
+    ///
+    /// See [`Graph::sub_graph`] for how paths address subgraphs.
+    pub fn all_sub_graphs(&self) -> Vec<(Vec<usize>, &SubGraph<'a>)> {
+        let mut sub_graphs = Vec::new();
+        let mut stack: Vec<(Vec<usize>, &SubGraph<'a>)> = self
+            .sub_graphs
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, sub_graph)| (vec![index], sub_graph))
+            .collect();
+        while let Some((path, sub_graph)) = stack.pop() {
+            for (index, child) in sub_graph.sub_graphs.iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.push(index);
+                stack.push((child_path, child));
+            }
+            sub_graphs.push((path, sub_graph));
+        }
+        sub_graphs
+    }
+}
+
+/// Which edges of a node are followed when traversing a graph.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// Edges going out of the node, towards its successors.
+    Outgoing,
+
+    /// Edges coming into the node, from its predecessors.
+    Incoming,
+
+    /// Edges in either direction.
+    Both,
+}
+
+/// A node of an indexed graph.
+#[derive(Clone, Debug)]
+pub struct IndexedNode<'g, 'a> {
+    pub id: &'g str,
+
+    /// Statements declaring the node, in the order they are rendered.
+    /// Empty for nodes which only exist implicitly as edge endpoints.
+    pub statements: Vec<&'g Node<'a>>,
+
+    /// Paths of the graph or subgraphs the node appears in, either in a node statement
+    /// or as an edge endpoint, in the order they are rendered.
+    /// The node also belongs to every subgraph enclosing these.
+    pub sub_graphs: Vec<Vec<usize>>,
+}
+
+impl<'g, 'a> IndexedNode<'g, 'a> {
+    /// Whether the node is only created through edges referencing it.
+    pub fn is_implicit(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Whether the node appears within the subgraph at `path` or any of its subgraphs.
+    pub fn is_in_sub_graph(&self, path: &[usize]) -> bool {
+        self.sub_graphs.iter().any(|p| p.starts_with(path))
+    }
+}
+
+/// An edge of an indexed graph.
+#[derive(Clone, Debug)]
+pub struct IndexedEdge<'g, 'a> {
+    pub edge: &'g Edge<'a>,
+
+    /// Path of the graph or subgraph containing the edge.
+    pub sub_graph: Vec<usize>,
+
+    /// Index of the edge within the edges of its graph or subgraph.
+    pub index: usize,
+}
+
+/// Index of the nodes and edges of a graph, including those of nested subgraphs.
+///
+/// Nodes are ordered by their first appearance and edges in the order they are
+/// rendered. For undirected graphs successors, predecessors and neighbors are the
+/// same, and in and out degrees are the number of edge ends at the node.
+pub struct GraphIndex<'g, 'a> {
+    is_directed: bool,
+    nodes: IndexMap<&'g str, IndexedNode<'g, 'a>>,
+    edges: Vec<IndexedEdge<'g, 'a>>,
+    /// Indices of the edges with each node as their source.
+    outgoing: HashMap<&'g str, Vec<usize>>,
+    /// Indices of the edges with each node as their target.
+    incoming: HashMap<&'g str, Vec<usize>>,
+}
+
+impl<'g, 'a> GraphIndex<'g, 'a> {
+    pub fn new(graph: &'g Graph<'a>) -> Self {
+        let mut index = Self {
+            is_directed: graph.is_directed,
+            nodes: IndexMap::new(),
+            edges: Vec::new(),
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+        };
+        index.visit(&[], &graph.sub_graphs, &graph.nodes, &graph.edges);
+        index
+    }
+
+    fn visit(
+        &mut self,
+        path: &[usize],
+        sub_graphs: &'g [SubGraph<'a>],
+        nodes: &'g [Node<'a>],
+        edges: &'g [Edge<'a>],
+    ) {
+        for (index, sub_graph) in sub_graphs.iter().enumerate() {
+            let mut sub_graph_path = path.to_vec();
+            sub_graph_path.push(index);
+            self.visit(
+                &sub_graph_path,
+                &sub_graph.sub_graphs,
+                &sub_graph.nodes,
+                &sub_graph.edges,
+            );
+        }
+        for node in nodes {
+            self.appear(node.id.as_str(), path).statements.push(node);
+        }
+        for (index, edge) in edges.iter().enumerate() {
+            self.appear(&edge.source, path);
+            self.appear(&edge.target, path);
+            let edge_index = self.edges.len();
+            self.outgoing
+                .entry(&edge.source)
+                .or_default()
+                .push(edge_index);
+            self.incoming
+                .entry(&edge.target)
+                .or_default()
+                .push(edge_index);
+            self.edges.push(IndexedEdge {
+                edge,
+                sub_graph: path.to_vec(),
+                index,
+            });
+        }
+    }
+
+    fn appear(&mut self, id: &'g str, path: &[usize]) -> &mut IndexedNode<'g, 'a> {
+        let node = self.nodes.entry(id).or_insert_with(|| IndexedNode {
+            id,
+            statements: Vec::new(),
+            sub_graphs: Vec::new(),
+        });
+        if !node.sub_graphs.iter().any(|p| p == path) {
+            node.sub_graphs.push(path.to_vec());
+        }
+        node
+    }
+
+    /// The node with the given id, whether declared or only referenced by edges.
+    pub fn node(&self, id: &str) -> Option<&IndexedNode<'g, 'a>> {
+        self.nodes.get(id)
+    }
+
+    /// Every node of the graph.
+    pub fn nodes(&self) -> impl Iterator<Item = &IndexedNode<'g, 'a>> {
+        self.nodes.values()
+    }
+
+    /// Nodes appearing within the subgraph at `path` or any of its subgraphs.
+    /// An empty path returns every node of the graph.
+    pub fn nodes_in_sub_graph(&self, path: &[usize]) -> Vec<&IndexedNode<'g, 'a>> {
+        self.nodes()
+            .filter(|node| node.is_in_sub_graph(path))
+            .collect()
+    }
+
+    /// Every edge of the graph.
+    pub fn edges(&self) -> &[IndexedEdge<'g, 'a>] {
+        &self.edges
+    }
+
+    /// Edges going out of the node.
+    pub fn out_edges(&self, id: &str) -> Vec<&IndexedEdge<'g, 'a>> {
+        if self.is_directed {
+            self.edges_at(&self.outgoing, id)
+        } else {
+            self.incident_edges(id)
+        }
+    }
+
+    /// Edges coming into the node.
+    pub fn in_edges(&self, id: &str) -> Vec<&IndexedEdge<'g, 'a>> {
+        if self.is_directed {
+            self.edges_at(&self.incoming, id)
+        } else {
+            self.incident_edges(id)
+        }
+    }
+
+    /// Edges with the node at either end.
+    pub fn incident_edges(&self, id: &str) -> Vec<&IndexedEdge<'g, 'a>> {
+        let mut indices: Vec<usize> = self
+            .outgoing
+            .get(id)
+            .into_iter()
+            .chain(self.incoming.get(id))
+            .flatten()
+            .copied()
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+            .into_iter()
+            .map(|index| &self.edges[index])
+            .collect()
+    }
+
+    fn edges_at(
+        &self,
+        edges: &HashMap<&'g str, Vec<usize>>,
+        id: &str,
+    ) -> Vec<&IndexedEdge<'g, 'a>> {
+        edges.get(id).map_or_else(Vec::new, |indices| {
+            indices.iter().map(|index| &self.edges[*index]).collect()
+        })
+    }
+
+    /// Nodes the node has an edge to, without duplicates.
+    pub fn successors(&self, id: &str) -> Vec<&'g str> {
+        unique(
+            self.out_edges(id)
+                .into_iter()
+                .map(|e| other_end(e.edge, id)),
+        )
+    }
+
+    /// Nodes with an edge to the node, without duplicates.
+    pub fn predecessors(&self, id: &str) -> Vec<&'g str> {
+        unique(self.in_edges(id).into_iter().map(|e| other_end(e.edge, id)))
+    }
+
+    /// Nodes connected to the node by an edge in either direction, without duplicates.
+    pub fn neighbors(&self, id: &str) -> Vec<&'g str> {
+        unique(
+            self.incident_edges(id)
+                .into_iter()
+                .map(|e| other_end(e.edge, id)),
+        )
+    }
+
+    /// Nodes connected to the node by edges in the direction, without duplicates.
+    pub fn adjacent(&self, id: &str, direction: Direction) -> Vec<&'g str> {
+        match direction {
+            Direction::Outgoing => self.successors(id),
+            Direction::Incoming => self.predecessors(id),
+            Direction::Both => self.neighbors(id),
+        }
+    }
+
+    pub fn in_degree(&self, id: &str) -> usize {
+        self.in_edges(id)
+            .iter()
+            .map(|e| self.ends(e.edge, id))
+            .sum()
+    }
+
+    pub fn out_degree(&self, id: &str) -> usize {
+        self.out_edges(id)
+            .iter()
+            .map(|e| self.ends(e.edge, id))
+            .sum()
+    }
+
+    /// Edges from `source` to `target`.
+    /// For undirected graphs edges between the two nodes in either direction.
+    pub fn edges_between(
+        &self,
+        source: &str,
+        target: &str,
+    ) -> Vec<&IndexedEdge<'g, 'a>> {
+        self.out_edges(source)
+            .into_iter()
+            .filter(|e| other_end(e.edge, source) == target)
+            .collect()
+    }
+
+    /// Number of ends of the edge counted towards the degrees of the node: one, except
+    /// for loops in undirected graphs which count twice as both their ends are at it.
+    fn ends(&self, edge: &Edge, id: &str) -> usize {
+        if !self.is_directed && edge.source == id && edge.target == id {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// The end of the edge which is not `id`, or `id` for loops.
+fn other_end<'g>(edge: &'g Edge, id: &str) -> &'g str {
+    if edge.source == id {
+        &edge.target
+    } else {
+        &edge.source
+    }
+}
+
+fn unique<'g, I: Iterator<Item = &'g str>>(ids: I) -> Vec<&'g str> {
+    ids.collect::<IndexSet<_>>().into_iter().collect()
+}
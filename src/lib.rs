@@ -174,8 +174,23 @@
 //! }
 //! ```
 
+pub mod algo;
 pub mod attributes;
+mod collapse;
+pub mod derive;
+pub mod diff;
 pub mod dot;
+mod effective;
+pub mod extract;
+pub mod index;
+pub mod lint;
+#[doc(hidden)]
+pub mod macros;
+pub mod merge;
+pub mod mutation;
+pub mod parse;
+pub mod stylesheet;
+pub mod theme;
 pub mod validation;
 
 #[doc(hidden)]
@@ -0,0 +1,774 @@
+//! Opinionated checks for graphs which are valid but likely not what was intended.
+//!
+//! Lints are identified by name and each reports at a configurable [`LintLevel`].
+//! Custom lints can be added to a [`Linter`] by implementing [`Lint`].
+//!
+//! ```rust
+//! use dotavious::lint::{LongLabel, Linter};
+//! use dotavious::{Edge, GraphBuilder};
+//!
+//! let graph = GraphBuilder::new_directed()
+//!     .add_edge(Edge::new("a", "b"))
+//!     .build()
+//!     .unwrap();
+//!
+//! let diagnostics = Linter::default()
+//!     .deny("unused-defaults")
+//!     .add_lint(LongLabel::new(20))
+//!     .lint(&graph);
+//! assert!(diagnostics.is_empty());
+//! ```
+
+use crate::attributes::{
+    AttributeName, AttributeText, EdgeStyle, EdgeStyleList, NodeStyle, NodeStyleList,
+    RankDir,
+};
+use crate::dot::{Edge, Graph, Node, SubGraph};
+use crate::validation::{
+    validate_layout_engine, walk, ElementPath, PathSegment, Severity,
+};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+
+/// How diagnostics of a lint are reported.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum LintLevel {
+    /// The lint is not run.
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    /// Severity of diagnostics reported at this level, `None` if they are not reported.
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Deny => Some(Severity::Error),
+        }
+    }
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        })
+    }
+}
+
+/// A problem reported by a lint.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    /// Name of the lint reporting it. Filled in by the [`Linter`].
+    pub lint: String,
+
+    /// Level the lint is configured at. Filled in by the [`Linter`].
+    pub level: LintLevel,
+
+    pub message: String,
+
+    pub path: ElementPath,
+
+    /// The attribute the diagnostic is about, if any.
+    pub field: Option<String>,
+
+    /// The offending value, if any.
+    pub value: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new<M: Into<String>>(path: ElementPath, message: M) -> Self {
+        Self {
+            lint: String::new(),
+            level: LintLevel::Warn,
+            message: message.into(),
+            path,
+            field: None,
+            value: None,
+        }
+    }
+
+    pub fn with_field<S: Into<String>>(mut self, field: S) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    pub fn with_value<S: Into<String>>(mut self, value: S) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Whether the lint reporting this is denied.
+    pub fn is_denied(&self) -> bool {
+        self.level == LintLevel::Deny
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = self.level.severity().unwrap_or(Severity::Info);
+        write!(f, "{}[{}]: ", severity, self.lint)?;
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        if let Some(field) = &self.field {
+            f.write_str(field)?;
+            if let Some(value) = &self.value {
+                write!(f, " = \"{}\"", value)?;
+            }
+            f.write_str(": ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// A check run over a whole graph.
+pub trait Lint {
+    /// Name identifying the lint when configuring its level, in kebab-case.
+    fn name(&self) -> &str;
+
+    /// Short description of what the lint reports.
+    fn description(&self) -> &str;
+
+    /// Level used unless configured otherwise.
+    fn default_level(&self) -> LintLevel {
+        LintLevel::Warn
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic>;
+}
+
+/// Runs a set of lints at their configured levels.
+///
+/// The default linter runs every built-in lint which needs no configuration.
+pub struct Linter {
+    lints: Vec<Box<dyn Lint>>,
+    levels: HashMap<String, LintLevel>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        let mut linter = Linter::new();
+        linter
+            .add_lint(UnusedDefaults)
+            .add_lint(ShadowedDefault)
+            .add_lint(RecordRankDir)
+            .add_lint(LongLabel::default())
+            .add_lint(InvisibleNodeEdges)
+            .add_lint(EngineNoOp);
+        linter
+    }
+}
+
+impl Linter {
+    /// A linter without any lints.
+    pub fn new() -> Self {
+        Self {
+            lints: Vec::new(),
+            levels: HashMap::new(),
+        }
+    }
+
+    /// Adds a lint, replacing any lint with the same name.
+    pub fn add_lint<L: Lint + 'static>(&mut self, lint: L) -> &mut Self {
+        self.lints.retain(|l| l.name() != lint.name());
+        self.lints.push(Box::new(lint));
+        self
+    }
+
+    pub fn level<S: Into<String>>(&mut self, name: S, level: LintLevel) -> &mut Self {
+        self.levels.insert(name.into(), level);
+        self
+    }
+
+    pub fn allow<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.level(name, LintLevel::Allow)
+    }
+
+    pub fn warn<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.level(name, LintLevel::Warn)
+    }
+
+    pub fn deny<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.level(name, LintLevel::Deny)
+    }
+
+    /// Names of the lints run, in the order they were added.
+    pub fn lint_names(&self) -> impl Iterator<Item = &str> {
+        self.lints.iter().map(|lint| lint.name())
+    }
+
+    /// Level the lint with the given name is run at, `None` if there is no such lint.
+    pub fn level_of(&self, name: &str) -> Option<LintLevel> {
+        let lint = self.lints.iter().find(|lint| lint.name() == name)?;
+        Some(self.configured_level(lint.as_ref()))
+    }
+
+    /// Runs every lint which is not allowed over the graph.
+    pub fn lint(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for lint in &self.lints {
+            let level = self.configured_level(lint.as_ref());
+            if level == LintLevel::Allow {
+                continue;
+            }
+            diagnostics.extend(lint.check(graph).into_iter().map(|mut diagnostic| {
+                diagnostic.lint = lint.name().to_string();
+                diagnostic.level = level;
+                diagnostic
+            }));
+        }
+        diagnostics
+    }
+
+    fn configured_level(&self, lint: &dyn Lint) -> LintLevel {
+        self.levels
+            .get(lint.name())
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+}
+
+/// Reports `node [...]` and `edge [...]` defaults which no node or edge picks up.
+/// Defaults only apply to nodes first appearing and edges within the same graph or
+/// subgraph after them.
+pub struct UnusedDefaults;
+
+impl Lint for UnusedDefaults {
+    fn name(&self) -> &str {
+        "unused-defaults"
+    }
+
+    fn description(&self) -> &str {
+        "node or edge defaults which do not apply to any node or edge"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let appearances = appearances(graph);
+        let mut diagnostics = Vec::new();
+        for scope in scopes(graph) {
+            if !scope.node_defaults.is_empty()
+                && !appearances
+                    .values()
+                    .any(|a| a.indices.starts_with(&scope.indices))
+            {
+                diagnostics.push(Diagnostic::new(
+                    scope.path.clone(),
+                    "Node defaults are not applied to any node",
+                ));
+            }
+            if !scope.edge_defaults.is_empty()
+                && !scopes_within(graph, &scope.indices).any(|s| !s.edges.is_empty())
+            {
+                diagnostics.push(Diagnostic::new(
+                    scope.path.clone(),
+                    "Edge defaults are not applied to any edge",
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Reports default attributes which every node or edge they apply to overrides,
+/// either with a default of a nested subgraph or with its own attribute.
+pub struct ShadowedDefault;
+
+impl Lint for ShadowedDefault {
+    fn name(&self) -> &str {
+        "shadowed-default"
+    }
+
+    fn description(&self) -> &str {
+        "default attributes overridden by every node or edge they apply to"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let appearances = appearances(graph);
+        let mut node_statements: HashMap<&str, Vec<&Attributes>> = HashMap::new();
+        for scope in scopes(graph) {
+            for node in scope.nodes {
+                node_statements
+                    .entry(node.id.as_str())
+                    .or_default()
+                    .push(&node.attributes);
+            }
+        }
+
+        let mut diagnostics = Vec::new();
+        for scope in scopes(graph) {
+            let nodes: Vec<&Appearance> = appearances
+                .values()
+                .filter(|a| a.indices.starts_with(&scope.indices))
+                .collect();
+            for (key, value) in scope.node_defaults {
+                let shadowed = !nodes.is_empty()
+                    && nodes.iter().all(|a| {
+                        redefined(graph, &scope.indices, &a.indices, key, |s| {
+                            &s.node_attributes
+                        }) || node_statements.get(a.id.as_str()).is_some_and(|s| {
+                            s.iter().any(|attrs| attrs.contains_key(key))
+                        })
+                    });
+                if shadowed {
+                    diagnostics.push(shadowed_diagnostic(&scope, key, value, "node"));
+                }
+            }
+
+            let edges: Vec<(Vec<usize>, &Edge)> = scopes_within(graph, &scope.indices)
+                .flat_map(|s| {
+                    let indices = s.indices;
+                    s.edges.iter().map(move |edge| (indices.clone(), edge))
+                })
+                .collect();
+            for (key, value) in scope.edge_defaults {
+                let shadowed = !edges.is_empty()
+                    && edges.iter().all(|(indices, edge)| {
+                        redefined(graph, &scope.indices, indices, key, |s| {
+                            &s.edge_attributes
+                        }) || edge.attributes.contains_key(key)
+                    });
+                if shadowed {
+                    diagnostics.push(shadowed_diagnostic(&scope, key, value, "edge"));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+fn shadowed_diagnostic(
+    scope: &Scope,
+    key: &AttributeName,
+    value: &AttributeText,
+    kind: &str,
+) -> Diagnostic {
+    Diagnostic::new(
+        scope.path.clone(),
+        format!("Default is overridden by every {} it applies to", kind),
+    )
+    .with_field(key.to_string())
+    .with_value(value.as_str())
+}
+
+/// Whether a subgraph below `from` down to and including `to` sets `key` in the
+/// defaults selected by `defaults`.
+fn redefined<'a, F>(
+    graph: &Graph<'a>,
+    from: &[usize],
+    to: &[usize],
+    key: &AttributeName,
+    defaults: F,
+) -> bool
+where
+    F: for<'s> Fn(&'s SubGraph<'a>) -> &'s Attributes<'a>,
+{
+    (from.len() + 1..=to.len()).any(|len| {
+        graph
+            .sub_graph(&to[..len])
+            .is_some_and(|sub_graph| defaults(sub_graph).contains_key(key))
+    })
+}
+
+/// Reports record shaped nodes in graphs laid out left to right or right to left.
+/// The rankdir flips the orientation of record fields, so the fields of such
+/// nodes are laid out vertically.
+pub struct RecordRankDir;
+
+impl Lint for RecordRankDir {
+    fn name(&self) -> &str {
+        "record-rankdir"
+    }
+
+    fn description(&self) -> &str {
+        "record shaped nodes whose fields are flipped by the graph's rankdir"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let rank_dir = match graph.rank_dir() {
+            Some(rank_dir @ RankDir::LeftRight)
+            | Some(rank_dir @ RankDir::RightLeft) => rank_dir,
+            _ => return Vec::new(),
+        };
+        let effective_attributes = graph.all_effective_node_attributes();
+        let mut diagnostics = Vec::new();
+        for appearance in appearances(graph).values() {
+            let shape = effective_attributes
+                .get(&appearance.id)
+                .and_then(|attributes| attributes.get(&AttributeName::Shape).cloned());
+            if let Some(shape) = shape {
+                if shape.as_str() == "record" || shape.as_str() == "Mrecord" {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            appearance.path.clone(),
+                            format!(
+                                "Record fields are flipped by rankdir={}, consider an \
+                                 HTML label",
+                                crate::DotString::dot_string(&rank_dir)
+                            ),
+                        )
+                        .with_field(AttributeName::Shape.to_string())
+                        .with_value(shape.as_str()),
+                    );
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Reports labels with a line longer than `max_length` characters.
+/// HTML labels are not checked.
+pub struct LongLabel {
+    pub max_length: usize,
+}
+
+impl LongLabel {
+    pub fn new(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl Default for LongLabel {
+    fn default() -> Self {
+        Self::new(40)
+    }
+}
+
+impl Lint for LongLabel {
+    fn name(&self) -> &str {
+        "long-label"
+    }
+
+    fn description(&self) -> &str {
+        "labels with long lines which are not broken up"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let labels = [
+            AttributeName::Label,
+            AttributeName::XLabel,
+            AttributeName::HeadLabel,
+            AttributeName::TailLabel,
+        ];
+        let mut diagnostics = Vec::new();
+        walk(graph, &mut |path, element| {
+            let attributes = element.attributes();
+            for key in &labels {
+                let value = match attributes.get(key) {
+                    Some(AttributeText::HtmlStr(_)) | None => continue,
+                    Some(value) => value.as_str(),
+                };
+                let longest = longest_line(value);
+                if longest > self.max_length {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            path.clone(),
+                            format!(
+                                "Line of {} characters is longer than {}, consider \
+                                 breaking it with \\n",
+                                longest, self.max_length
+                            ),
+                        )
+                        .with_field(key.to_string())
+                        .with_value(value),
+                    );
+                }
+            }
+        });
+        diagnostics
+    }
+}
+
+/// Number of characters of the longest line, lines being ended by `\n`, `\l` or `\r`
+/// escapes or newlines.
+fn longest_line(label: &str) -> usize {
+    let mut longest = 0;
+    let mut length = 0;
+    let mut chars = label.chars().peekable();
+    while let Some(c) = chars.next() {
+        let line_end = match c {
+            '\n' => true,
+            '\\' => matches!(chars.peek(), Some('n') | Some('l') | Some('r')),
+            _ => false,
+        };
+        if line_end {
+            if c == '\\' {
+                chars.next();
+            }
+            longest = longest.max(length);
+            length = 0;
+        } else {
+            length += 1;
+        }
+    }
+    longest.max(length)
+}
+
+/// Reports fontname attributes naming a font outside of an allow-list.
+/// Fonts are compared ignoring ASCII case.
+pub struct FontAllowList {
+    pub fonts: Vec<String>,
+}
+
+impl FontAllowList {
+    pub fn new<I, S>(fonts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            fonts: fonts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Lint for FontAllowList {
+    fn name(&self) -> &str {
+        "font-allow-list"
+    }
+
+    fn description(&self) -> &str {
+        "fonts which are not in the allow-list"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        walk(graph, &mut |path, element| {
+            let font = match element.attributes().get(&AttributeName::FontName) {
+                Some(font) => font.as_str(),
+                None => return,
+            };
+            if !self.fonts.iter().any(|f| f.eq_ignore_ascii_case(font)) {
+                diagnostics.push(
+                    Diagnostic::new(path.clone(), "Font is not in the allow-list")
+                        .with_field(AttributeName::FontName.to_string())
+                        .with_value(font),
+                );
+            }
+        });
+        diagnostics
+    }
+}
+
+/// Reports invisible nodes with visible edges, which are drawn ending in empty space.
+pub struct InvisibleNodeEdges;
+
+impl Lint for InvisibleNodeEdges {
+    fn name(&self) -> &str {
+        "invisible-node-edges"
+    }
+
+    fn description(&self) -> &str {
+        "invisible nodes with visible edges"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let mut visible_edges: HashMap<&str, usize> = HashMap::new();
+        for scope in scopes(graph) {
+            for (index, edge) in scope.edges.iter().enumerate() {
+                let invisible = graph
+                    .effective_edge_attributes(&scope.indices, index)
+                    .and_then(|attributes| {
+                        attributes
+                            .get(&AttributeName::Style)
+                            .and_then(|style| EdgeStyleList::try_from(style).ok())
+                    })
+                    .is_some_and(|styles| styles.contains(&EdgeStyle::Invisible));
+                if !invisible {
+                    *visible_edges.entry(&edge.source).or_default() += 1;
+                    if edge.target != edge.source {
+                        *visible_edges.entry(&edge.target).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        let effective_attributes = graph.all_effective_node_attributes();
+        let mut diagnostics = Vec::new();
+        for appearance in appearances(graph).values() {
+            let count = match visible_edges.get(appearance.id.as_str()) {
+                Some(count) => *count,
+                None => continue,
+            };
+            let invisible = effective_attributes
+                .get(&appearance.id)
+                .and_then(|attributes| {
+                    attributes
+                        .get(&AttributeName::Style)
+                        .and_then(|style| NodeStyleList::try_from(style).ok())
+                })
+                .is_some_and(|styles| styles.contains(&NodeStyle::Invisible));
+            if invisible {
+                diagnostics.push(Diagnostic::new(
+                    appearance.path.clone(),
+                    format!(
+                        "Node {} is invisible but has {} visible edge{}",
+                        appearance.id,
+                        count,
+                        if count == 1 { "" } else { "s" }
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Reports attributes which have no effect with the graph's layout engine.
+///
+/// See [`validate_layout_engine`].
+pub struct EngineNoOp;
+
+impl Lint for EngineNoOp {
+    fn name(&self) -> &str {
+        "engine-no-op"
+    }
+
+    fn description(&self) -> &str {
+        "attributes which have no effect with the graph's layout engine"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        validate_layout_engine(graph)
+            .into_iter()
+            .map(|error| {
+                let diagnostic =
+                    Diagnostic::new(error.path, error.message).with_field(error.field);
+                match error.value {
+                    Some(value) => diagnostic.with_value(value),
+                    None => diagnostic,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The root graph or a subgraph with the statements it directly contains.
+struct Scope<'g, 'a> {
+    /// Subgraph indices, empty for the root graph.
+    indices: Vec<usize>,
+    path: ElementPath,
+    node_defaults: &'g Attributes<'a>,
+    edge_defaults: &'g Attributes<'a>,
+    sub_graphs: &'g [SubGraph<'a>],
+    nodes: &'g [Node<'a>],
+    edges: &'g [Edge<'a>],
+}
+
+impl<'g, 'a> Scope<'g, 'a> {
+    fn root(graph: &'g Graph<'a>) -> Self {
+        let id = graph.id.as_ref().map(|id| id.as_str().to_string());
+        Self {
+            indices: Vec::new(),
+            path: ElementPath::new(PathSegment::Graph(id)),
+            node_defaults: &graph.node_attributes,
+            edge_defaults: &graph.edge_attributes,
+            sub_graphs: &graph.sub_graphs,
+            nodes: &graph.nodes,
+            edges: &graph.edges,
+        }
+    }
+
+    fn children(&self) -> Vec<Scope<'g, 'a>> {
+        self.sub_graphs
+            .iter()
+            .enumerate()
+            .map(|(index, sub_graph)| {
+                let mut indices = self.indices.clone();
+                indices.push(index);
+                Scope {
+                    indices,
+                    path: self.path.child(PathSegment::SubGraph(sub_graph.id.clone())),
+                    node_defaults: &sub_graph.node_attributes,
+                    edge_defaults: &sub_graph.edge_attributes,
+                    sub_graphs: &sub_graph.sub_graphs,
+                    nodes: &sub_graph.nodes,
+                    edges: &sub_graph.edges,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Every scope of the graph, parents before their subgraphs.
+fn scopes<'g, 'a>(graph: &'g Graph<'a>) -> Vec<Scope<'g, 'a>> {
+    let mut scopes = Vec::new();
+    let mut stack = vec![Scope::root(graph)];
+    while let Some(scope) = stack.pop() {
+        stack.extend(scope.children().into_iter().rev());
+        scopes.push(scope);
+    }
+    scopes
+}
+
+/// The scope at `indices` and every scope nested within it.
+fn scopes_within<'g, 'a, 'i>(
+    graph: &'g Graph<'a>,
+    indices: &'i [usize],
+) -> impl Iterator<Item = Scope<'g, 'a>> + 'i
+where
+    'g: 'i,
+{
+    scopes(graph)
+        .into_iter()
+        .filter(move |scope| scope.indices.starts_with(indices))
+}
+
+/// Where a node first appears, either in a node statement or as an edge endpoint.
+struct Appearance {
+    id: String,
+    indices: Vec<usize>,
+    path: ElementPath,
+}
+
+/// First appearance of every node, in the order statements are rendered.
+fn appearances(graph: &Graph) -> IndexMap<String, Appearance> {
+    fn visit(scope: &Scope, appearances: &mut IndexMap<String, Appearance>) {
+        for child in scope.children() {
+            visit(&child, appearances);
+        }
+        let mut appear = |id: &str, segment: PathSegment| {
+            if !appearances.contains_key(id) {
+                appearances.insert(
+                    id.to_string(),
+                    Appearance {
+                        id: id.to_string(),
+                        indices: scope.indices.clone(),
+                        path: scope.path.child(segment),
+                    },
+                );
+            }
+        };
+        for node in scope.nodes {
+            appear(
+                node.id.as_str(),
+                PathSegment::Node(node.id.as_str().to_string()),
+            );
+        }
+        for (index, edge) in scope.edges.iter().enumerate() {
+            for id in &[&edge.source, &edge.target] {
+                appear(
+                    id,
+                    PathSegment::Edge {
+                        index: Some(index),
+                        source: edge.source.clone(),
+                        target: edge.target.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    let mut appearances = IndexMap::new();
+    visit(&Scope::root(graph), &mut appearances);
+    appearances
+}
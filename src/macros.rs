@@ -0,0 +1,283 @@
+//! The [`dot!`] macro for writing graphs inline in DOT syntax.
+//!
+//! The items here are used by the expansion of the macro and are not meant to be used
+//! directly.
+
+use crate::attributes::AttributeText;
+use crate::dot::GraphBuilder;
+use crate::validation::{ValidationError, ValidationResult};
+
+/// Builds a [`Graph`] from DOT statements, validating the attributes like the builders
+/// do.
+///
+/// The graph is written as in DOT: `graph` or `digraph`, optionally preceded by
+/// `strict` and followed by an id, with a body of node, edge, subgraph, attribute
+/// and `graph`, `node` and `edge` default statements, optionally separated by
+/// semicolons. Ids and attribute values are identifiers or literals, or any Rust
+/// expression in parentheses. Ids can be anything implementing `ToString`, values
+/// anything convertible to an [`AttributeText`], such as a [`Color`] or [`Shape`].
+/// Attribute names are identifiers. As with the builders, subgraphs are rendered
+/// before the nodes and edges of their parent.
+///
+/// Evaluates to a [`ValidationResult`] with the errors of all statements.
+///
+/// ```rust
+/// use dotavious::attributes::Color;
+/// use dotavious::{dot, Dot};
+///
+/// let service = "api";
+/// let graph = dot! {
+///     digraph G {
+///         rankdir = LR;
+///         node [shape = box];
+///         (service) -> db [color = (Color::Named("red"))];
+///         subgraph cluster_storage {
+///             label = "Storage layer";
+///             db [shape = cylinder]
+///         }
+///     }
+/// }
+/// .unwrap();
+///
+/// assert_eq!(
+///     Dot { graph }.to_string(),
+///     r#"digraph G {
+///     graph [rankdir=LR];
+///     node [shape=box];
+///     subgraph cluster_storage {
+///         graph [label="Storage layer"];
+///         db [shape=cylinder];
+///     }
+///
+///     api -> db [color="red"];
+/// }
+/// "#
+/// );
+/// ```
+///
+/// [`Graph`]: crate::Graph
+/// [`AttributeText`]: crate::attributes::AttributeText
+/// [`Color`]: crate::attributes::Color
+/// [`Shape`]: crate::attributes::Shape
+/// [`ValidationResult`]: crate::validation::ValidationResult
+#[macro_export]
+macro_rules! dot {
+    (strict digraph $($rest:tt)*) => { $crate::dot!(@root true true; $($rest)*) };
+    (strict graph $($rest:tt)*) => { $crate::dot!(@root true false; $($rest)*) };
+    (digraph $($rest:tt)*) => { $crate::dot!(@root false true; $($rest)*) };
+    (graph $($rest:tt)*) => { $crate::dot!(@root false false; $($rest)*) };
+
+    (@root $strict:tt $directed:tt; { $($body:tt)* }) => {
+        $crate::dot!(@graph $strict $directed None; $($body)*)
+    };
+    (@root $strict:tt $directed:tt; $id:tt { $($body:tt)* }) => {
+        $crate::dot!(@graph $strict $directed Some($crate::dot!(@id $id)); $($body)*)
+    };
+    (@graph $strict:tt $directed:tt $id:expr; $($body:tt)*) => {{
+        let mut errors = ::std::vec::Vec::new();
+        let mut graph = $crate::macros::graph_builder($strict, $directed, $id);
+        $crate::dot!(@stmts graph, errors; $($body)*);
+        $crate::macros::finish(graph.build(), errors)
+    }};
+
+    (@stmts $b:ident, $e:ident;) => {};
+    (@stmts $b:ident, $e:ident; ; $($rest:tt)*) => {
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; graph [$($attrs:tt)*] $($rest:tt)*) => {
+        $crate::dot!(@defaults $b, Graph; $($attrs)*);
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; node [$($attrs:tt)*] $($rest:tt)*) => {
+        $crate::dot!(@defaults $b, Node; $($attrs)*);
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; edge [$($attrs:tt)*] $($rest:tt)*) => {
+        $crate::dot!(@defaults $b, Edge; $($attrs)*);
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; subgraph { $($body:tt)* } $($rest:tt)*) => {
+        $crate::dot!(@sub_graph $b, $e, $crate::SubGraphBuilder::new(); $($body)*);
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; subgraph $id:tt { $($body:tt)* } $($rest:tt)*) => {
+        $crate::dot!(
+            @sub_graph $b, $e, $crate::SubGraphBuilder::new_named($crate::dot!(@id $id));
+            $($body)*
+        );
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; { $($body:tt)* } $($rest:tt)*) => {
+        $crate::dot!(@sub_graph $b, $e, $crate::SubGraphBuilder::new(); $($body)*);
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; $key:ident = $value:tt $($rest:tt)*) => {
+        $b.add_attribute(
+            $crate::attributes::AttributeType::Graph,
+            stringify!($key),
+            $crate::dot!(@value $value),
+        );
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; $source:tt -> $($rest:tt)*) => {
+        $crate::dot!(@edges $b, $e; [$source] $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; $source:tt -- $($rest:tt)*) => {
+        $crate::dot!(@edges $b, $e; [$source] $($rest)*)
+    };
+    (
+        @stmts $b:ident, $e:ident;
+        $id:tt [$($key:ident = $value:tt),* $(,)?] $($rest:tt)*
+    ) => {
+        {
+            let mut node = $crate::NodeBuilder::new($crate::dot!(@id $id));
+            $(
+                $crate::attributes::NodeAttributes::add_attribute(
+                    &mut node,
+                    stringify!($key),
+                    $crate::dot!(@value $value),
+                );
+            )*
+            if let Some(node) = $crate::macros::collect(&mut $e, node.build()) {
+                $b.add_node(node);
+            }
+        }
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@stmts $b:ident, $e:ident; $id:tt $($rest:tt)*) => {
+        $crate::dot!(@stmts $b, $e; $id [] $($rest)*)
+    };
+
+    (@edges $b:ident, $e:ident; [$($ids:tt)*] $id:tt -> $($rest:tt)*) => {
+        $crate::dot!(@edges $b, $e; [$($ids)* $id] $($rest)*)
+    };
+    (@edges $b:ident, $e:ident; [$($ids:tt)*] $id:tt -- $($rest:tt)*) => {
+        $crate::dot!(@edges $b, $e; [$($ids)* $id] $($rest)*)
+    };
+    (
+        @edges $b:ident, $e:ident;
+        [$($ids:tt)*] $id:tt [$($key:ident = $value:tt),* $(,)?] $($rest:tt)*
+    ) => {
+        {
+            let ids = [$($crate::dot!(@id $ids),)* $crate::dot!(@id $id)];
+            let attributes: ::std::vec::Vec<(
+                $crate::attributes::AttributeName,
+                $crate::attributes::AttributeText,
+            )> = ::std::vec![$((stringify!($key).into(), $crate::dot!(@value $value))),*];
+            for pair in ids.windows(2) {
+                let mut edge = $crate::EdgeBuilder::new(pair[0].clone(), pair[1].clone());
+                for (key, value) in &attributes {
+                    edge.add_attribute(key.clone(), value.clone());
+                }
+                if let Some(edge) = $crate::macros::collect(&mut $e, edge.build()) {
+                    $b.add_edge(edge);
+                }
+            }
+        }
+        $crate::dot!(@stmts $b, $e; $($rest)*)
+    };
+    (@edges $b:ident, $e:ident; [$($ids:tt)*] $id:tt $($rest:tt)*) => {
+        $crate::dot!(@edges $b, $e; [$($ids)*] $id [] $($rest)*)
+    };
+
+    (@sub_graph $b:ident, $e:ident, $builder:expr; $($body:tt)*) => {{
+        let mut sub_graph = $builder;
+        $crate::dot!(@stmts sub_graph, $e; $($body)*);
+        if let Some(sub_graph) = $crate::macros::collect(&mut $e, sub_graph.build()) {
+            $b.add_sub_graph(sub_graph);
+        }
+    }};
+
+    (@defaults $b:ident, $kind:ident; $($key:ident = $value:tt),* $(,)?) => {
+        $(
+            $b.add_attribute(
+                $crate::attributes::AttributeType::$kind,
+                stringify!($key),
+                $crate::dot!(@value $value),
+            );
+        )*
+    };
+
+    (@id ($id:expr)) => { ::std::string::ToString::to_string(&$id) };
+    (@id $id:ident) => { ::std::string::ToString::to_string(stringify!($id)) };
+    (@id $id:literal) => { ::std::string::ToString::to_string(&$id) };
+
+    (@value ($value:expr)) => { $crate::macros::value($value) };
+    (@value $value:literal) => { $crate::macros::value($value) };
+    (@value $value:ident) => { $crate::macros::value(stringify!($value)) };
+}
+
+/// Conversion of attribute values written in [`dot!`], which unlike [`AttributeText`]
+/// accepts any numeric type so literals need no suffix.
+pub trait IntoAttributeText<'a> {
+    fn into_attribute_text(self) -> AttributeText<'a>;
+}
+
+impl<'a, T: Into<AttributeText<'a>>> IntoAttributeText<'a> for T {
+    fn into_attribute_text(self) -> AttributeText<'a> {
+        self.into()
+    }
+}
+
+macro_rules! numeric_attribute_text {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> IntoAttributeText<'a> for $t {
+                fn into_attribute_text(self) -> AttributeText<'a> {
+                    AttributeText::attr(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+numeric_attribute_text!(i32, i64, u64, usize, f64);
+
+pub fn value<'a, V: IntoAttributeText<'a>>(value: V) -> AttributeText<'a> {
+    value.into_attribute_text()
+}
+
+pub fn graph_builder<'a>(
+    strict: bool,
+    directed: bool,
+    id: Option<String>,
+) -> GraphBuilder<'a> {
+    let mut builder = match (directed, id) {
+        (true, Some(id)) => GraphBuilder::new_named_directed(id),
+        (true, None) => GraphBuilder::new_directed(),
+        (false, Some(id)) => GraphBuilder::new_named_undirected(id),
+        (false, None) => GraphBuilder::new_undirected(),
+    };
+    if strict {
+        builder.strict();
+    }
+    builder
+}
+
+/// Keeps the value of a statement, or its errors to report once the graph is built.
+pub fn collect<T>(
+    errors: &mut Vec<ValidationError>,
+    result: ValidationResult<T>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(mut statement_errors) => {
+            errors.append(&mut statement_errors);
+            None
+        }
+    }
+}
+
+pub fn finish<T>(
+    result: ValidationResult<T>,
+    mut errors: Vec<ValidationError>,
+) -> ValidationResult<T> {
+    match result {
+        Ok(graph) if errors.is_empty() => Ok(graph),
+        Ok(_) => Err(errors),
+        Err(mut graph_errors) => {
+            errors.append(&mut graph_errors);
+            Err(errors)
+        }
+    }
+}
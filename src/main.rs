@@ -0,0 +1,320 @@
+//! The `dotavious` command-line tool, for formatting, checking and inspecting DOT files.
+
+use dotavious::lint::Linter;
+use dotavious::parse::{parse_document, Document};
+use dotavious::validation::{
+    validate_attributes, validate_layers, validate_layout_engine, validate_structure,
+    validate_styles, UndeclaredNodePolicy,
+};
+use dotavious::{Dot, Graph};
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::process;
+use std::{env, fs};
+
+const USAGE: &str = "Usage: dotavious <command> [options] [FILE...]
+
+Commands:
+    fmt [--check] [FILE...]         Format files in place, or report files which are
+                                    not formatted with --check
+    check [FILE...]                 Validate and lint files, failing on errors
+    convert [--from FORMAT] [--to FORMAT] [-o OUTPUT] [FILE]
+                                    Convert a file between formats
+    stats [FILE...]                 Print node, edge and cluster counts and the degree
+                                    distribution
+
+Files are read from standard input when none or - is given.
+Supported formats: dot";
+
+const FORMATS: &[&str] = &["dot"];
+
+/// Exit code for invalid or unformatted input.
+const FAILURE: i32 = 1;
+
+/// Exit code for invalid arguments or unreadable files.
+const USAGE_ERROR: i32 = 2;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let code = match args.split_first() {
+        Some((command, args)) => match command.as_str() {
+            "fmt" => fmt(args),
+            "check" => check(args),
+            "convert" => convert(args),
+            "stats" => stats(args),
+            "-h" | "--help" | "help" => {
+                println!("{}", USAGE);
+                Ok(0)
+            }
+            _ => Err(format!("Unknown command {}", command)),
+        },
+        None => Err("Missing command".to_string()),
+    };
+    match code {
+        Ok(code) => process::exit(code),
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            process::exit(USAGE_ERROR);
+        }
+    }
+}
+
+/// Splits arguments into the flags given, taking a value for those in `with_values`,
+/// and the files, which default to standard input.
+fn parse_args(
+    args: &[String],
+    flags: &[&str],
+    with_values: &[&str],
+) -> Result<(BTreeMap<String, String>, Vec<String>), String> {
+    let mut options = BTreeMap::new();
+    let mut files = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if with_values.contains(&arg.as_str()) {
+            match args.next() {
+                Some(value) => options.insert(arg.clone(), value.clone()),
+                None => return Err(format!("Missing value for {}", arg)),
+            };
+        } else if flags.contains(&arg.as_str()) {
+            options.insert(arg.clone(), String::new());
+        } else if arg.starts_with('-') && arg != "-" {
+            return Err(format!("Unknown option {}", arg));
+        } else {
+            files.push(arg.clone());
+        }
+    }
+    if files.is_empty() {
+        files.push("-".to_string());
+    }
+    Ok((options, files))
+}
+
+fn read(file: &str) -> Result<String, String> {
+    let mut source = String::new();
+    let result = if file == "-" {
+        io::stdin().read_to_string(&mut source).map(|_| ())
+    } else {
+        fs::read_to_string(file).map(|contents| source = contents)
+    };
+    result.map_err(|e| format!("Cannot read {}: {}", file, e))?;
+    Ok(source)
+}
+
+fn write(file: &str, contents: &str) -> Result<(), String> {
+    if file == "-" {
+        print!("{}", contents);
+        Ok(())
+    } else {
+        fs::write(file, contents).map_err(|e| format!("Cannot write {}: {}", file, e))
+    }
+}
+
+fn display_name(file: &str) -> &str {
+    if file == "-" {
+        "<stdin>"
+    } else {
+        file
+    }
+}
+
+/// Parses the file, reporting syntax errors at their location in the file.
+fn parse_file(file: &str) -> Result<Option<(String, Document)>, String> {
+    let source = read(file)?;
+    match parse_document(&source) {
+        Ok(document) => Ok(Some((source, document))),
+        Err(error) => {
+            eprintln!("{}:{}", display_name(file), error);
+            Ok(None)
+        }
+    }
+}
+
+fn fmt(args: &[String]) -> Result<i32, String> {
+    let (options, files) = parse_args(args, &["--check"], &[])?;
+    let check = options.contains_key("--check");
+    let mut code = 0;
+    for file in &files {
+        let (source, document) = match parse_file(file)? {
+            Some(parsed) => parsed,
+            None => {
+                code = FAILURE;
+                continue;
+            }
+        };
+        let formatted = Dot {
+            graph: document.graph.clone(),
+        }
+        .to_string();
+        if let Some(reason) = formatting_change(&document, &formatted) {
+            eprintln!("{}: cannot be formatted, {}", display_name(file), reason);
+            code = FAILURE;
+            continue;
+        }
+        if check {
+            if formatted != source {
+                println!("{}: not formatted", display_name(file));
+                code = FAILURE;
+            }
+        } else if file == "-" || formatted != source {
+            write(file, &formatted)?;
+        }
+    }
+    Ok(code)
+}
+
+/// Why formatting would change the meaning of the document, if it would.
+fn formatting_change(document: &Document, formatted: &str) -> Option<String> {
+    if document.dropped_comments > 0 {
+        return Some(format!(
+            "formatting would drop {} comment(s)",
+            document.dropped_comments
+        ));
+    }
+    rendering_change(document, formatted)
+}
+
+/// Why the rendered graph does not give its elements the same attributes as the
+/// document, if it does not.
+fn rendering_change(document: &Document, rendered: &str) -> Option<String> {
+    match parse_document(rendered) {
+        Ok(reparsed) if document.same_attributes(&reparsed) => None,
+        Ok(_) => Some(
+            "formatting would reorder statements and change the attributes of nodes, \
+             edges or subgraphs"
+                .to_string(),
+        ),
+        Err(error) => Some(format!("the formatted graph does not parse: {}", error)),
+    }
+}
+
+fn check(args: &[String]) -> Result<i32, String> {
+    let (_, files) = parse_args(args, &[], &[])?;
+    let linter = Linter::default();
+    let mut code = 0;
+    for file in &files {
+        let graph = match parse_file(file)? {
+            Some((_, document)) => document.graph,
+            None => {
+                code = FAILURE;
+                continue;
+            }
+        };
+        let mut errors = validate_attributes(&graph);
+        errors.extend(validate_structure(&graph, UndeclaredNodePolicy::Implicit));
+        errors.extend(validate_layers(&graph));
+        errors.extend(validate_styles(&graph));
+        errors.extend(validate_layout_engine(&graph));
+        for error in &errors {
+            println!("{}: {}", display_name(file), error);
+        }
+        let diagnostics = linter.lint(&graph);
+        for diagnostic in &diagnostics {
+            println!("{}: {}", display_name(file), diagnostic);
+        }
+        if errors.iter().any(|e| e.is_error())
+            || diagnostics.iter().any(|d| d.is_denied())
+        {
+            code = FAILURE;
+        }
+    }
+    Ok(code)
+}
+
+fn convert(args: &[String]) -> Result<i32, String> {
+    let (options, files) = parse_args(args, &[], &["--from", "--to", "-o"])?;
+    for format in ["--from", "--to"].iter().filter_map(|o| options.get(*o)) {
+        if !FORMATS.contains(&format.as_str()) {
+            return Err(format!(
+                "Unsupported format {}, supported formats: {}",
+                format,
+                FORMATS.join(", ")
+            ));
+        }
+    }
+    let file = match files.as_slice() {
+        [file] => file,
+        _ => return Err("convert takes a single file".to_string()),
+    };
+    let document = match parse_file(file)? {
+        Some((_, document)) => document,
+        None => return Ok(FAILURE),
+    };
+    let converted = Dot {
+        graph: document.graph.clone(),
+    }
+    .to_string();
+    if let Some(reason) = rendering_change(&document, &converted) {
+        eprintln!("{}: cannot be converted, {}", display_name(file), reason);
+        return Ok(FAILURE);
+    }
+    let output = options.get("-o").map_or("-", String::as_str);
+    write(output, &converted)?;
+    Ok(0)
+}
+
+fn stats(args: &[String]) -> Result<i32, String> {
+    let (_, files) = parse_args(args, &[], &[])?;
+    let mut code = 0;
+    for file in &files {
+        let graph = match parse_file(file)? {
+            Some((_, document)) => document.graph,
+            None => {
+                code = FAILURE;
+                continue;
+            }
+        };
+        if files.len() > 1 {
+            println!("{}:", display_name(file));
+        }
+        print!("{}", graph_stats(&graph));
+    }
+    Ok(code)
+}
+
+fn graph_stats(graph: &Graph) -> String {
+    let index = graph.index();
+    let sub_graphs = graph.all_sub_graphs();
+    let clusters = sub_graphs
+        .iter()
+        .filter(|(_, s)| s.id.as_deref().is_some_and(|id| id.starts_with("cluster")))
+        .count();
+    let mut degrees: BTreeMap<usize, usize> = BTreeMap::new();
+    for node in index.nodes() {
+        *degrees
+            .entry(index.incident_edges(node.id).len())
+            .or_default() += 1;
+    }
+
+    let mut stats = format!(
+        "nodes: {}\nedges: {}\nsubgraphs: {}\nclusters: {}\n",
+        index.nodes().count(),
+        index.edges().len(),
+        sub_graphs.len(),
+        clusters
+    );
+    stats.push_str("degree distribution:\n");
+    for (degree, count) in degrees {
+        stats.push_str(&format!("    {}: {}\n", degree, count));
+    }
+    stats
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph_stats;
+    use dotavious::parse::parse;
+
+    #[test]
+    fn stats() {
+        let graph = parse(
+            "digraph { subgraph cluster_a { a -> b } subgraph s { c } a -> c; b -> c; d }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            "nodes: 4\nedges: 3\nsubgraphs: 2\nclusters: 1\ndegree distribution:\n    \
+             0: 1\n    2: 3\n",
+            graph_stats(&graph)
+        );
+    }
+}
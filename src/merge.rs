@@ -0,0 +1,253 @@
+//! Combining partial graphs of the same system into one.
+
+use crate::attributes::{AttributeName, AttributeText};
+use crate::dot::{Graph, SubGraph};
+use crate::mutation::{is_parallel, statement_attributes, MutationError};
+use indexmap::IndexMap;
+use std::collections::HashSet;
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+type Text<'a> = AttributeText<'a>;
+
+/// Resolves an attribute from its name and its left and right values.
+pub type ResolveFn<'a> = dyn Fn(&AttributeName, &Text<'a>, &Text<'a>) -> Text<'a> + 'a;
+
+/// How an attribute set to different values in both merged graphs is resolved.
+pub enum MergePolicy<'a> {
+    /// Keep the value of the graph being merged into.
+    PreferLeft,
+
+    /// Keep the value of the graph being merged.
+    PreferRight,
+
+    /// Fail without modifying the graph.
+    Error,
+
+    /// Compute the value from the attribute and the left and right values.
+    Custom(Box<ResolveFn<'a>>),
+}
+
+impl<'a> MergePolicy<'a> {
+    pub fn custom<F>(resolve: F) -> Self
+    where
+        F: Fn(&AttributeName, &Text<'a>, &Text<'a>) -> Text<'a> + 'a,
+    {
+        MergePolicy::Custom(Box::new(resolve))
+    }
+
+    fn resolve(
+        &self,
+        name: &AttributeName,
+        left: &AttributeText<'a>,
+        right: &AttributeText<'a>,
+    ) -> Result<AttributeText<'a>, MutationError> {
+        if left == right {
+            return Ok(left.clone());
+        }
+        match self {
+            MergePolicy::PreferLeft => Ok(left.clone()),
+            MergePolicy::PreferRight => Ok(right.clone()),
+            MergePolicy::Error => Err(MutationError::AttributeConflict {
+                attribute: name.to_string(),
+                first: left.as_str().to_string(),
+                second: right.as_str().to_string(),
+            }),
+            MergePolicy::Custom(resolve) => Ok(resolve(name, left, right)),
+        }
+    }
+
+    fn merge_attributes(
+        &self,
+        left: &mut Attributes<'a>,
+        right: &Attributes<'a>,
+    ) -> Result<(), MutationError> {
+        for (name, value) in right {
+            let merged = match left.get(name) {
+                Some(existing) => self.resolve(name, existing, value)?,
+                None => value.clone(),
+            };
+            left.insert(name.clone(), merged);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Graph<'a> {
+    /// Adds the nodes, edges and subgraphs of `other` to the graph.
+    ///
+    /// Nodes are united by id, with the attributes of all their statements combined on
+    /// their first statement. Subgraphs with the same id and the same parent are merged
+    /// recursively, other subgraphs are added. Edges between the same endpoints and
+    /// ports are united one to one, or all into the first if either graph is strict,
+    /// in which case so is the result. Attributes of the graphs, their defaults and
+    /// united elements set to different values are resolved with `policy`.
+    ///
+    /// Fails without modifying the graph on attribute conflicts under
+    /// [`MergePolicy::Error`] or if only one of the graphs is directed.
+    pub fn merge(
+        &mut self,
+        other: Graph<'a>,
+        policy: MergePolicy<'a>,
+    ) -> Result<(), MutationError> {
+        if self.is_directed != other.is_directed {
+            return Err(MutationError::MixedDirectedness);
+        }
+        let strict = self.strict || other.strict;
+        let (left, right) = (self.index(), other.index());
+
+        let mut nodes: IndexMap<String, Attributes<'a>> = IndexMap::new();
+        for node in right.nodes() {
+            if let Some(existing) = left.node(node.id) {
+                let mut attributes = statement_attributes(&existing.statements);
+                policy.merge_attributes(
+                    &mut attributes,
+                    &statement_attributes(&node.statements),
+                )?;
+                nodes.insert(node.id.to_string(), attributes);
+            }
+        }
+
+        let left_edges = left.edges();
+        let mut edges: Vec<Option<Attributes<'a>>> = vec![None; left_edges.len()];
+        let mut added_edges = Vec::new();
+        for edge in right.edges() {
+            let united = (0..left_edges.len()).find(|index| {
+                (strict || edges[*index].is_none())
+                    && is_parallel(left_edges[*index].edge, edge.edge, self.is_directed)
+            });
+            if let Some(index) = united {
+                let attributes = edges[index]
+                    .get_or_insert_with(|| left_edges[index].edge.attributes.clone());
+                policy.merge_attributes(attributes, &edge.edge.attributes)?;
+            }
+            added_edges.push(united.is_none());
+        }
+
+        let mut merged = self.clone();
+        let mut position = 0;
+        merged.for_each_scope_mut(&mut |scope| {
+            for edge in scope.edges.iter_mut() {
+                if let Some(attributes) = edges[position].take() {
+                    edge.attributes = attributes;
+                }
+                position += 1;
+            }
+        });
+
+        let mut root = SubGraph::new(
+            None,
+            merged.graph_attributes,
+            merged.node_attributes,
+            merged.edge_attributes,
+            merged.sub_graphs,
+            merged.nodes,
+            merged.edges,
+        );
+        let other_root = SubGraph::new(
+            None,
+            other.graph_attributes,
+            other.node_attributes,
+            other.edge_attributes,
+            other.sub_graphs,
+            other.nodes,
+            other.edges,
+        );
+        merge_sub_graph(&mut root, other_root, &policy, &mut added_edges.into_iter())?;
+        merged.graph_attributes = root.graph_attributes;
+        merged.node_attributes = root.node_attributes;
+        merged.edge_attributes = root.edge_attributes;
+        merged.sub_graphs = root.sub_graphs;
+        merged.nodes = root.nodes;
+        merged.edges = root.edges;
+
+        // Statements in the root graph add nothing to nodes declared in a subgraph once
+        // their attributes are combined on the first statement.
+        let in_sub_graphs: HashSet<String> = merged
+            .all_sub_graphs()
+            .iter()
+            .flat_map(|(_, sub_graph)| &sub_graph.nodes)
+            .map(|node| node.id.as_str().to_string())
+            .collect();
+        merged.nodes.retain(|node| {
+            let id = node.id.as_str();
+            !(nodes.contains_key(id) && in_sub_graphs.contains(id))
+        });
+
+        let mut declared: Vec<&str> = Vec::new();
+        merged.for_each_scope_mut(&mut |scope| {
+            for node in scope.nodes.iter_mut() {
+                if let Some((id, attributes)) = nodes.get_key_value(node.id.as_str()) {
+                    node.attributes = if declared.contains(&id.as_str()) {
+                        IndexMap::new()
+                    } else {
+                        declared.push(id);
+                        attributes.clone()
+                    };
+                }
+            }
+        });
+
+        merged.strict = strict;
+        if merged.id.is_none() {
+            merged.id = other.id;
+        }
+        if merged.comment.is_none() {
+            merged.comment = other.comment;
+        }
+        *self = merged;
+        Ok(())
+    }
+}
+
+/// Merges the statements of `from` into `into`, adding the edges of `from` in the order
+/// they are rendered for which `added_edges` yields true.
+fn merge_sub_graph<'a>(
+    into: &mut SubGraph<'a>,
+    from: SubGraph<'a>,
+    policy: &MergePolicy<'a>,
+    added_edges: &mut dyn Iterator<Item = bool>,
+) -> Result<(), MutationError> {
+    policy.merge_attributes(&mut into.graph_attributes, &from.graph_attributes)?;
+    policy.merge_attributes(&mut into.node_attributes, &from.node_attributes)?;
+    policy.merge_attributes(&mut into.edge_attributes, &from.edge_attributes)?;
+
+    for sub_graph in from.sub_graphs {
+        let existing = sub_graph.id.as_ref().and_then(|id| {
+            into.sub_graphs
+                .iter()
+                .position(|s| s.id.as_ref() == Some(id))
+        });
+        match existing {
+            Some(index) => merge_sub_graph(
+                &mut into.sub_graphs[index],
+                sub_graph,
+                policy,
+                added_edges,
+            )?,
+            None => {
+                let mut added = SubGraph::new(
+                    sub_graph.id.clone(),
+                    IndexMap::new(),
+                    IndexMap::new(),
+                    IndexMap::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                );
+                merge_sub_graph(&mut added, sub_graph, policy, added_edges)?;
+                into.sub_graphs.push(added);
+            }
+        }
+    }
+    for node in from.nodes {
+        if !into.nodes.iter().any(|n| n.id.as_str() == node.id.as_str()) {
+            into.nodes.push(node);
+        }
+    }
+    for edge in from.edges {
+        if added_edges.next() == Some(true) {
+            into.edges.push(edge);
+        }
+    }
+    Ok(())
+}
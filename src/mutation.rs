@@ -0,0 +1,531 @@
+//! Operations modifying a graph while keeping its nodes, edges and the attributes
+//! referencing them consistent.
+
+use crate::attributes::{AttributeName, AttributeText};
+use crate::dot::{Edge, Graph, Node, SubGraph};
+use indexmap::IndexMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+
+/// How conflicting values of the same attribute are resolved when combining attributes.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ConflictPolicy {
+    /// Keep the value of the element being merged into.
+    KeepFirst,
+
+    /// Keep the value of the element being merged.
+    KeepSecond,
+
+    /// Fail without modifying anything.
+    Error,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MutationError {
+    /// No node statement or edge refers to the node.
+    NodeNotFound(String),
+
+    /// A node with the id already exists.
+    NodeExists(String),
+
+    /// No subgraph exists at the path.
+    SubGraphNotFound(Vec<usize>),
+
+    /// Another subgraph already has the id.
+    SubGraphExists(String),
+
+    /// Both elements set the attribute to different values.
+    AttributeConflict {
+        attribute: String,
+        first: String,
+        second: String,
+    },
+
+    /// Only one of the graphs is directed.
+    MixedDirectedness,
+}
+
+impl Display for MutationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MutationError::NodeNotFound(id) => write!(f, "Node {} does not exist", id),
+            MutationError::NodeExists(id) => write!(f, "Node {} already exists", id),
+            MutationError::SubGraphNotFound(path) => {
+                write!(f, "No subgraph exists at {:?}", path)
+            }
+            MutationError::SubGraphExists(id) => {
+                write!(f, "Subgraph {} already exists", id)
+            }
+            MutationError::AttributeConflict {
+                attribute,
+                first,
+                second,
+            } => write!(
+                f,
+                "Conflicting values \"{}\" and \"{}\" for {}",
+                first, second, attribute
+            ),
+            MutationError::MixedDirectedness => {
+                write!(f, "Cannot combine a directed and an undirected graph")
+            }
+        }
+    }
+}
+
+impl Error for MutationError {}
+
+/// How the values of an attribute set on several merged edges are combined.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AttributeMerge {
+    /// Keep the value of the first edge.
+    KeepFirst,
+
+    /// Keep the value of the last edge.
+    KeepLast,
+
+    /// Add up numeric values, such as weights. Values which are not numbers are ignored.
+    Sum,
+
+    /// Keep the largest numeric value.
+    Max,
+
+    /// Keep the smallest numeric value.
+    Min,
+
+    /// Join the distinct values with the separator, such as `\n` for labels.
+    Concat(String),
+}
+
+impl AttributeMerge {
+    fn merge<'a>(&self, values: &[&AttributeText<'a>]) -> AttributeText<'a> {
+        let first = values[0];
+        let numbers = || values.iter().filter_map(|v| v.as_str().parse::<f64>().ok());
+        let number = match self {
+            AttributeMerge::KeepFirst => None,
+            AttributeMerge::KeepLast => return values[values.len() - 1].clone(),
+            AttributeMerge::Sum => numbers().reduce(|a, b| a + b),
+            AttributeMerge::Max => numbers().reduce(f64::max),
+            AttributeMerge::Min => numbers().reduce(f64::min),
+            AttributeMerge::Concat(separator) => {
+                let mut texts: Vec<&str> = Vec::new();
+                for value in values {
+                    if !texts.contains(&value.as_str()) {
+                        texts.push(value.as_str());
+                    }
+                }
+                return with_text(first, texts.join(separator));
+            }
+        };
+        match number {
+            Some(number) => AttributeText::from(number.to_string()),
+            None => first.clone(),
+        }
+    }
+}
+
+/// How the attributes of parallel edges are combined when collapsing them.
+/// Attributes are kept from the first edge unless configured otherwise.
+///
+/// ```rust
+/// use dotavious::attributes::AttributeName;
+/// use dotavious::mutation::{AttributeMerge, EdgeMergeStrategy};
+///
+/// let mut strategy = EdgeMergeStrategy::new();
+/// strategy
+///     .attribute(AttributeName::Weight, AttributeMerge::Sum)
+///     .attribute(AttributeName::Label, AttributeMerge::Concat("\\n".to_string()));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EdgeMergeStrategy {
+    pub default: AttributeMerge,
+    pub attributes: IndexMap<AttributeName, AttributeMerge>,
+}
+
+impl Default for EdgeMergeStrategy {
+    fn default() -> Self {
+        Self {
+            default: AttributeMerge::KeepFirst,
+            attributes: IndexMap::new(),
+        }
+    }
+}
+
+impl EdgeMergeStrategy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How attributes without their own merge are combined.
+    pub fn default_merge(&mut self, merge: AttributeMerge) -> &mut Self {
+        self.default = merge;
+        self
+    }
+
+    pub fn attribute<N: Into<AttributeName>>(
+        &mut self,
+        name: N,
+        merge: AttributeMerge,
+    ) -> &mut Self {
+        self.attributes.insert(name.into(), merge);
+        self
+    }
+
+    fn merge<'a>(&self, edges: &[Attributes<'a>]) -> Attributes<'a> {
+        let mut merged = IndexMap::new();
+        for key in edges.iter().flat_map(|attributes| attributes.keys()) {
+            if merged.contains_key(key) {
+                continue;
+            }
+            let values: Vec<&AttributeText<'a>> = edges
+                .iter()
+                .filter_map(|attributes| attributes.get(key))
+                .collect();
+            let merge = self.attributes.get(key).unwrap_or(&self.default);
+            merged.insert(key.clone(), merge.merge(&values));
+        }
+        merged
+    }
+}
+
+impl<'a> Graph<'a> {
+    /// The subgraph at `path`, or `None` if the path does not exist or is empty.
+    ///
+    /// See [`Graph::sub_graph`] for how paths address subgraphs.
+    pub fn sub_graph_mut(&mut self, path: &[usize]) -> Option<&mut SubGraph<'a>> {
+        let (first, rest) = path.split_first()?;
+        let mut sub_graph = self.sub_graphs.get_mut(*first)?;
+        for index in rest {
+            sub_graph = sub_graph.sub_graphs.get_mut(*index)?;
+        }
+        Some(sub_graph)
+    }
+
+    /// Removes the node's statements from the graph and every subgraph along with all
+    /// edges from or to it.
+    /// Returns whether the node existed.
+    pub fn remove_node(&mut self, id: &str) -> bool {
+        let mut removed = false;
+        self.for_each_scope_mut(&mut |scope| {
+            let (nodes, edges) = (scope.nodes.len(), scope.edges.len());
+            scope.nodes.retain(|node| node.id.as_str() != id);
+            scope
+                .edges
+                .retain(|edge| edge.source != id && edge.target != id);
+            removed |= nodes != scope.nodes.len() || edges != scope.edges.len();
+        });
+        removed
+    }
+
+    /// Renames the node in its statements and edges, along with the graph's `root`
+    /// attribute and `viewport` focus when they refer to it.
+    ///
+    /// `lhead` and `ltail` refer to clusters rather than nodes,
+    /// see [`Graph::rename_sub_graph`].
+    pub fn rename_node(&mut self, id: &str, new_id: &str) -> Result<(), MutationError> {
+        if id == new_id {
+            return Ok(());
+        }
+        let index = self.index();
+        if index.node(id).is_none() {
+            return Err(MutationError::NodeNotFound(id.to_string()));
+        }
+        if index.node(new_id).is_some() {
+            return Err(MutationError::NodeExists(new_id.to_string()));
+        }
+        self.replace_node_id(id, new_id);
+        Ok(())
+    }
+
+    /// Merges node `b` into node `a`.
+    ///
+    /// Edges from or to `b` are moved to `a`, which may turn edges between the two
+    /// into loops. Statements of `b` become statements of `a`, so `a` joins the subgraphs
+    /// `b` was declared in. The attributes of both are combined on the first statement
+    /// of `a`, resolving attributes both set to different values with `policy`.
+    pub fn merge_nodes(
+        &mut self,
+        a: &str,
+        b: &str,
+        policy: ConflictPolicy,
+    ) -> Result<(), MutationError> {
+        if a == b {
+            return Ok(());
+        }
+        let index = self.index();
+        let (node_a, node_b) = match (index.node(a), index.node(b)) {
+            (Some(node_a), Some(node_b)) => (node_a, node_b),
+            (None, _) => return Err(MutationError::NodeNotFound(a.to_string())),
+            (_, None) => return Err(MutationError::NodeNotFound(b.to_string())),
+        };
+        let mut attributes = statement_attributes(&node_a.statements);
+        for (key, value) in statement_attributes(&node_b.statements) {
+            match attributes.get(&key) {
+                Some(existing) if *existing == value => {}
+                Some(existing) => match policy {
+                    ConflictPolicy::KeepFirst => {}
+                    ConflictPolicy::KeepSecond => {
+                        attributes.insert(key, value);
+                    }
+                    ConflictPolicy::Error => {
+                        return Err(MutationError::AttributeConflict {
+                            attribute: key.to_string(),
+                            first: existing.as_str().to_string(),
+                            second: value.as_str().to_string(),
+                        });
+                    }
+                },
+                None => {
+                    attributes.insert(key, value);
+                }
+            }
+        }
+
+        self.replace_node_id(b, a);
+        let mut first = true;
+        self.for_each_scope_mut(&mut |scope| {
+            for node in scope.nodes.iter_mut().filter(|node| node.id.as_str() == a) {
+                node.attributes = if first {
+                    attributes.clone()
+                } else {
+                    IndexMap::new()
+                };
+                first = false;
+            }
+        });
+        Ok(())
+    }
+
+    /// Moves the node into the subgraph at `path`, or the root graph for an empty path.
+    ///
+    /// The node's statements are replaced by a single statement in the subgraph with
+    /// the attributes of all of them. Edges are left in place, so the node still appears
+    /// in the subgraphs of edges referencing it.
+    pub fn move_node_to_sub_graph(
+        &mut self,
+        id: &str,
+        path: &[usize],
+    ) -> Result<(), MutationError> {
+        if !path.is_empty() && self.sub_graph(path).is_none() {
+            return Err(MutationError::SubGraphNotFound(path.to_vec()));
+        }
+        let index = self.index();
+        let node = match index.node(id) {
+            Some(node) => node,
+            None => return Err(MutationError::NodeNotFound(id.to_string())),
+        };
+        let mut moved = match node.statements.first() {
+            Some(statement) => Node::new(statement.id.clone()),
+            None => Node::new(id.to_string()),
+        };
+        moved.attributes = statement_attributes(&node.statements);
+
+        self.for_each_scope_mut(&mut |scope| {
+            scope.nodes.retain(|node| node.id.as_str() != id);
+        });
+        match self.sub_graph_mut(path) {
+            Some(sub_graph) => sub_graph.nodes.push(moved),
+            None => self.nodes.push(moved),
+        }
+        Ok(())
+    }
+
+    /// Removes every edge of the graph and its subgraphs matching the predicate.
+    /// Returns the number of edges removed.
+    pub fn remove_edge_where<F>(&mut self, mut predicate: F) -> usize
+    where
+        F: FnMut(&Edge<'a>) -> bool,
+    {
+        let mut removed = 0;
+        self.for_each_scope_mut(&mut |scope| {
+            let edges = scope.edges.len();
+            scope.edges.retain(|edge| !predicate(edge));
+            removed += edges - scope.edges.len();
+        });
+        removed
+    }
+
+    #[doc(hidden)]
+    pub fn remove_edges_where<F>(&mut self, predicate: F) -> usize
+    where
+        F: FnMut(&Edge<'a>) -> bool,
+    {
+        self.remove_edge_where(predicate)
+    }
+
+    /// Replaces edges between the same nodes and ports within the same graph or subgraph
+    /// by the first of them, combining their attributes with `strategy`.
+    /// Edges of undirected graphs are parallel regardless of their direction.
+    ///
+    /// Unlike `concentrate=true`, which only merges edges when drawing, this simplifies
+    /// the graph itself. Returns the number of edges removed.
+    pub fn collapse_parallel_edges(&mut self, strategy: &EdgeMergeStrategy) -> usize {
+        let is_directed = self.is_directed;
+        let mut removed = 0;
+        self.for_each_scope_mut(&mut |scope| {
+            let mut kept: Vec<Edge<'a>> = Vec::new();
+            let mut attributes: Vec<Vec<Attributes<'a>>> = Vec::new();
+            for edge in scope.edges.drain(..) {
+                match kept.iter().position(|k| is_parallel(k, &edge, is_directed)) {
+                    Some(index) => {
+                        attributes[index].push(edge.attributes);
+                        removed += 1;
+                    }
+                    None => {
+                        attributes.push(vec![edge.attributes.clone()]);
+                        kept.push(edge);
+                    }
+                }
+            }
+            for (edge, attributes) in kept.iter_mut().zip(attributes) {
+                if attributes.len() > 1 {
+                    edge.attributes = strategy.merge(&attributes);
+                }
+            }
+            *scope.edges = kept;
+        });
+        removed
+    }
+
+    /// Renames the subgraph at `path` along with the `lhead` and `ltail` attributes
+    /// referring to it.
+    pub fn rename_sub_graph<S: Into<String>>(
+        &mut self,
+        path: &[usize],
+        new_id: S,
+    ) -> Result<(), MutationError> {
+        let new_id = new_id.into();
+        if self
+            .all_sub_graphs()
+            .iter()
+            .any(|(p, sub_graph)| p != path && sub_graph.id.as_ref() == Some(&new_id))
+        {
+            return Err(MutationError::SubGraphExists(new_id));
+        }
+        let sub_graph = match self.sub_graph_mut(path) {
+            Some(sub_graph) => sub_graph,
+            None => return Err(MutationError::SubGraphNotFound(path.to_vec())),
+        };
+        let id = match sub_graph.id.replace(new_id.clone()) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        let rename = |attributes: &mut Attributes<'a>| {
+            for key in &[AttributeName::LHead, AttributeName::LTail] {
+                if let Some(value) = attributes.get_mut(key) {
+                    if value.as_str() == id {
+                        *value = AttributeText::from(new_id.clone());
+                    }
+                }
+            }
+        };
+        self.for_each_scope_mut(&mut |scope| {
+            rename(scope.edge_attributes);
+            for edge in scope.edges.iter_mut() {
+                rename(&mut edge.attributes);
+            }
+        });
+        Ok(())
+    }
+
+    /// Replaces the id in node statements, edges and graph attributes referring to it.
+    fn replace_node_id(&mut self, id: &str, new_id: &str) {
+        self.for_each_scope_mut(&mut |scope| {
+            for node in scope.nodes.iter_mut().filter(|node| node.id.as_str() == id) {
+                node.id = AttributeText::from(new_id.to_string());
+            }
+            for edge in scope.edges.iter_mut() {
+                if edge.source == id {
+                    edge.source = new_id.to_string();
+                }
+                if edge.target == id {
+                    edge.target = new_id.to_string();
+                }
+            }
+        });
+
+        if let Some(root) = self.graph_attributes.get_mut(&AttributeName::Root) {
+            if root.as_str() == id {
+                *root = AttributeText::from(new_id.to_string());
+            }
+        }
+        if let Some(viewport) = self.graph_attributes.get_mut(&AttributeName::ViewPort) {
+            let parts: Vec<&str> = viewport.as_str().splitn(4, ',').collect();
+            if let [width, height, zoom, focus] = parts[..] {
+                if focus.trim().trim_matches('\'') == id {
+                    *viewport = AttributeText::quoted(format!(
+                        "{},{},{},'{}'",
+                        width, height, zoom, new_id
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Calls `f` with the statements of every subgraph, then with those of the graph,
+    /// in the order they are rendered.
+    pub(crate) fn for_each_scope_mut(&mut self, f: &mut dyn FnMut(ScopeMut<'_, 'a>)) {
+        for_each_sub_graph_mut(&mut self.sub_graphs, f);
+        f(ScopeMut {
+            edge_attributes: &mut self.edge_attributes,
+            nodes: &mut self.nodes,
+            edges: &mut self.edges,
+        });
+    }
+}
+
+/// Statements of the root graph or a subgraph.
+pub(crate) struct ScopeMut<'s, 'a> {
+    pub(crate) edge_attributes: &'s mut Attributes<'a>,
+    pub(crate) nodes: &'s mut Vec<Node<'a>>,
+    pub(crate) edges: &'s mut Vec<Edge<'a>>,
+}
+
+fn for_each_sub_graph_mut<'a>(
+    sub_graphs: &mut [SubGraph<'a>],
+    f: &mut dyn FnMut(ScopeMut<'_, 'a>),
+) {
+    for sub_graph in sub_graphs {
+        for_each_sub_graph_mut(&mut sub_graph.sub_graphs, f);
+        f(ScopeMut {
+            edge_attributes: &mut sub_graph.edge_attributes,
+            nodes: &mut sub_graph.nodes,
+            edges: &mut sub_graph.edges,
+        });
+    }
+}
+
+pub(crate) fn is_parallel(a: &Edge, b: &Edge, is_directed: bool) -> bool {
+    let source = (&a.source, &a.source_port_position);
+    let target = (&a.target, &a.target_port_position);
+    let other_source = (&b.source, &b.source_port_position);
+    let other_target = (&b.target, &b.target_port_position);
+    (source == other_source && target == other_target)
+        || (!is_directed && source == other_target && target == other_source)
+}
+
+/// The text in the same kind of attribute text as `kind`.
+fn with_text<'a>(kind: &AttributeText<'a>, text: String) -> AttributeText<'a> {
+    match kind {
+        AttributeText::AttrStr(_) => AttributeText::from(text),
+        AttributeText::EscStr(_) => AttributeText::escaped(text),
+        AttributeText::HtmlStr(_) => AttributeText::html(text),
+        AttributeText::QuotedStr(_) => AttributeText::quoted(text),
+    }
+}
+
+/// Attributes of all statements declaring a node, later statements taking precedence.
+pub(crate) fn statement_attributes<'a>(statements: &[&Node<'a>]) -> Attributes<'a> {
+    let mut attributes = IndexMap::new();
+    for statement in statements {
+        attributes.extend(
+            statement
+                .attributes
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+    }
+    attributes
+}
@@ -0,0 +1,790 @@
+//! Reading graphs from DOT source.
+//!
+//! Statements are gathered the way the builders keep them: graph attributes and
+//! defaults at the top of their graph or subgraph, then subgraphs, nodes and edges in
+//! the order they appear. Edge statements with several edges, such as `a -> b -> c`
+//! or edges to a subgraph, are split into an edge per pair of nodes.
+//! Comments are dropped, except for a single leading `//` line which becomes the
+//! graph's comment.
+//!
+//! As defaults only apply to nodes, edges and subgraphs created after them, gathering
+//! statements can change the attributes Graphviz gives them, such as for `a` in
+//! `a; node [shape=box]; b`. [`parse_document`] keeps the attributes each element gets
+//! in the order of the source, so [`Document::same_attributes`] can tell whether
+//! rendering a graph changes them.
+//!
+//! ```rust
+//! use dotavious::parse::parse;
+//! use dotavious::Dot;
+//!
+//! let graph = parse("digraph G { a -> b -> c [color=red]; }").unwrap();
+//! assert_eq!(2, graph.edges.len());
+//! assert_eq!(
+//!     Dot { graph }.to_string(),
+//!     "digraph G {\n    a -> b [color=red];\n    b -> c [color=red];\n}\n"
+//! );
+//! ```
+
+use crate::attributes::{AttributeName, AttributeText, PortPosition};
+use crate::dot::{DotString, Edge, Graph, Node, SubGraph};
+use indexmap::{IndexMap, IndexSet};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+type Attributes = IndexMap<AttributeName, AttributeText<'static>>;
+
+/// Error returned when DOT source can not be parsed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+
+    pub message: String,
+}
+
+impl ParseError {
+    fn new<M: Into<String>>(position: Position, message: M) -> Self {
+        Self {
+            line: position.line,
+            column: position.column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a graph from DOT source.
+pub fn parse(input: &str) -> Result<Graph<'static>, ParseError> {
+    parse_document(input).map(|document| document.graph)
+}
+
+/// A graph parsed from DOT source, with what rendering the graph would not keep.
+pub struct Document {
+    pub graph: Graph<'static>,
+
+    /// Number of comments which are not kept as the graph's comment.
+    pub dropped_comments: usize,
+
+    attributes: SourceAttributes,
+}
+
+impl Document {
+    /// Whether every node, edge and subgraph of `other` gets the same attributes as in
+    /// this document, applying defaults in the order of the statements of each source.
+    pub fn same_attributes(&self, other: &Document) -> bool {
+        self.attributes == other.attributes
+    }
+}
+
+/// Parses a graph from DOT source along with the attributes Graphviz gives each
+/// element, see [`Document`].
+pub fn parse_document(input: &str) -> Result<Document, ParseError> {
+    let (comment, dropped_comments, tokens) = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        scopes: Vec::new(),
+        resolution: Resolution::default(),
+    };
+    let mut graph = parser.graph()?;
+    graph.comment = comment;
+    Ok(Document {
+        graph,
+        dropped_comments,
+        attributes: parser.resolution.finish(),
+    })
+}
+
+/// Attributes as text, sorted so they compare regardless of order.
+type SortedAttributes = Vec<(String, String)>;
+
+fn sorted(attributes: &Attributes) -> SortedAttributes {
+    let mut sorted: SortedAttributes = attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.dot_string()))
+        .collect();
+    sorted.sort();
+    sorted
+}
+
+/// Attributes in effect for each element of a graph.
+#[derive(PartialEq, Debug)]
+struct SourceAttributes {
+    nodes: BTreeMap<String, SortedAttributes>,
+    /// Edges by their endpoints, including ports, sorted.
+    edges: Vec<(String, String, SortedAttributes)>,
+    /// Graph attributes of the subgraphs in the order they are opened.
+    sub_graphs: Vec<SortedAttributes>,
+}
+
+/// Attributes of elements as they are created while reading the source.
+#[derive(Default)]
+struct Resolution {
+    /// Node defaults in scope where each node first appears.
+    node_defaults: IndexMap<String, Attributes>,
+    /// Attributes from the statements of each node, in order.
+    node_attributes: HashMap<String, Attributes>,
+    edges: Vec<(String, String, SortedAttributes)>,
+    sub_graphs: Vec<SortedAttributes>,
+}
+
+impl Resolution {
+    fn node(&mut self, id: &str, scope: &Scope) {
+        if !self.node_defaults.contains_key(id) {
+            self.node_defaults
+                .insert(id.to_string(), scope.node.clone());
+        }
+    }
+
+    fn finish(mut self) -> SourceAttributes {
+        let mut nodes = BTreeMap::new();
+        for (id, mut attributes) in self.node_defaults {
+            if let Some(own) = self.node_attributes.remove(&id) {
+                attributes.extend(own);
+            }
+            nodes.insert(id, sorted(&attributes));
+        }
+        self.edges.sort();
+        SourceAttributes {
+            nodes,
+            edges: self.edges,
+            sub_graphs: self.sub_graphs,
+        }
+    }
+}
+
+/// Graph attributes and defaults in effect at a point of a graph or subgraph.
+#[derive(Clone, Default)]
+struct Scope {
+    graph: Attributes,
+    node: Attributes,
+    edge: Attributes,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Token {
+    Id(String),
+    Quoted(String),
+    Html(String),
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Semicolon,
+    Comma,
+    Equals,
+    Colon,
+    Plus,
+    Arrow,
+    Line,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Id(id) => format!("\"{}\"", id),
+            Token::Quoted(_) => "a quoted string".to_string(),
+            Token::Html(_) => "an HTML string".to_string(),
+            Token::LeftBrace => "\"{\"".to_string(),
+            Token::RightBrace => "\"}\"".to_string(),
+            Token::LeftBracket => "\"[\"".to_string(),
+            Token::RightBracket => "\"]\"".to_string(),
+            Token::Semicolon => "\";\"".to_string(),
+            Token::Comma => "\",\"".to_string(),
+            Token::Equals => "\"=\"".to_string(),
+            Token::Colon => "\":\"".to_string(),
+            Token::Plus => "\"+\"".to_string(),
+            Token::Arrow => "\"->\"".to_string(),
+            Token::Line => "\"--\"".to_string(),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Id(id) if id.eq_ignore_ascii_case(keyword))
+    }
+}
+
+struct Chars<'s> {
+    chars: std::iter::Peekable<std::str::Chars<'s>>,
+    position: Position,
+}
+
+impl<'s> Chars<'s> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.chars.clone();
+        chars.next();
+        chars.next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+}
+
+type Tokens = Vec<(Token, Position)>;
+
+fn is_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || !c.is_ascii()
+}
+
+/// Splits the source into tokens, returning the leading comment if it is a single
+/// `//` line and the number of other comments.
+fn tokenize(input: &str) -> Result<(Option<String>, usize, Tokens), ParseError> {
+    let mut chars = Chars {
+        chars: input.chars().peekable(),
+        position: Position { line: 1, column: 1 },
+    };
+    let mut comments: Vec<String> = Vec::new();
+    let mut other_comments = 0;
+    let mut tokens = Vec::new();
+    let mut line_start = true;
+    while let Some(c) = chars.peek() {
+        let start = chars.position;
+        if c == '\n' {
+            chars.next();
+            line_start = true;
+            continue;
+        }
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let at_line_start = line_start;
+        line_start = false;
+
+        let line_comment = c == '/' && chars.peek_second() == Some('/');
+        if line_comment || (c == '#' && at_line_start) {
+            let mut text = String::new();
+            while let Some(c) = chars.peek().filter(|c| *c != '\n') {
+                text.push(c);
+                chars.next();
+            }
+            if line_comment && tokens.is_empty() {
+                comments.push(text[2..].trim().to_string());
+            } else {
+                other_comments += 1;
+            }
+            continue;
+        }
+        if c == '/' && chars.peek_second() == Some('*') {
+            chars.next();
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('*') if chars.peek() == Some('/') => {
+                        chars.next();
+                        break;
+                    }
+                    Some(_) => {}
+                    None => return Err(ParseError::new(start, "Unterminated comment")),
+                }
+            }
+            other_comments += 1;
+            continue;
+        }
+
+        let token = match c {
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ';' => Token::Semicolon,
+            ',' => Token::Comma,
+            '=' => Token::Equals,
+            ':' => Token::Colon,
+            '+' => Token::Plus,
+            '-' if chars.peek_second() == Some('>') => {
+                chars.next();
+                Token::Arrow
+            }
+            '-' if chars.peek_second() == Some('-') => {
+                chars.next();
+                Token::Line
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some('"') => text.push('"'),
+                            Some('\n') => {}
+                            Some(c) => {
+                                text.push('\\');
+                                text.push(c);
+                            }
+                            None => {
+                                return Err(ParseError::new(
+                                    start,
+                                    "Unterminated string",
+                                ))
+                            }
+                        },
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => {
+                            return Err(ParseError::new(start, "Unterminated string"))
+                        }
+                    }
+                }
+                tokens.push((Token::Quoted(text), start));
+                continue;
+            }
+            '<' => {
+                chars.next();
+                let mut text = String::new();
+                let mut depth = 1;
+                loop {
+                    match chars.next() {
+                        Some('<') => {
+                            depth += 1;
+                            text.push('<');
+                        }
+                        Some('>') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                            text.push('>');
+                        }
+                        Some(c) => text.push(c),
+                        None => {
+                            return Err(ParseError::new(
+                                start,
+                                "Unterminated HTML string",
+                            ))
+                        }
+                    }
+                }
+                tokens.push((Token::Html(text), start));
+                continue;
+            }
+            c if c == '-' || c == '.' || c.is_ascii_digit() => {
+                let mut text = String::new();
+                text.push(c);
+                chars.next();
+                while let Some(c) =
+                    chars.peek().filter(|c| c.is_ascii_digit() || *c == '.')
+                {
+                    text.push(c);
+                    chars.next();
+                }
+                if text == "-" || text == "." || text.matches('.').count() > 1 {
+                    return Err(ParseError::new(
+                        start,
+                        format!("Invalid number {}", text),
+                    ));
+                }
+                tokens.push((Token::Id(text), start));
+                continue;
+            }
+            c if is_id_char(c) => {
+                let mut text = String::new();
+                while let Some(c) = chars.peek().filter(|c| is_id_char(*c)) {
+                    text.push(c);
+                    chars.next();
+                }
+                tokens.push((Token::Id(text), start));
+                continue;
+            }
+            c => {
+                return Err(ParseError::new(
+                    start,
+                    format!("Unexpected character {}", c),
+                ))
+            }
+        };
+        chars.next();
+        tokens.push((token, start));
+    }
+
+    let comment = match comments.as_slice() {
+        [comment] => Some(comment.clone()),
+        _ => {
+            other_comments += comments.len();
+            None
+        }
+    };
+    Ok((comment, other_comments, tokens))
+}
+
+struct Parser {
+    tokens: Tokens,
+    position: usize,
+    /// Attributes in effect in the graph and each enclosing subgraph, innermost last.
+    scopes: Vec<Scope>,
+    resolution: Resolution,
+}
+
+/// An end of an edge: a node with its port, or all the nodes of a subgraph.
+enum Endpoint {
+    Node(AttributeText<'static>, Option<PortPosition>),
+    SubGraph(Vec<String>),
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn peek_second(&self) -> Option<&Token> {
+        self.tokens.get(self.position + 1).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|token| token.is_keyword(keyword)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error<M: Into<String>>(&self, message: M) -> ParseError {
+        let position = match self.tokens.get(self.position) {
+            Some((_, position)) => *position,
+            None => self
+                .tokens
+                .last()
+                .map_or(Position { line: 1, column: 1 }, |t| t.1),
+        };
+        ParseError::new(position, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => {
+                self.error(format!("Expected {}, found {}", expected, token.describe()))
+            }
+            None => self.error(format!("Expected {}, found the end of input", expected)),
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), ParseError> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&token.describe()))
+        }
+    }
+
+    fn graph(&mut self) -> Result<Graph<'static>, ParseError> {
+        let strict = self.eat_keyword("strict");
+        let is_directed = if self.eat_keyword("digraph") {
+            true
+        } else if self.eat_keyword("graph") {
+            false
+        } else {
+            return Err(self.unexpected("\"graph\" or \"digraph\""));
+        };
+        let id = match self.peek() {
+            Some(Token::LeftBrace) => None,
+            _ => Some(self.id()?),
+        };
+        self.expect(Token::LeftBrace)?;
+        self.scopes.push(Scope::default());
+        let root = self.statements(None, is_directed)?;
+        self.expect(Token::RightBrace)?;
+        if self.peek().is_some() {
+            return Err(self.unexpected("the end of input"));
+        }
+        Ok(Graph::new(
+            id,
+            is_directed,
+            strict,
+            None,
+            root.graph_attributes,
+            root.node_attributes,
+            root.edge_attributes,
+            root.sub_graphs,
+            root.nodes,
+            root.edges,
+        ))
+    }
+
+    /// An id, concatenating quoted strings joined by `+`.
+    fn id(&mut self) -> Result<AttributeText<'static>, ParseError> {
+        match self.peek() {
+            Some(Token::Id(_)) | Some(Token::Quoted(_)) | Some(Token::Html(_)) => {}
+            _ => return Err(self.unexpected("an id")),
+        }
+        match self.next() {
+            Some(Token::Id(id)) => Ok(AttributeText::attr(id)),
+            Some(Token::Html(html)) => Ok(AttributeText::html(html)),
+            Some(Token::Quoted(mut text)) => {
+                while self.peek() == Some(&Token::Plus) {
+                    self.next();
+                    match self.next() {
+                        Some(Token::Quoted(next)) => text.push_str(&next),
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.unexpected("a quoted string"));
+                        }
+                    }
+                }
+                Ok(AttributeText::quoted(text))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn statements(
+        &mut self,
+        id: Option<String>,
+        is_directed: bool,
+    ) -> Result<SubGraph<'static>, ParseError> {
+        let mut scope = SubGraph::new(
+            id,
+            IndexMap::new(),
+            IndexMap::new(),
+            IndexMap::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+        while !matches!(self.peek(), Some(Token::RightBrace) | None) {
+            self.statement(&mut scope, is_directed)?;
+            self.eat(&Token::Semicolon);
+        }
+        Ok(scope)
+    }
+
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("statements are parsed within a scope")
+    }
+
+    fn statement(
+        &mut self,
+        scope: &mut SubGraph<'static>,
+        is_directed: bool,
+    ) -> Result<(), ParseError> {
+        let kind = ["graph", "node", "edge"]
+            .iter()
+            .copied()
+            .find(|kind| self.peek().is_some_and(|t| t.is_keyword(kind)));
+        if let Some(kind) = kind {
+            self.next();
+            let attributes = self.attribute_lists()?;
+            let current = self.scope();
+            let (defaults, current) = match kind {
+                "graph" => (&mut scope.graph_attributes, &mut current.graph),
+                "node" => (&mut scope.node_attributes, &mut current.node),
+                _ => (&mut scope.edge_attributes, &mut current.edge),
+            };
+            current.extend(attributes.clone());
+            defaults.extend(attributes);
+            return Ok(());
+        }
+
+        if self.peek_second() == Some(&Token::Equals) {
+            let key = AttributeName::from(self.id()?.as_str());
+            self.next();
+            let value = self.id()?;
+            self.scope().graph.insert(key.clone(), value.clone());
+            scope.graph_attributes.insert(key, value);
+            return Ok(());
+        }
+
+        let first = self.endpoint(scope, is_directed)?;
+        if !matches!(self.peek(), Some(Token::Arrow) | Some(Token::Line)) {
+            if let Endpoint::Node(id, _) = first {
+                let mut node = Node::new(id);
+                node.attributes = self.attribute_lists()?;
+                self.resolution
+                    .node_attributes
+                    .entry(node.id.as_str().to_string())
+                    .or_default()
+                    .extend(node.attributes.clone());
+                scope.nodes.push(node);
+            }
+            return Ok(());
+        }
+
+        let mut endpoints = vec![first];
+        loop {
+            match (self.peek(), is_directed) {
+                (Some(Token::Arrow), true) | (Some(Token::Line), false) => {
+                    self.next();
+                }
+                (Some(Token::Arrow), false) => {
+                    return Err(self.error("Directed edge in an undirected graph"))
+                }
+                (Some(Token::Line), true) => {
+                    return Err(self.error("Undirected edge in a directed graph"))
+                }
+                _ => break,
+            }
+            endpoints.push(self.endpoint(scope, is_directed)?);
+        }
+        let attributes = self.attribute_lists()?;
+        let mut effective = self.scope().edge.clone();
+        effective.extend(attributes.clone());
+        let effective = sorted(&effective);
+        for pair in endpoints.windows(2) {
+            for (source, source_port) in pair[0].nodes() {
+                for (target, target_port) in pair[1].nodes() {
+                    let mut edge = Edge::new(source, target);
+                    edge.source_port_position = source_port.cloned();
+                    edge.target_port_position = target_port.cloned();
+                    edge.attributes = attributes.clone();
+                    self.resolution.edges.push((
+                        endpoint_text(source, source_port),
+                        endpoint_text(target, target_port),
+                        effective.clone(),
+                    ));
+                    scope.edges.push(edge);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn endpoint(
+        &mut self,
+        scope: &mut SubGraph<'static>,
+        is_directed: bool,
+    ) -> Result<Endpoint, ParseError> {
+        let sub_graph = self.peek() == Some(&Token::LeftBrace)
+            || self.peek().is_some_and(|t| t.is_keyword("subgraph"));
+        if sub_graph {
+            let id = if self.eat_keyword("subgraph")
+                && self.peek() != Some(&Token::LeftBrace)
+            {
+                Some(self.id()?.as_str().to_string())
+            } else {
+                None
+            };
+            self.expect(Token::LeftBrace)?;
+            // Subgraphs start with the attributes in effect where they are opened
+            let parent = self.scope().clone();
+            self.scopes.push(parent);
+            let index = self.resolution.sub_graphs.len();
+            self.resolution.sub_graphs.push(Vec::new());
+            let sub_graph = self.statements(id, is_directed)?;
+            self.expect(Token::RightBrace)?;
+            let sub_graph_scope = self.scopes.pop().expect("the subgraph's scope");
+            self.resolution.sub_graphs[index] = sorted(&sub_graph_scope.graph);
+            let mut nodes = IndexSet::new();
+            collect_node_ids(&sub_graph, &mut nodes);
+            scope.sub_graphs.push(sub_graph);
+            return Ok(Endpoint::SubGraph(nodes.into_iter().collect()));
+        }
+
+        let id = self.id()?;
+        let current = self
+            .scopes
+            .last()
+            .expect("statements are parsed within a scope");
+        self.resolution.node(id.as_str(), current);
+        let mut port = Vec::new();
+        while self.eat(&Token::Colon) {
+            port.push(self.id()?.as_str().to_string());
+        }
+        let port = match port.as_slice() {
+            [] => None,
+            parts => match parts.join(":").parse() {
+                Ok(port) => Some(port),
+                Err(_) => return Err(self.error("Invalid port")),
+            },
+        };
+        Ok(Endpoint::Node(id, port))
+    }
+
+    /// One or more attribute lists, such as `[a=b, c=d][e=f]`.
+    fn attribute_lists(&mut self) -> Result<Attributes, ParseError> {
+        let mut attributes = Attributes::new();
+        while self.eat(&Token::LeftBracket) {
+            while !self.eat(&Token::RightBracket) {
+                let key = self.id()?;
+                self.expect(Token::Equals)?;
+                let value = self.id()?;
+                attributes.insert(AttributeName::from(key.as_str()), value);
+                if !self.eat(&Token::Comma) {
+                    self.eat(&Token::Semicolon);
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+impl Endpoint {
+    fn nodes(&self) -> Vec<(&str, Option<&PortPosition>)> {
+        match self {
+            Endpoint::Node(id, port) => vec![(id.as_str(), port.as_ref())],
+            Endpoint::SubGraph(ids) => {
+                ids.iter().map(|id| (id.as_str(), None)).collect()
+            }
+        }
+    }
+}
+
+fn endpoint_text(id: &str, port: Option<&PortPosition>) -> String {
+    match port {
+        Some(port) => format!("{}:{}", id, port.dot_string()),
+        None => id.to_string(),
+    }
+}
+
+/// Ids of the nodes of the subgraph and its subgraphs, including those only named by
+/// edges, in the order they appear.
+fn collect_node_ids(sub_graph: &SubGraph, ids: &mut IndexSet<String>) {
+    for child in &sub_graph.sub_graphs {
+        collect_node_ids(child, ids);
+    }
+    for node in &sub_graph.nodes {
+        ids.insert(node.id.as_str().to_string());
+    }
+    for edge in &sub_graph.edges {
+        ids.insert(edge.source.clone());
+        ids.insert(edge.target.clone());
+    }
+}
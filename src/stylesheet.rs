@@ -0,0 +1,154 @@
+//! CSS rules for the classes of elements, for styling SVG output.
+//!
+//! Graphviz adds the `class` attribute of nodes, edges, clusters and the graph to their
+//! SVG elements, and links the file named by the graph's `stylesheet` attribute.
+//! A [`Stylesheet`] keeps the CSS rules for those classes next to the graph, and as a
+//! [`Lint`] reports classes which are used without a rule or have a rule but are unused.
+//!
+//! ```rust
+//! use dotavious::attributes::NodeAttributes;
+//! use dotavious::lint::Linter;
+//! use dotavious::stylesheet::Stylesheet;
+//! use dotavious::{GraphBuilder, NodeBuilder};
+//!
+//! let mut stylesheet = Stylesheet::new();
+//! stylesheet.rule("database", "fill", "lightblue");
+//!
+//! let graph = GraphBuilder::new_directed()
+//!     .add_node(
+//!         NodeBuilder::new("users")
+//!             .class("database".to_string())
+//!             .build()
+//!             .unwrap(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//!
+//! assert!(Linter::new().add_lint(stylesheet.clone()).lint(&graph).is_empty());
+//! assert_eq!(".database {\n    fill: lightblue;\n}\n", stylesheet.to_css());
+//! ```
+
+use crate::attributes::AttributeName;
+use crate::dot::Graph;
+use crate::lint::{Diagnostic, Lint};
+use crate::validation::{walk, ElementPath, PathSegment};
+use indexmap::IndexMap;
+
+/// CSS declarations by class name.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Stylesheet {
+    rules: IndexMap<String, IndexMap<String, String>>,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the property in the rule for the class, adding the rule if needed.
+    pub fn rule<C, P, V>(&mut self, class: C, property: P, value: V) -> &mut Self
+    where
+        C: Into<String>,
+        P: Into<String>,
+        V: Into<String>,
+    {
+        self.rules
+            .entry(class.into())
+            .or_default()
+            .insert(property.into(), value.into());
+        self
+    }
+
+    /// Names of the classes with a rule, in the order they were added.
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.rules.keys().map(String::as_str)
+    }
+
+    /// Declarations of the rule for the class, by property.
+    pub fn declarations(&self, class: &str) -> Option<&IndexMap<String, String>> {
+        self.rules.get(class)
+    }
+
+    /// The rules as the contents of a CSS file, such as the one named by the graph's
+    /// `stylesheet` attribute.
+    pub fn to_css(&self) -> String {
+        let rules: Vec<String> = self
+            .rules
+            .iter()
+            .map(|(class, declarations)| {
+                let mut rule = format!(".{} {{\n", class);
+                for (property, value) in declarations {
+                    rule.push_str(&format!("    {}: {};\n", property, value));
+                }
+                rule.push_str("}\n");
+                rule
+            })
+            .collect();
+        rules.join("\n")
+    }
+
+    /// The rules as a `<style>` element to embed in SVG output.
+    pub fn to_style_element(&self) -> String {
+        format!(
+            "<style type=\"text/css\"><![CDATA[\n{}]]></style>\n",
+            self.to_css()
+        )
+    }
+}
+
+impl Lint for Stylesheet {
+    fn name(&self) -> &str {
+        "stylesheet-classes"
+    }
+
+    fn description(&self) -> &str {
+        "classes used without a stylesheet rule, or with a rule but unused"
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        let used = used_classes(graph);
+        let mut diagnostics = Vec::new();
+        for (class, paths) in &used {
+            if self.rules.contains_key(class) {
+                continue;
+            }
+            for path in paths {
+                diagnostics.push(
+                    Diagnostic::new(
+                        path.clone(),
+                        format!("Class {} has no stylesheet rule", class),
+                    )
+                    .with_field("class")
+                    .with_value(class.clone()),
+                );
+            }
+        }
+        let root = ElementPath::new(PathSegment::Graph(
+            graph.id.as_ref().map(|id| id.as_str().to_string()),
+        ));
+        for class in self.classes().filter(|class| !used.contains_key(*class)) {
+            diagnostics.push(Diagnostic::new(
+                root.clone(),
+                format!("Stylesheet rule for class {} is unused", class),
+            ));
+        }
+        diagnostics
+    }
+}
+
+/// Classes assigned to the graph, its subgraphs, nodes and edges and their defaults,
+/// with the paths of the elements using each.
+pub fn used_classes(graph: &Graph) -> IndexMap<String, Vec<ElementPath>> {
+    let mut classes: IndexMap<String, Vec<ElementPath>> = IndexMap::new();
+    walk(graph, &mut |path, element| {
+        if let Some(names) = element.attributes().get(&AttributeName::Class) {
+            for name in names.as_str().split_whitespace() {
+                classes
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+    });
+    classes
+}
@@ -0,0 +1,272 @@
+//! Reusable default attributes and named node styles.
+
+use crate::attributes::{
+    AttributeName, AttributeText, Color, EdgeAttributes, GraphAttributeStatementBuilder,
+    GraphAttributes, NodeAttributes, NodeStyle, NodeStyleList, Shape,
+};
+use crate::dot::{EdgeAttributeStatementBuilder, Graph, NodeAttributeStatementBuilder};
+use indexmap::IndexMap;
+
+type Attributes<'a> = IndexMap<AttributeName, AttributeText<'a>>;
+
+/// Graph, node and edge defaults along with named classes of node styles.
+///
+/// Applied with [`GraphBuilder::with_theme`], classes are given to nodes with
+/// [`NodeBuilder::with_class`].
+///
+/// ```rust
+/// use dotavious::attributes::{NodeAttributes, Shape};
+/// use dotavious::theme::Theme;
+/// use dotavious::{GraphBuilder, NodeBuilder};
+///
+/// let mut theme = Theme::light();
+/// theme.add_class(
+///     "cache",
+///     theme.class("database").shape(Shape::Box3D).build().unwrap(),
+/// );
+///
+/// let graph = GraphBuilder::new_directed()
+///     .with_theme(&theme)
+///     .add_node(NodeBuilder::new("redis").with_class("cache").build().unwrap())
+///     .build()
+///     .unwrap();
+/// assert_eq!(Some(Shape::Box3D), graph.nodes[0].shape());
+/// ```
+///
+/// [`GraphBuilder::with_theme`]: crate::GraphBuilder::with_theme
+/// [`NodeBuilder::with_class`]: crate::NodeBuilder::with_class
+#[derive(Clone, Debug, Default)]
+pub struct Theme<'a> {
+    pub graph_attributes: Attributes<'a>,
+
+    pub node_attributes: Attributes<'a>,
+
+    pub edge_attributes: Attributes<'a>,
+
+    /// Node attributes of each class, by class name.
+    pub classes: IndexMap<String, Attributes<'a>>,
+}
+
+impl<'a> Theme<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_graph_attributes(
+        &mut self,
+        graph_attributes: Attributes<'a>,
+    ) -> &mut Self {
+        self.graph_attributes.extend(graph_attributes);
+        self
+    }
+
+    pub fn add_node_attributes(&mut self, node_attributes: Attributes<'a>) -> &mut Self {
+        self.node_attributes.extend(node_attributes);
+        self
+    }
+
+    pub fn add_edge_attributes(&mut self, edge_attributes: Attributes<'a>) -> &mut Self {
+        self.edge_attributes.extend(edge_attributes);
+        self
+    }
+
+    /// Adds the attributes to the class, creating it if needed.
+    pub fn add_class<S: Into<String>>(
+        &mut self,
+        name: S,
+        attributes: Attributes<'a>,
+    ) -> &mut Self {
+        self.classes
+            .entry(name.into())
+            .or_default()
+            .extend(attributes);
+        self
+    }
+
+    /// A builder starting from the attributes of the class, such as for deriving another
+    /// class from it. The builder is empty if the theme has no such class.
+    pub fn class(&self, name: &str) -> NodeAttributeStatementBuilder<'a> {
+        let mut builder = NodeAttributeStatementBuilder::new();
+        if let Some(attributes) = self.classes.get(name) {
+            builder.attributes = attributes.clone();
+        }
+        builder
+    }
+
+    /// Dark text on white with light grey nodes.
+    pub fn light() -> Self {
+        Self::with_palette(&Palette {
+            font: "Helvetica",
+            background: Color::Named("white"),
+            text: Color::RGB {
+                red: 0x21,
+                green: 0x21,
+                blue: 0x21,
+            },
+            line: Color::RGB {
+                red: 0x61,
+                green: 0x61,
+                blue: 0x61,
+            },
+            fill: Color::RGB {
+                red: 0xf5,
+                green: 0xf5,
+                blue: 0xf5,
+            },
+            accent: Color::RGB {
+                red: 0xd6,
+                green: 0xe4,
+                blue: 0xf0,
+            },
+        })
+    }
+
+    /// Light text on a dark background with dark grey nodes.
+    pub fn dark() -> Self {
+        Self::with_palette(&Palette {
+            font: "Helvetica",
+            background: Color::RGB {
+                red: 0x1e,
+                green: 0x1e,
+                blue: 0x1e,
+            },
+            text: Color::RGB {
+                red: 0xee,
+                green: 0xee,
+                blue: 0xee,
+            },
+            line: Color::RGB {
+                red: 0xb0,
+                green: 0xb0,
+                blue: 0xb0,
+            },
+            fill: Color::RGB {
+                red: 0x2d,
+                green: 0x2d,
+                blue: 0x2d,
+            },
+            accent: Color::RGB {
+                red: 0x26,
+                green: 0x4f,
+                blue: 0x78,
+            },
+        })
+    }
+
+    /// Black on white without fills, for printing.
+    pub fn monochrome() -> Self {
+        Self::with_palette(&Palette {
+            font: "Times-Roman",
+            background: Color::Named("white"),
+            text: Color::Named("black"),
+            line: Color::Named("black"),
+            fill: Color::Named("white"),
+            accent: Color::Named("white"),
+        })
+    }
+
+    /// The built-in themes, which differ only in their colors and font.
+    /// Every theme has the classes `database`, `service`, `external` and `highlight`.
+    fn with_palette(palette: &Palette<'a>) -> Self {
+        let font = palette.font.to_string();
+        let mut theme = Theme::new();
+        theme
+            .add_graph_attributes(
+                GraphAttributeStatementBuilder::new()
+                    .background_color(palette.background)
+                    .font_color(palette.text)
+                    .font_name(font.clone())
+                    .build_ignore_validation(),
+            )
+            .add_node_attributes(
+                NodeAttributeStatementBuilder::new()
+                    .shape(Shape::Box)
+                    .styles(
+                        NodeStyleList::new()
+                            .style(NodeStyle::Rounded)
+                            .style(NodeStyle::Filled),
+                    )
+                    .color(palette.line)
+                    .fill_color(palette.fill)
+                    .font_color(palette.text)
+                    .font_name(font.clone())
+                    .build_ignore_validation(),
+            )
+            .add_edge_attributes(
+                EdgeAttributeStatementBuilder::new()
+                    .color(palette.line)
+                    .font_color(palette.text)
+                    .font_name(font)
+                    .build_ignore_validation(),
+            )
+            .add_class(
+                "database",
+                NodeAttributeStatementBuilder::new()
+                    .shape(Shape::Cylinder)
+                    .style(NodeStyle::Filled)
+                    .fill_color(palette.accent)
+                    .build_ignore_validation(),
+            )
+            .add_class(
+                "service",
+                NodeAttributeStatementBuilder::new()
+                    .fill_color(palette.accent)
+                    .build_ignore_validation(),
+            )
+            .add_class(
+                "external",
+                NodeAttributeStatementBuilder::new()
+                    .styles(
+                        NodeStyleList::new()
+                            .style(NodeStyle::Rounded)
+                            .style(NodeStyle::Dashed),
+                    )
+                    .build_ignore_validation(),
+            )
+            .add_class(
+                "highlight",
+                NodeAttributeStatementBuilder::new()
+                    .pen_width(2.0)
+                    .build_ignore_validation(),
+            );
+        theme
+    }
+}
+
+struct Palette<'a> {
+    font: &'static str,
+    background: Color<'a>,
+    text: Color<'a>,
+    line: Color<'a>,
+    fill: Color<'a>,
+    accent: Color<'a>,
+}
+
+/// Adds the attributes of the classes named in the `class` attribute of each node
+/// statement which the statement does not set itself.
+/// Later classes take precedence over earlier ones.
+pub(crate) fn apply_classes<'a>(
+    graph: &mut Graph<'a>,
+    classes: &IndexMap<String, Attributes<'a>>,
+) {
+    if classes.is_empty() {
+        return;
+    }
+    graph.for_each_scope_mut(&mut |scope| {
+        for node in scope.nodes.iter_mut() {
+            let names = match node.attributes.get(&AttributeName::Class) {
+                Some(names) => names.as_str().to_string(),
+                None => continue,
+            };
+            let mut attributes = Attributes::new();
+            for name in names.split_whitespace() {
+                if let Some(class) = classes.get(name) {
+                    attributes.extend(class.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+            }
+            for (key, value) in attributes {
+                node.attributes.entry(key).or_insert(value);
+            }
+        }
+    });
+}
@@ -1,9 +1,859 @@
+use crate::attributes::{
+    validate_attribute, AttributeContext, AttributeName, AttributeText, GraphStyle,
+    GraphStyleList, LayerRange, Layers, LayoutEngine, NodeStyle, NodeStyleList,
+    PortPosition, Shape, DEFAULT_LAYER_LIST_SEP, DEFAULT_LAYER_SEP,
+};
+use crate::dot::{DotString, Edge, Graph, Node, SubGraph};
+use indexmap::IndexMap;
 use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 
 pub type ValidationResult<T> = std::result::Result<T, Vec<ValidationError>>;
 
+/// How severe a validation error is.
+/// Only errors cause building to fail.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Stable code identifying the kind of a validation error.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ErrorCode {
+    /// The attribute is not a known Graphviz attribute.
+    UnknownAttribute,
+
+    /// The attribute does not apply to the kind of element it is set on.
+    InvalidContext,
+
+    /// The value is not of the attribute's type.
+    InvalidValue,
+
+    /// The value is outside of the attribute's allowed range.
+    OutOfRange,
+
+    /// An HTML string is used for an attribute which is not a label.
+    HtmlNotAllowed,
+
+    /// An escString substitution is not valid for the element or attribute.
+    InvalidSubstitution,
+
+    /// A layer range references a layer not defined by the graph.
+    UndefinedLayer,
+
+    /// A style is not valid for the shape or kind of element it is set on.
+    InvalidStyle,
+
+    /// The attribute has no effect with the graph's layout engine.
+    UnsupportedByEngine,
+
+    /// An edge references a node which is not declared by a node statement.
+    UndeclaredNode,
+
+    /// A node is declared more than once with different values for an attribute.
+    ConflictingNodeAttributes,
+
+    /// More than one subgraph has the same id.
+    DuplicateSubGraph,
+
+    /// lhead or ltail does not name a cluster containing the edge's endpoint.
+    InvalidClusterReference,
+
+    /// lhead or ltail is used without compound=true on the graph.
+    MissingCompound,
+
+    /// An edge references a port not defined by the node's record or HTML label.
+    UnknownPort,
+
+    /// Edges between the same nodes in a strict graph, which are merged.
+    ParallelEdge,
+}
+
+impl ErrorCode {
+    /// The code as a stable, kebab-case string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::UnknownAttribute => "unknown-attribute",
+            ErrorCode::InvalidContext => "invalid-context",
+            ErrorCode::InvalidValue => "invalid-value",
+            ErrorCode::OutOfRange => "out-of-range",
+            ErrorCode::HtmlNotAllowed => "html-not-allowed",
+            ErrorCode::InvalidSubstitution => "invalid-substitution",
+            ErrorCode::UndefinedLayer => "undefined-layer",
+            ErrorCode::InvalidStyle => "invalid-style",
+            ErrorCode::UnsupportedByEngine => "unsupported-by-engine",
+            ErrorCode::UndeclaredNode => "undeclared-node",
+            ErrorCode::ConflictingNodeAttributes => "conflicting-node-attributes",
+            ErrorCode::DuplicateSubGraph => "duplicate-subgraph",
+            ErrorCode::InvalidClusterReference => "invalid-cluster-reference",
+            ErrorCode::MissingCompound => "missing-compound",
+            ErrorCode::UnknownPort => "unknown-port",
+            ErrorCode::ParallelEdge => "parallel-edge",
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How edges referencing nodes without a node statement are reported.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum UndeclaredNodePolicy {
+    /// Such nodes are created implicitly, as Graphviz does.
+    #[default]
+    Implicit,
+    Warn,
+    Error,
+}
+
+/// An element of the graph along an [`ElementPath`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PathSegment {
+    Graph(Option<String>),
+    SubGraph(Option<String>),
+    Node(String),
+
+    /// An edge, with its index within the enclosing graph or subgraph when known.
+    Edge {
+        index: Option<usize>,
+        source: String,
+        target: String,
+    },
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Graph(Some(id)) => write!(f, "graph {}", id),
+            PathSegment::Graph(None) => write!(f, "graph"),
+            PathSegment::SubGraph(Some(id)) => write!(f, "subgraph {}", id),
+            PathSegment::SubGraph(None) => write!(f, "subgraph"),
+            PathSegment::Node(id) => write!(f, "node {}", id),
+            PathSegment::Edge {
+                index: Some(index),
+                source,
+                target,
+            } => write!(f, "edge {} ({} -> {})", index, source, target),
+            PathSegment::Edge {
+                index: None,
+                source,
+                target,
+            } => write!(f, "edge {} -> {}", source, target),
+        }
+    }
+}
+
+/// Location of the element a validation error was raised for, from the root graph down
+/// through subgraphs to a node or edge.
+/// Empty when the location is not known, such as for attribute statements.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ElementPath {
+    pub segments: Vec<PathSegment>,
+}
+
+impl ElementPath {
+    pub fn new(segment: PathSegment) -> Self {
+        Self {
+            segments: vec![segment],
+        }
+    }
+
+    /// This path extended by `segment`.
+    pub fn child(&self, segment: PathSegment) -> Self {
+        let mut path = self.clone();
+        path.segments.push(segment);
+        path
+    }
+
+    /// This path prefixed by `parent`.
+    pub fn within(&self, parent: &ElementPath) -> Self {
+        let mut path = parent.clone();
+        path.segments.extend(self.segments.iter().cloned());
+        path
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+impl Display for ElementPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" > ")?;
+            }
+            segment.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ValidationError {
     pub message: Cow<'static, str>,
     pub field: Cow<'static, str>,
-}
\ No newline at end of file
+    pub code: ErrorCode,
+    pub severity: Severity,
+    pub path: ElementPath,
+
+    /// The offending value, if any.
+    pub value: Option<String>,
+}
+
+impl ValidationError {
+    /// An error with an unknown location and value.
+    pub fn new<F, M>(code: ErrorCode, field: F, message: M) -> Self
+    where
+        F: Into<Cow<'static, str>>,
+        M: Into<Cow<'static, str>>,
+    {
+        Self {
+            message: message.into(),
+            field: field.into(),
+            code,
+            severity: Severity::Error,
+            path: ElementPath::default(),
+            value: None,
+        }
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_path(mut self, path: ElementPath) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn with_value<S: Into<String>>(mut self, value: S) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Whether this causes building to fail.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: ", self.severity, self.code)?;
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        f.write_str(&self.field)?;
+        if let Some(value) = &self.value {
+            write!(f, " = \"{}\"", value)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Places errors raised by a builder at `path`,
+/// filling in missing values from the builder's attributes.
+pub(crate) fn locate_errors(
+    errors: &[ValidationError],
+    path: &ElementPath,
+    attributes: &IndexMap<AttributeName, AttributeText>,
+) -> Vec<ValidationError> {
+    errors
+        .iter()
+        .map(|error| {
+            let mut error = error.clone();
+            error.path = error.path.within(path);
+            if error.value.is_none() {
+                error.value = attributes
+                    .get(error.field.as_ref())
+                    .map(|value| value.as_str().to_string());
+            }
+            error
+        })
+        .collect()
+}
+
+/// An element visited when walking a graph, in the order it is rendered.
+pub(crate) enum Element<'g, 'a> {
+    /// Graph attributes of the root graph or a subgraph.
+    Graph(&'g IndexMap<AttributeName, AttributeText<'a>>),
+    NodeDefaults(&'g IndexMap<AttributeName, AttributeText<'a>>),
+    EdgeDefaults(&'g IndexMap<AttributeName, AttributeText<'a>>),
+    SubGraph(&'g SubGraph<'a>),
+    Node(&'g Node<'a>),
+    Edge(&'g Edge<'a>),
+}
+
+impl<'g, 'a> Element<'g, 'a> {
+    pub(crate) fn attributes(&self) -> &'g IndexMap<AttributeName, AttributeText<'a>> {
+        match self {
+            Element::Graph(attributes)
+            | Element::NodeDefaults(attributes)
+            | Element::EdgeDefaults(attributes) => attributes,
+            Element::SubGraph(sub_graph) => &sub_graph.graph_attributes,
+            Element::Node(node) => &node.attributes,
+            Element::Edge(edge) => &edge.attributes,
+        }
+    }
+}
+
+/// Visits every element of the graph with its path.
+/// Graph attributes and defaults are located at their graph or subgraph.
+pub(crate) fn walk<'g, 'a>(
+    graph: &'g Graph<'a>,
+    f: &mut dyn FnMut(&ElementPath, Element<'g, 'a>),
+) {
+    let id = graph.id.as_ref().map(|id| id.as_str().to_string());
+    let path = ElementPath::new(PathSegment::Graph(id));
+    f(&path, Element::Graph(&graph.graph_attributes));
+    walk_statements(
+        &path,
+        &graph.node_attributes,
+        &graph.edge_attributes,
+        &graph.sub_graphs,
+        &graph.nodes,
+        &graph.edges,
+        f,
+    );
+}
+
+fn walk_statements<'g, 'a>(
+    path: &ElementPath,
+    node_attributes: &'g IndexMap<AttributeName, AttributeText<'a>>,
+    edge_attributes: &'g IndexMap<AttributeName, AttributeText<'a>>,
+    sub_graphs: &'g [SubGraph<'a>],
+    nodes: &'g [Node<'a>],
+    edges: &'g [Edge<'a>],
+    f: &mut dyn FnMut(&ElementPath, Element<'g, 'a>),
+) {
+    f(path, Element::NodeDefaults(node_attributes));
+    f(path, Element::EdgeDefaults(edge_attributes));
+    for sub_graph in sub_graphs {
+        let path = path.child(PathSegment::SubGraph(sub_graph.id.clone()));
+        f(&path, Element::SubGraph(sub_graph));
+        walk_statements(
+            &path,
+            &sub_graph.node_attributes,
+            &sub_graph.edge_attributes,
+            &sub_graph.sub_graphs,
+            &sub_graph.nodes,
+            &sub_graph.edges,
+            f,
+        );
+    }
+    for node in nodes {
+        let segment = PathSegment::Node(node.id.as_str().to_string());
+        f(&path.child(segment), Element::Node(node));
+    }
+    for (index, edge) in edges.iter().enumerate() {
+        let segment = PathSegment::Edge {
+            index: Some(index),
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+        };
+        f(&path.child(segment), Element::Edge(edge));
+    }
+}
+
+/// Reports attributes which are unknown, not valid for the element they are set on or
+/// have invalid values, as the builders do when attributes are added.
+/// Useful for graphs which were not built with the builders, such as parsed ones.
+pub fn validate_attributes(graph: &Graph) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    walk(graph, &mut |path, element| {
+        let context = match &element {
+            Element::Graph(_) => AttributeContext::Graph,
+            Element::SubGraph(sub_graph) => {
+                AttributeContext::for_sub_graph(sub_graph.id.as_deref())
+            }
+            Element::NodeDefaults(_) | Element::Node(_) => AttributeContext::Node,
+            Element::EdgeDefaults(_) | Element::Edge(_) => AttributeContext::Edge,
+        };
+        let attributes = element.attributes();
+        let mut element_errors = Vec::new();
+        for (key, value) in attributes {
+            validate_attribute(&mut element_errors, &[context], key, value);
+        }
+        errors.extend(locate_errors(&element_errors, path, attributes));
+    });
+    errors
+}
+
+/// Reports attributes which have no effect with the layout engine the graph will be
+/// laid out with, as given by its layout attribute. Graphs without a layout
+/// attribute are laid out by dot.
+///
+/// These are warnings as Graphviz silently ignores such attributes.
+pub fn validate_layout_engine(graph: &Graph) -> Vec<ValidationError> {
+    let engine = graph
+        .graph_attributes
+        .get("layout")
+        .and_then(|layout| LayoutEngine::try_from(layout).ok())
+        .unwrap_or(LayoutEngine::Dot);
+
+    let mut errors = Vec::new();
+    walk(graph, &mut |path, element| {
+        for (key, value) in element.attributes() {
+            if !engine.supports(key.as_str()) {
+                errors.push(
+                    ValidationError::new(
+                        ErrorCode::UnsupportedByEngine,
+                        key.to_string(),
+                        format!(
+                            "Has no effect with the {} layout engine",
+                            engine.dot_string()
+                        ),
+                    )
+                    .with_severity(Severity::Warning)
+                    .with_path(path.clone())
+                    .with_value(value.as_str()),
+                );
+            }
+        }
+    });
+    errors
+}
+
+/// Reports layer ranges referencing layers which are not defined by the graph's
+/// layers attribute, honouring the graph's layersep and layerlistsep.
+pub fn validate_layers(graph: &Graph) -> Vec<ValidationError> {
+    let attribute = |name| graph.graph_attributes.get(&name).map(AttributeText::as_str);
+    let layer_sep = attribute(AttributeName::LayerSep).unwrap_or(DEFAULT_LAYER_SEP);
+    let layer_list_sep =
+        attribute(AttributeName::LayerListSep).unwrap_or(DEFAULT_LAYER_LIST_SEP);
+    let layers = attribute(AttributeName::Layers)
+        .and_then(|layers| Layers::parse_with_separator(layers, layer_sep).ok())
+        .unwrap_or_else(|| Layers::new(Vec::<String>::new()));
+
+    let mut errors = Vec::new();
+    walk(graph, &mut |path, element| {
+        let attributes = element.attributes();
+        for key in &[AttributeName::Layer, AttributeName::LayerSelect] {
+            let value = match attributes.get(key) {
+                Some(value) => value.as_str(),
+                None => continue,
+            };
+            let error = |code, message: String| {
+                ValidationError::new(code, key.to_string(), message)
+                    .with_path(path.clone())
+                    .with_value(value)
+            };
+            let range = match LayerRange::parse_with_separators(
+                value,
+                layer_sep,
+                layer_list_sep,
+            ) {
+                Ok(range) => range,
+                Err(e) => {
+                    errors.push(error(ErrorCode::InvalidValue, e.to_string()));
+                    continue;
+                }
+            };
+            for id in range.layer_ids().filter(|id| !layers.contains(id)) {
+                errors.push(error(
+                    ErrorCode::UndefinedLayer,
+                    format!(
+                        "Layer \"{}\" is not defined in the graph's layers",
+                        id.dot_string()
+                    ),
+                ));
+            }
+        }
+    });
+    errors
+}
+
+/// Reports styles which are not valid for the shape of the node or the kind of subgraph
+/// they are applied to: wedged is only valid for elliptical nodes and striped only for
+/// rectangular nodes and clusters.
+pub fn validate_styles(graph: &Graph) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut sub_graph_errors = Vec::new();
+    let mut nodes: IndexMap<&str, ElementPath> = IndexMap::new();
+    walk(graph, &mut |path, element| match element {
+        Element::SubGraph(sub_graph) => {
+            let is_cluster = AttributeContext::for_sub_graph(sub_graph.id.as_deref())
+                == AttributeContext::Cluster;
+            let striped = sub_graph
+                .graph_attributes
+                .get("style")
+                .and_then(|style| GraphStyleList::try_from(style).ok())
+                .is_some_and(|styles| styles.contains(&GraphStyle::Striped));
+            if striped && !is_cluster {
+                sub_graph_errors.push(style_error(
+                    path,
+                    &sub_graph.graph_attributes,
+                    format!(
+                        "Striped style on subgraph {} is only valid for clusters",
+                        sub_graph.id.as_deref().unwrap_or("")
+                    ),
+                ));
+            }
+        }
+        Element::Node(node) => {
+            nodes
+                .entry(node.id.as_str())
+                .or_insert_with(|| path.clone());
+        }
+        Element::Edge(edge) => {
+            let mut parent = path.clone();
+            parent.segments.pop();
+            for id in [edge.source.as_str(), edge.target.as_str()] {
+                nodes
+                    .entry(id)
+                    .or_insert_with(|| parent.child(PathSegment::Node(id.to_string())));
+            }
+        }
+        _ => {}
+    });
+
+    let effective_attributes = graph.all_effective_node_attributes();
+    for (id, path) in nodes {
+        let attributes = match effective_attributes.get(id) {
+            Some(attributes) => attributes,
+            None => continue,
+        };
+        let styles = match attributes.get("style").map(NodeStyleList::try_from) {
+            Some(Ok(styles)) => styles,
+            _ => continue,
+        };
+        let shape = attributes
+            .get("shape")
+            .map_or(Ok(Shape::Ellipse), Shape::try_from);
+        let shape = match shape {
+            Ok(shape) => shape,
+            Err(_) => continue,
+        };
+        if styles.contains(&NodeStyle::Wedged) && !shape.is_elliptical() {
+            errors.push(style_error(
+                &path,
+                attributes,
+                format!(
+                    "Wedged style on node {} is only valid for elliptical shapes",
+                    id
+                ),
+            ));
+        }
+        if styles.contains(&NodeStyle::Striped) && !shape.is_rectangular() {
+            errors.push(style_error(
+                &path,
+                attributes,
+                format!(
+                    "Striped style on node {} is only valid for rectangular shapes",
+                    id
+                ),
+            ));
+        }
+    }
+    errors.extend(sub_graph_errors);
+    errors
+}
+
+fn style_error(
+    path: &ElementPath,
+    attributes: &IndexMap<AttributeName, AttributeText>,
+    message: String,
+) -> ValidationError {
+    let mut error = ValidationError::new(ErrorCode::InvalidStyle, "style", message)
+        .with_path(path.clone());
+    error.value = attributes
+        .get("style")
+        .map(|style| style.as_str().to_string());
+    error
+}
+
+/// Reports structural problems of the graph:
+/// - edges referencing undeclared nodes, according to `undeclared_nodes`
+/// - nodes declared more than once with conflicting attributes
+/// - subgraphs sharing an id
+/// - lhead and ltail not naming a cluster containing the edge's endpoint,
+///   or used without compound=true
+/// - ports not defined by record or HTML labels
+/// - parallel edges in a strict graph
+pub fn validate_structure(
+    graph: &Graph,
+    undeclared_nodes: UndeclaredNodePolicy,
+) -> Vec<ValidationError> {
+    let mut nodes: IndexMap<&str, Vec<&Node>> = IndexMap::new();
+    let mut sub_graph_ids: Vec<&str> = Vec::new();
+    let mut edges: Vec<(ElementPath, &Edge)> = Vec::new();
+    let mut errors = Vec::new();
+    walk(graph, &mut |path, element| match element {
+        Element::SubGraph(sub_graph) => {
+            if let Some(id) = sub_graph.id.as_deref() {
+                if sub_graph_ids.contains(&id) {
+                    errors.push(
+                        ValidationError::new(
+                            ErrorCode::DuplicateSubGraph,
+                            "id",
+                            format!("Subgraph id {} is used more than once", id),
+                        )
+                        .with_path(path.clone())
+                        .with_value(id),
+                    );
+                }
+                sub_graph_ids.push(id);
+            }
+        }
+        Element::Node(node) => {
+            let declarations = nodes.entry(node.id.as_str()).or_default();
+            for declaration in declarations.iter() {
+                for (key, value) in &node.attributes {
+                    if declaration.attributes.get(key).is_some_and(|v| v != value) {
+                        errors.push(
+                            ValidationError::new(
+                                ErrorCode::ConflictingNodeAttributes,
+                                key.to_string(),
+                                format!(
+                                    "Conflicts with an earlier declaration of node {}",
+                                    node.id.as_str()
+                                ),
+                            )
+                            .with_severity(Severity::Warning)
+                            .with_path(path.clone())
+                            .with_value(value.as_str()),
+                        );
+                    }
+                }
+            }
+            declarations.push(node);
+        }
+        Element::Edge(edge) => edges.push((path.clone(), edge)),
+        _ => {}
+    });
+
+    let compound = graph
+        .graph_attributes
+        .get(&AttributeName::Compound)
+        .and_then(|compound| bool::try_from(compound).ok())
+        .unwrap_or(false);
+    let mut reported_compound = false;
+    // Only resolved once an edge has a port to check
+    let mut effective_attributes = None;
+    for (i, (path, edge)) in edges.iter().enumerate() {
+        let endpoints = [
+            (
+                "source",
+                &edge.source,
+                edge.source_port_position
+                    .clone()
+                    .or_else(|| edge.tail_port()),
+            ),
+            (
+                "target",
+                &edge.target,
+                edge.target_port_position
+                    .clone()
+                    .or_else(|| edge.head_port()),
+            ),
+        ];
+        for (field, id, port) in &endpoints {
+            let declarations = nodes.get(id.as_str());
+            if declarations.is_none()
+                && undeclared_nodes != UndeclaredNodePolicy::Implicit
+            {
+                let severity = match undeclared_nodes {
+                    UndeclaredNodePolicy::Error => Severity::Error,
+                    _ => Severity::Warning,
+                };
+                errors.push(
+                    ValidationError::new(
+                        ErrorCode::UndeclaredNode,
+                        *field,
+                        format!("Node {} is not declared", id),
+                    )
+                    .with_severity(severity)
+                    .with_path(path.clone())
+                    .with_value(id.as_str()),
+                );
+            }
+            if let Some(PortPosition::Port { port_name, .. }) = port {
+                let attributes = effective_attributes
+                    .get_or_insert_with(|| graph.all_effective_node_attributes())
+                    .get(id.as_str());
+                if let Some(error) =
+                    attributes.and_then(|a| check_port(a, id, port_name))
+                {
+                    errors.push(error.with_path(path.clone()));
+                }
+            }
+        }
+
+        for (key, endpoint) in &[
+            (AttributeName::LHead, &edge.target),
+            (AttributeName::LTail, &edge.source),
+        ] {
+            let cluster = match edge.attributes.get(key) {
+                Some(cluster) => cluster.as_str(),
+                None => continue,
+            };
+            let error = |code, message: String| {
+                ValidationError::new(code, key.to_string(), message)
+                    .with_path(path.clone())
+                    .with_value(cluster)
+            };
+            if !compound && !reported_compound {
+                reported_compound = true;
+                errors.push(error(
+                    ErrorCode::MissingCompound,
+                    String::from("Requires compound=true on the graph"),
+                ));
+            }
+            match find_sub_graph(&graph.sub_graphs, cluster) {
+                Some(sub_graph)
+                    if AttributeContext::for_sub_graph(sub_graph.id.as_deref())
+                        == AttributeContext::Cluster =>
+                {
+                    if !sub_graph_contains(sub_graph, endpoint) {
+                        errors.push(error(
+                            ErrorCode::InvalidClusterReference,
+                            format!(
+                                "Cluster {} does not contain node {}",
+                                cluster, endpoint
+                            ),
+                        ));
+                    }
+                }
+                _ => errors.push(error(
+                    ErrorCode::InvalidClusterReference,
+                    format!("{} is not a cluster", cluster),
+                )),
+            }
+        }
+
+        if graph.strict {
+            let parallel = edges[..i].iter().any(|(_, other)| {
+                (other.source == edge.source && other.target == edge.target)
+                    || (!graph.is_directed
+                        && other.source == edge.target
+                        && other.target == edge.source)
+            });
+            if parallel {
+                errors.push(
+                    ValidationError::new(
+                        ErrorCode::ParallelEdge,
+                        "edge",
+                        "Parallel edges are merged in a strict graph",
+                    )
+                    .with_severity(Severity::Warning)
+                    .with_path(path.clone()),
+                );
+            }
+        }
+    }
+    errors
+}
+
+/// Checks that a node with a record shape or HTML label defines the port.
+/// Ports of other nodes are not checked.
+fn check_port(
+    attributes: &IndexMap<AttributeName, AttributeText>,
+    id: &str,
+    port_name: &str,
+) -> Option<ValidationError> {
+    let is_record = attributes
+        .get(&AttributeName::Shape)
+        .is_some_and(|shape| shape.as_str() == "record" || shape.as_str() == "Mrecord");
+    let label = attributes.get(&AttributeName::Label);
+    let defined = match label {
+        Some(AttributeText::HtmlStr(html)) => {
+            html_ports(html).any(|port| port.eq_ignore_ascii_case(port_name))
+        }
+        _ if is_record => label
+            .map_or(id, |label| label.as_str())
+            .split(['|', '{', '}'])
+            .filter_map(|field| {
+                let field = field.trim_start();
+                let end = field.strip_prefix('<')?.find('>')?;
+                Some(field[1..=end].trim())
+            })
+            .any(|port| port == port_name),
+        _ => return None,
+    };
+    if defined {
+        return None;
+    }
+    Some(
+        ValidationError::new(
+            ErrorCode::UnknownPort,
+            "port",
+            format!("Port {} is not defined by node {}", port_name, id),
+        )
+        .with_value(port_name),
+    )
+}
+
+/// Values of the `port` attributes of the elements of an HTML label.
+/// Attribute names are case-insensitive and may have whitespace around the `=`.
+fn html_ports(html: &str) -> impl Iterator<Item = &str> {
+    let lower = html.to_ascii_lowercase();
+    let mut ports = Vec::new();
+    let mut start = 0;
+    while let Some(found) = lower[start..].find("port") {
+        let at = start + found;
+        start = at + "port".len();
+        if !lower[..at].ends_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let value = match html[start..].trim_start().strip_prefix('=') {
+            Some(value) => value.trim_start(),
+            None => continue,
+        };
+        let quote = match value.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => continue,
+        };
+        if let Some(end) = value[1..].find(quote) {
+            ports.push(&value[1..=end]);
+        }
+    }
+    ports.into_iter()
+}
+
+fn find_sub_graph<'g, 'a>(
+    sub_graphs: &'g [SubGraph<'a>],
+    id: &str,
+) -> Option<&'g SubGraph<'a>> {
+    sub_graphs.iter().find_map(|sub_graph| {
+        if sub_graph.id.as_deref() == Some(id) {
+            Some(sub_graph)
+        } else {
+            find_sub_graph(&sub_graph.sub_graphs, id)
+        }
+    })
+}
+
+/// Whether the node appears within the subgraph or any of its subgraphs,
+/// either in a node statement or as an edge endpoint.
+fn sub_graph_contains(sub_graph: &SubGraph, id: &str) -> bool {
+    sub_graph.nodes.iter().any(|node| node.id.as_str() == id)
+        || sub_graph
+            .edges
+            .iter()
+            .any(|edge| edge.source == id || edge.target == id)
+        || sub_graph
+            .sub_graphs
+            .iter()
+            .any(|sub_graph| sub_graph_contains(sub_graph, id))
+}
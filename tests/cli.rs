@@ -0,0 +1,118 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn dotavious(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dotavious"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Writes `contents` to a file in the temporary directory, named after the test.
+fn dot_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "dotavious-cli-{}-{}.dot",
+        std::process::id(),
+        name
+    ));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn fmt_formats_in_place() {
+    let path = dot_file("fmt", "digraph {a->b}");
+    let file = path.to_str().unwrap();
+
+    let output = dotavious(&["fmt", "--check", file], "");
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        format!("{}: not formatted\n", file),
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    assert_eq!(Some(0), dotavious(&["fmt", file], "").status.code());
+    assert_eq!(
+        "digraph {\n    a -> b;\n}\n",
+        fs::read_to_string(&path).unwrap()
+    );
+    assert_eq!(
+        Some(0),
+        dotavious(&["fmt", "--check", file], "").status.code()
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn fmt_refuses_to_change_meaning() {
+    for (name, source) in &[
+        (
+            "defaults",
+            "digraph { node [color=red]; x; node [color=blue]; y }\n",
+        ),
+        ("comments", "digraph { a -> b; /* note */ }\n"),
+    ] {
+        let path = dot_file(name, source);
+        let file = path.to_str().unwrap();
+
+        let output = dotavious(&["fmt", file], "");
+        assert_eq!(Some(1), output.status.code(), "{}", source);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be formatted"));
+        assert_eq!(*source, fs::read_to_string(&path).unwrap());
+
+        let output = dotavious(&["fmt", "--check", file], "");
+        assert_eq!(Some(1), output.status.code(), "{}", source);
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn convert_quotes_keyword_ids() {
+    let output = dotavious(
+        &["convert"],
+        "graph { \"node\" -- b; subgraph \"Edge\" { c } }",
+    );
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!(
+        "graph {\n    subgraph \"Edge\" {\n        c;\n    }\n\n    \"node\" -- b;\n}\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn exit_codes() {
+    assert_eq!(
+        Some(0),
+        dotavious(&["check"], "digraph { a -> b }").status.code()
+    );
+    assert_eq!(
+        Some(1),
+        dotavious(&["check"], "digraph { a [penwidth=-1] }")
+            .status
+            .code()
+    );
+    assert_eq!(
+        Some(1),
+        dotavious(&["stats"], "digraph { a -> }").status.code()
+    );
+    assert_eq!(Some(2), dotavious(&["unknown"], "").status.code());
+    assert_eq!(
+        Some(2),
+        dotavious(&["convert", "--to", "svg"], "digraph {}")
+            .status
+            .code()
+    );
+}
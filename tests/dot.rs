@@ -1,12 +1,31 @@
+
+use dotavious::algo;
 use dotavious::attributes::{
-    AttributeText, AttributeType, Color, CompassPoint, EdgeAttributes, EdgeStyle,
-    GraphAttributeStatementBuilder, GraphAttributes, GraphStyle, NodeAttributes,
-    NodeStyle, PortPosition, RankDir, Shape,
+    AttributeName, AttributeText, AttributeType, Color, CompassPoint, EdgeAttributes,
+    EdgeStyle, EscString, GraphAttributeStatementBuilder, GraphAttributes, GraphStyle, GraphStyleList,
+    LayerRange, Layers, LayoutEngine, Mode, Model, NodeAttributes, NodeStyle,
+    NodeStyleList, Overlap, PortPosition, RankDir, Shape, StartType, ViewPort,
+};
+use dotavious::diff::{diff, visual_diff, AttributeChange};
+use dotavious::index::{Direction, IndexedNode};
+use dotavious::merge::MergePolicy;
+use dotavious::lint::{Diagnostic, FontAllowList, Lint, LintLevel, Linter, LongLabel};
+use dotavious::parse::{parse, parse_document};
+use dotavious::mutation::{
+    AttributeMerge, ConflictPolicy, EdgeMergeStrategy, MutationError,
+};
+use dotavious::stylesheet::{used_classes, Stylesheet};
+use dotavious::theme::Theme;
+use dotavious::validation::{
+    validate_attributes, validate_layout_engine, ElementPath, ErrorCode, PathSegment, Severity,
+    UndeclaredNodePolicy,
 };
 use dotavious::{
-    Dot, Edge, EdgeAttributeStatementBuilder, EdgeBuilder, Graph, GraphBuilder, Node,
-    NodeAttributeStatementBuilder, NodeBuilder, SubGraphBuilder,
+    dot, Dot, Edge, EdgeAttributeStatementBuilder, EdgeBuilder, Graph, GraphBuilder,
+    Node, NodeAttributeStatementBuilder, NodeBuilder, SubGraphBuilder,
 };
+use indexmap::IndexMap;
+use std::convert::TryFrom;
 use std::io;
 use std::io::Read;
 
@@ -257,6 +276,74 @@ fn builder_support_shape() {
     );
 }
 
+#[test]
+fn attribute_and_shape_names() {
+    let g = GraphBuilder::new_named_directed("names")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .force_label(true)
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N0")
+                .url("https://graphviz.org".to_string())
+                .peripheries(2)
+                .shape(Shape::TripleOctagon)
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N1")
+                .shape(Shape::Assembly)
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N2")
+                .shape(Shape::Rpromoter)
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N3")
+                .shape(Shape::Lpromoter)
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("N0", "N1")
+                .edge_url("edge.html".to_string())
+                .label_url("label.html".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let r = test_input(g);
+    assert_eq!(
+        r.unwrap(),
+        r#"digraph names {
+    graph [forcelabels=true];
+    N0 [URL="https://graphviz.org", peripheries=2, shape=tripleoctagon];
+    N1 [shape=assembly];
+    N2 [shape=rpromoter];
+    N3 [shape=lpromoter];
+    N0 -> N1 [edgeURL="edge.html", labelURL="label.html"];
+}
+"#
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn misspelled_shape_names_are_deprecated_aliases() {
+    assert_eq!(Shape::Assembly, Shape::Assemply);
+    assert_eq!(Shape::Rpromoter, Shape::Rpromotor);
+    assert_eq!(Shape::Lpromoter, Shape::Lpromotor);
+}
+
 #[test]
 fn single_edge() {
     let g = GraphBuilder::new_named_directed("single_edge")
@@ -371,7 +458,34 @@ fn edge_statement_port_position() {
         r#"digraph edge_statement_port_position {
     N0 [shape=record, label="a|<port0>b"];
     N1 [shape=record, label="e|<port1>f"];
-    "N0:port0:sw" -> "N1:port1:ne";
+    N0:port0:sw -> N1:port1:ne;
+}
+"#
+    );
+}
+
+#[test]
+fn edge_port_position_with_quoted_id() {
+    let edge = EdgeBuilder::new("node 0", "N1")
+        .source_port_position(PortPosition::Compass(CompassPoint::E))
+        .build()
+        .unwrap();
+
+    let g = GraphBuilder::new_named_directed("quoted_port")
+        .add_node(Node::new("node 0"))
+        .add_node(Node::new("N1"))
+        .add_edge(edge)
+        .build()
+        .unwrap();
+
+    let r = test_input(g);
+
+    assert_eq!(
+        r.unwrap(),
+        r#"digraph quoted_port {
+    "node 0";
+    N1;
+    "node 0":e -> N1;
 }
 "#
     );
@@ -653,10 +767,10 @@ fn edge_validation_error() {
 
     let validation_errors = edge_builder.unwrap_err();
     assert_eq!(1, validation_errors.len());
-    assert_eq!("arrowsize", validation_errors.get(0).unwrap().field);
+    assert_eq!("arrowsize", validation_errors.first().unwrap().field);
     assert_eq!(
         "Must be greater than or equal to 0",
-        validation_errors.get(0).unwrap().message
+        validation_errors.first().unwrap().message
     );
 }
 
@@ -679,10 +793,10 @@ fn edge_attributes_validation_error() {
 
     let validation_errors = edge_builder.unwrap_err();
     assert_eq!(1, validation_errors.len());
-    assert_eq!("arrowsize", validation_errors.get(0).unwrap().field);
+    assert_eq!("arrowsize", validation_errors.first().unwrap().field);
     assert_eq!(
         "Must be greater than or equal to 0",
-        validation_errors.get(0).unwrap().message
+        validation_errors.first().unwrap().message
     );
 }
 
@@ -703,10 +817,10 @@ fn node_validation_error() {
 
     let validation_errors = node_builder.unwrap_err();
     assert_eq!(1, validation_errors.len());
-    assert_eq!("height", validation_errors.get(0).unwrap().field);
+    assert_eq!("height", validation_errors.first().unwrap().field);
     assert_eq!(
         "Must be greater than or equal to 0.02",
-        validation_errors.get(0).unwrap().message
+        validation_errors.first().unwrap().message
     );
 }
 
@@ -727,10 +841,10 @@ fn node_attribute_validation_error() {
 
     let validation_errors = node_builder.unwrap_err();
     assert_eq!(1, validation_errors.len());
-    assert_eq!("height", validation_errors.get(0).unwrap().field);
+    assert_eq!("height", validation_errors.first().unwrap().field);
     assert_eq!(
         "Must be greater than or equal to 0.02",
-        validation_errors.get(0).unwrap().message
+        validation_errors.first().unwrap().message
     );
 }
 
@@ -751,10 +865,10 @@ fn graph_attributes_validation_error() {
 
     let validation_errors = graph_builder.unwrap_err();
     assert_eq!(1, validation_errors.len());
-    assert_eq!("fontsize", validation_errors.get(0).unwrap().field);
+    assert_eq!("fontsize", validation_errors.first().unwrap().field);
     assert_eq!(
         "Must be greater than or equal to 1.0",
-        validation_errors.get(0).unwrap().message
+        validation_errors.first().unwrap().message
     );
 }
 
@@ -766,3 +880,2560 @@ fn graph_attributes_build_ignore_validation_error() {
 
     assert!(graph.contains_key("fontsize"))
 }
+
+#[test]
+fn engine_specific_graph_attributes() {
+    let g = GraphBuilder::new_named_undirected("engine_attributes")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .layout(LayoutEngine::Neato)
+                .overlap(Overlap::Prism(Some(100)))
+                .mode(Mode::KK)
+                .model(Model::Subset)
+                .start(StartType::Random(Some(5)))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("N0", "N1")
+                .len(2.0)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let r = test_input(g);
+
+    assert_eq!(
+        r.unwrap(),
+        r#"graph engine_attributes {
+    graph [layout=neato, overlap=prism100, mode=KK, model=subset, start=random5];
+    N0 -- N1 [len=2];
+}
+"#
+    );
+}
+
+#[test]
+fn engine_specific_attribute_validation_error() {
+    let graph_builder = GraphAttributeStatementBuilder::new()
+        .dim(1)
+        .spring_constant(-1.0)
+        .build();
+
+    assert!(graph_builder.is_err());
+
+    let validation_errors = graph_builder.unwrap_err();
+    assert_eq!(2, validation_errors.len());
+    assert_eq!("dim", validation_errors.first().unwrap().field);
+    assert_eq!("K", validation_errors.get(1).unwrap().field);
+}
+
+#[test]
+fn validate_layout_engine_reports_no_op_attributes() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .overlap(Overlap::Scale)
+                .rank_dir(RankDir::LeftRight)
+                .build()
+                .unwrap(),
+        )
+        .add_edge(EdgeBuilder::new("a", "b").len(2.0).build().unwrap())
+        .build()
+        .unwrap();
+
+    let errors = validate_layout_engine(&g);
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_ref()).collect();
+    assert_eq!(vec!["overlap", "len"], fields);
+    assert_eq!(
+        "Has no effect with the dot layout engine",
+        errors.first().unwrap().message
+    );
+}
+
+#[test]
+fn validate_layout_engine_uses_layout_attribute() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .layout(LayoutEngine::Neato)
+                .overlap(Overlap::Scale)
+                .rank_dir(RankDir::LeftRight)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let errors = validate_layout_engine(&g);
+    assert_eq!(1, errors.len());
+    assert_eq!("rankdir", errors.first().unwrap().field);
+}
+
+#[test]
+fn unknown_attribute_suggests_known_name() {
+    let node = NodeBuilder::new("N0")
+        .add_attribute("fillcolour", AttributeText::quoted("red"))
+        .build()
+        .unwrap();
+    let g = GraphBuilder::new_directed().add_node(node).build().unwrap();
+
+    let warnings = validate_attributes(&g);
+    assert_eq!(1, warnings.len());
+    assert_eq!(Severity::Warning, warnings[0].severity);
+    assert_eq!("fillcolour", warnings[0].field);
+    assert_eq!(
+        "Unknown attribute. Did you mean \"fillcolor\"?",
+        warnings[0].message
+    );
+}
+
+#[test]
+fn unknown_graph_attribute_is_a_warning() {
+    let (_, warnings) = GraphBuilder::new_directed()
+        .add_attribute(AttributeType::Graph, "foo", AttributeText::attr("bar"))
+        .build_with_warnings()
+        .unwrap();
+
+    assert_eq!(1, warnings.len());
+    assert_eq!(ErrorCode::UnknownAttribute, warnings[0].code);
+    assert_eq!("Unknown attribute", warnings[0].message);
+}
+
+#[test]
+fn unknown_node_and_edge_attributes_are_warnings() {
+    let (_, warnings) = GraphBuilder::new_directed()
+        .add_node(
+            NodeBuilder::new("a")
+                .add_attribute("fillcolour", AttributeText::attr("red"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("a", "b")
+                .add_attribute("colour", AttributeText::attr("red"))
+                .build()
+                .unwrap(),
+        )
+        .build_with_warnings()
+        .unwrap();
+
+    let fields: Vec<&str> = warnings.iter().map(|w| w.field.as_ref()).collect();
+    assert_eq!(vec!["fillcolour", "colour"], fields);
+    assert!(warnings.iter().all(|w| w.code == ErrorCode::UnknownAttribute));
+    assert_eq!("graph > node a", warnings[0].path.to_string());
+}
+
+#[test]
+fn node_attribute_on_edge_validation_error() {
+    let edge = EdgeBuilder::new("N0", "N1")
+        .add_attribute("shape", AttributeText::from(Shape::Box))
+        .build();
+
+    let validation_errors = edge.unwrap_err();
+    assert_eq!(1, validation_errors.len());
+    assert_eq!("shape", validation_errors.first().unwrap().field);
+    assert_eq!(
+        "Not a valid edge attribute",
+        validation_errors.first().unwrap().message
+    );
+}
+
+#[test]
+fn attribute_value_type_validation_error() {
+    let mut g = GraphBuilder::new_named_directed("G");
+    g.add_attribute(AttributeType::Node, "width", AttributeText::attr("wide"))
+        .add_attribute(
+            AttributeType::Edge,
+            "arrowhead",
+            AttributeText::attr("lteeoldiamond"),
+        )
+        .add_attribute(AttributeType::Graph, "rankdir", AttributeText::attr("LR"));
+
+    let validation_errors = g.build().unwrap_err();
+    assert_eq!(1, validation_errors.len());
+    assert_eq!("width", validation_errors.first().unwrap().field);
+    assert_eq!(
+        "Must be a value of type double",
+        validation_errors.first().unwrap().message
+    );
+}
+
+#[test]
+fn cluster_only_attribute_validation() {
+    let cluster = SubGraphBuilder::new_named("cluster_0")
+        .add_attribute(AttributeType::Graph, "pencolor".to_string(), "red".into())
+        .build();
+    assert!(cluster.is_ok());
+
+    let sub_graph = SubGraphBuilder::new_named("sub")
+        .add_attribute(AttributeType::Graph, "pencolor".to_string(), "red".into())
+        .build();
+    assert_eq!(
+        "Not a valid subgraph attribute",
+        sub_graph.unwrap_err().first().unwrap().message
+    );
+}
+
+#[test]
+fn typed_attribute_getters() {
+    let node = NodeBuilder::new("N0")
+        .shape(Shape::Box)
+        .add_attribute("custom", AttributeText::quoted("value"))
+        .build()
+        .unwrap();
+    assert!(GraphBuilder::new_directed()
+        .add_node(node.clone())
+        .build()
+        .is_ok());
+
+    assert_eq!(
+        Some(&AttributeText::from(Shape::Box)),
+        node.get(AttributeName::Shape)
+    );
+    assert_eq!(
+        Some(&AttributeText::quoted("value")),
+        node.get(AttributeName::Other("custom".to_string()))
+    );
+    assert_eq!(None, node.get(AttributeName::Color));
+
+    let edge = EdgeBuilder::new("N0", "N1").arrow_size(2.0).build().unwrap();
+    assert_eq!(Some(&AttributeText::from(2.0)), edge.get(AttributeName::ArrowSize));
+    assert_eq!(Some(&AttributeText::from(2.0)), edge.get("arrowsize"));
+}
+
+#[test]
+fn typed_attribute_read_back() {
+    let node = NodeBuilder::new("N0")
+        .shape(Shape::Mdiamond)
+        .fill_color(Color::RGB {
+            red: 160,
+            green: 82,
+            blue: 45,
+        })
+        .width(1.5)
+        .build()
+        .unwrap();
+
+    assert_eq!(Some(Shape::Mdiamond), node.shape());
+    assert_eq!(
+        Some(Color::RGB {
+            red: 160,
+            green: 82,
+            blue: 45
+        }),
+        node.fill_color()
+    );
+    assert_eq!(Some(1.5), node.width());
+    assert_eq!(None, node.height());
+
+    let edge = EdgeBuilder::new("N0", "N1")
+        .pen_width(2.0)
+        .head_port(PortPosition::Compass(CompassPoint::NE))
+        .build()
+        .unwrap();
+
+    assert_eq!(Some(2.0), edge.pen_width());
+    assert_eq!(
+        Some(PortPosition::Compass(CompassPoint::NE)),
+        edge.head_port()
+    );
+
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .rank_dir(RankDir::LeftRight)
+                .overlap(Overlap::Prism(Some(10)))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(Some(RankDir::LeftRight), g.rank_dir());
+    assert_eq!(Some(Overlap::Prism(Some(10))), g.overlap());
+}
+
+#[test]
+fn typed_attribute_read_back_invalid_value() {
+    let node = NodeBuilder::new("N0")
+        .add_attribute("shape", AttributeText::attr("blob"))
+        .build_ignore_validation();
+
+    assert_eq!(None, node.shape());
+    assert!(Shape::try_from(node.get(AttributeName::Shape).unwrap()).is_err());
+}
+
+#[test]
+fn fractional_edge_weight() {
+    let edge = EdgeBuilder::new("N0", "N1").weight(2).build().unwrap();
+    assert_eq!(Some(2.0), edge.weight());
+
+    let edge = EdgeBuilder::new("N0", "N1")
+        .add_attribute("weight", AttributeText::attr("2.5"))
+        .build()
+        .unwrap();
+    assert_eq!(Some(2.5), edge.weight());
+}
+
+#[test]
+fn effective_node_attributes() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_node_attributes(
+            NodeAttributeStatementBuilder::new()
+                .shape(Shape::Box)
+                .color(Color::Named("black"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node_attributes(
+                    NodeAttributeStatementBuilder::new()
+                        .color(Color::Named("red"))
+                        .build()
+                        .unwrap(),
+                )
+                .add_node(NodeBuilder::new("a").font_size(10.0).build().unwrap())
+                .add_edge(Edge::new("a", "b"))
+                .build()
+                .unwrap(),
+        )
+        .add_node(NodeBuilder::new("a").font_size(12.0).build().unwrap())
+        .add_node(Node::new("c"))
+        .build()
+        .unwrap();
+
+    let a = g.effective_node_attributes("a").unwrap();
+    assert_eq!(Some(&AttributeText::from(Shape::Box)), a.get("shape"));
+    assert_eq!(Some(&AttributeText::from(Color::Named("red"))), a.get("color"));
+    assert_eq!(Some(&AttributeText::from(12.0)), a.get("fontsize"));
+
+    // implicit node first appearing as an edge endpoint within the cluster
+    let b = g.effective_node_attributes("b").unwrap();
+    assert_eq!(Some(&AttributeText::from(Color::Named("red"))), b.get("color"));
+    assert_eq!(None, b.get("fontsize"));
+
+    let c = g.effective_node_attributes("c").unwrap();
+    assert_eq!(Some(&AttributeText::from(Color::Named("black"))), c.get("color"));
+
+    assert_eq!(None, g.effective_node_attributes("d"));
+}
+
+#[test]
+fn effective_edge_and_graph_attributes() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .font_size(14.0)
+                .label("G")
+                .build()
+                .unwrap(),
+        )
+        .add_edge_attributes(
+            EdgeAttributeStatementBuilder::new()
+                .color(Color::Named("red"))
+                .arrow_size(2.0)
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .label("outer")
+                        .build()
+                        .unwrap(),
+                )
+                .add_sub_graph(
+                    SubGraphBuilder::new_named("cluster_1")
+                        .add_graph_attributes(
+                            GraphAttributeStatementBuilder::new()
+                                .font_size(8.0)
+                                .build()
+                                .unwrap(),
+                        )
+                        .add_edge_attributes(
+                            EdgeAttributeStatementBuilder::new()
+                                .color(Color::Named("blue"))
+                                .build()
+                                .unwrap(),
+                        )
+                        .add_edge(
+                            EdgeBuilder::new("a", "b")
+                                .pen_width(3.0)
+                                .build()
+                                .unwrap(),
+                        )
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("b", "c"))
+        .build()
+        .unwrap();
+
+    assert_eq!(Some("cluster_1"), g.sub_graph(&[0, 0]).unwrap().id.as_deref());
+    assert!(g.sub_graph(&[]).is_none());
+    assert!(g.sub_graph(&[1]).is_none());
+
+    let edge = g.effective_edge_attributes(&[0, 0], 0).unwrap();
+    assert_eq!(Some(&AttributeText::from(Color::Named("blue"))), edge.get("color"));
+    assert_eq!(Some(&AttributeText::from(2.0)), edge.get("arrowsize"));
+    assert_eq!(Some(&AttributeText::from(3.0)), edge.get("penwidth"));
+
+    let edge = g.effective_edge_attributes(&[], 0).unwrap();
+    assert_eq!(Some(&AttributeText::from(Color::Named("red"))), edge.get("color"));
+    assert_eq!(None, g.effective_edge_attributes(&[], 1));
+    assert_eq!(None, g.effective_edge_attributes(&[0, 1], 0));
+
+    let graph = g.effective_graph_attributes(&[0, 0]).unwrap();
+    assert_eq!(Some(&AttributeText::from(8.0)), graph.get("fontsize"));
+    assert_eq!(Some(&AttributeText::quoted("outer")), graph.get("label"));
+    assert_eq!(None, g.effective_graph_attributes(&[2]));
+}
+
+#[test]
+fn layers() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .layers(Layers::new(vec!["ops", "dev", "sec"]))
+                .layer_select(LayerRange::range("ops", "dev"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .layer(LayerRange::all())
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N0")
+                .layer(LayerRange::list(vec!["ops", "sec"]))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("N0", "N1")
+                .layer(LayerRange::layer(2))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let r = test_input(g);
+    assert_eq!(
+        r.unwrap(),
+        r#"digraph G {
+    graph [layers="ops:dev:sec", layerselect="ops:dev"];
+    subgraph cluster_0 {
+        graph [layer="all"];
+    }
+
+    N0 [layer="ops,sec"];
+    N0 -> N1 [layer="2"];
+}
+"#
+    );
+}
+
+#[test]
+fn layers_with_custom_separators() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .layers(Layers::new(vec!["a:1", "b"]).with_separator("|"))
+                .layer_list_sep(";".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N0")
+                .layer(LayerRange::list(vec!["a:1", "b"]).with_separators("|", ";"))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let r = test_input(g);
+    assert_eq!(
+        r.unwrap(),
+        r#"digraph G {
+    graph [layersep="|", layers="a:1|b", layerlistsep=";"];
+    N0 [layer="a:1;b"];
+}
+"#
+    );
+}
+
+#[test]
+fn undefined_layer_validation_error() {
+    let result = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .layers(Layers::new(vec!["ops", "dev"]))
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N0")
+                .layer(LayerRange::range("ops", 3))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("N0", "N1")
+                .layer(LayerRange::layer("qa"))
+                .build()
+                .unwrap(),
+        )
+        .build();
+
+    let errors = result.unwrap_err();
+    assert_eq!(2, errors.len());
+    assert_eq!("layer", errors[0].field);
+    assert_eq!(
+        "Layer \"3\" is not defined in the graph's layers",
+        errors[0].message
+    );
+    assert_eq!(
+        "Layer \"qa\" is not defined in the graph's layers",
+        errors[1].message
+    );
+}
+
+#[test]
+fn undefined_layer_in_default_statements() {
+    let result = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .layers(Layers::new(vec!["ops", "dev"]))
+                .build()
+                .unwrap(),
+        )
+        .add_node_attributes(
+            NodeAttributeStatementBuilder::new()
+                .layer(LayerRange::layer("missing"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_edge_attributes(
+                    EdgeAttributeStatementBuilder::new()
+                        .layer(LayerRange::layer("qa"))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .build();
+
+    let errors = result.unwrap_err();
+    assert_eq!(2, errors.len());
+    assert_eq!("graph G", errors[0].path.to_string());
+    assert_eq!(Some("missing".to_string()), errors[0].value);
+    assert_eq!("graph G > subgraph cluster_0", errors[1].path.to_string());
+    assert_eq!(Some("qa".to_string()), errors[1].value);
+}
+
+#[test]
+fn style_lists() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .styles(GraphStyleList::from(vec![
+                            GraphStyle::Striped,
+                            GraphStyle::Rounded,
+                        ]))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N0")
+                .shape(Shape::Box)
+                .styles(
+                    NodeStyleList::new()
+                        .style(NodeStyle::Striped)
+                        .style(NodeStyle::Dashed)
+                        .set_line_width(2.0),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("N0", "N1")
+                .style(EdgeStyle::Invisible)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Some(
+            NodeStyleList::new()
+                .style(NodeStyle::Striped)
+                .style(NodeStyle::Dashed)
+                .set_line_width(2.0)
+        ),
+        g.nodes[0].styles()
+    );
+
+    let r = test_input(g);
+    assert_eq!(
+        r.unwrap(),
+        r#"digraph G {
+    subgraph cluster_0 {
+        graph [style="striped,rounded"];
+    }
+
+    N0 [shape=box, style="striped,dashed,setlinewidth(2)"];
+    N0 -> N1 [style=invis];
+}
+"#
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn misspelled_style_names_are_deprecated_aliases() {
+    assert_eq!(NodeStyle::Striped, NodeStyle::Stripped);
+    assert_eq!(NodeStyle::Radial, NodeStyle::Radical);
+    assert_eq!(GraphStyle::Radial, GraphStyle::Radical);
+}
+
+#[test]
+fn style_combination_validation_errors() {
+    let result = GraphBuilder::new_named_directed("G")
+        .add_node_attributes(
+            NodeAttributeStatementBuilder::new()
+                .shape(Shape::Box)
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("group")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .style(GraphStyle::Striped)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N0")
+                .styles(NodeStyleList::from(vec![NodeStyle::Filled, NodeStyle::Wedged]))
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N1")
+                .shape(Shape::Circle)
+                .styles(NodeStyleList::from(vec![NodeStyle::Striped]))
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N2")
+                .style(NodeStyle::Striped)
+                .build()
+                .unwrap(),
+        )
+        .build();
+
+    let errors = result.unwrap_err();
+    assert_eq!(
+        vec![
+            "Wedged style on node N0 is only valid for elliptical shapes",
+            "Striped style on node N1 is only valid for rectangular shapes",
+            "Striped style on subgraph group is only valid for clusters",
+        ],
+        errors.iter().map(|e| e.message.as_ref()).collect::<Vec<_>>()
+    );
+    assert!(errors.iter().all(|e| e.field == "style"));
+}
+
+#[test]
+fn esc_string_substitutions() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_node(
+            NodeBuilder::new("N0")
+                .add_attribute(
+                    AttributeName::Label,
+                    EscString::new()
+                        .node_name()
+                        .text(" in ")
+                        .graph_name()
+                        .line_left()
+                        .into(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("N0", "N1")
+                .add_attribute(
+                    AttributeName::Tooltip,
+                    EscString::new()
+                        .tail_name()
+                        .text(" \\ ")
+                        .head_name()
+                        .into(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let r = test_input(g);
+    assert_eq!(
+        r.unwrap(),
+        r#"digraph G {
+    N0 [label="\N in \G\l"];
+    N0 -> N1 [tooltip="\T \\ \H"];
+}
+"#
+    );
+}
+
+#[test]
+fn esc_string_substitution_validation_error() {
+    let result = NodeBuilder::new("N0")
+        .add_attribute(
+            AttributeName::Tooltip,
+            EscString::new().tail_name().text(" to ").head_name().into(),
+        )
+        .build();
+
+    let errors = result.unwrap_err();
+    assert_eq!("tooltip", errors[0].field);
+    assert_eq!("\\T is only valid for edges", errors[0].message);
+
+    let result = EdgeBuilder::new("N0", "N1")
+        .add_attribute(AttributeName::Label, EscString::new().label().into())
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn validation_error_location_code_and_value() {
+    let errors = NodeBuilder::new("N0").height(0.01).build().unwrap_err();
+    let error = errors.first().unwrap();
+    assert_eq!(ErrorCode::OutOfRange, error.code);
+    assert_eq!(Severity::Error, error.severity);
+    assert_eq!(Some("0.01".to_string()), error.value);
+    assert_eq!(
+        ElementPath::new(PathSegment::Node("N0".to_string())),
+        error.path
+    );
+    assert_eq!(
+        "error[out-of-range]: node N0: height = \"0.01\": Must be greater than or equal to 0.02",
+        error.to_string()
+    );
+
+    let errors = EdgeBuilder::new("N0", "N1")
+        .add_attribute("shape", AttributeText::attr("box"))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        "error[invalid-context]: edge N0 -> N1: shape = \"box\": Not a valid edge attribute",
+        errors.first().unwrap().to_string()
+    );
+}
+
+#[test]
+fn graph_validation_error_paths() {
+    let errors = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_edge(Edge::new("a", "b"))
+                .add_edge(
+                    EdgeBuilder::new("b", "c")
+                        .layer(LayerRange::layer("qa"))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap_err();
+
+    assert_eq!(1, errors.len());
+    assert_eq!(ErrorCode::UndefinedLayer, errors[0].code);
+    assert_eq!(
+        "graph G > subgraph cluster_0 > edge 1 (b -> c)",
+        errors[0].path.to_string()
+    );
+}
+
+#[test]
+fn build_with_warnings() {
+    let (g, warnings) = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .overlap(Overlap::Scale)
+                .build()
+                .unwrap(),
+        )
+        .add_edge(EdgeBuilder::new("a", "b").len(2.0).build().unwrap())
+        .build_with_warnings()
+        .unwrap();
+
+    assert_eq!(1, g.edges.len());
+    assert_eq!(2, warnings.len());
+    assert!(warnings.iter().all(|w| w.severity == Severity::Warning));
+    assert!(warnings
+        .iter()
+        .all(|w| w.code == ErrorCode::UnsupportedByEngine));
+    assert_eq!("graph G > edge 0 (a -> b)", warnings[1].path.to_string());
+    assert_eq!(Some("2".to_string()), warnings[1].value);
+}
+
+#[test]
+fn undeclared_node_policy() {
+    let builder = || {
+        let mut builder = GraphBuilder::new_named_directed("G");
+        builder
+            .add_node(Node::new("a"))
+            .add_edge(Edge::new("a", "b"));
+        builder
+    };
+
+    assert!(builder().build().is_ok());
+
+    let (_, warnings) = builder()
+        .undeclared_nodes(UndeclaredNodePolicy::Warn)
+        .build_with_warnings()
+        .unwrap();
+    assert_eq!(1, warnings.len());
+    assert_eq!(ErrorCode::UndeclaredNode, warnings[0].code);
+    assert_eq!("target", warnings[0].field);
+
+    let errors = builder()
+        .undeclared_nodes(UndeclaredNodePolicy::Error)
+        .build()
+        .unwrap_err();
+    assert_eq!(1, errors.len());
+    assert_eq!("Node b is not declared", errors[0].message);
+}
+
+#[test]
+fn duplicate_declarations_validation() {
+    let result = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(NodeBuilder::new("a").shape(Shape::Box).build().unwrap())
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(SubGraphBuilder::new_named("cluster_0").build().unwrap())
+        .add_node(NodeBuilder::new("a").shape(Shape::Circle).build().unwrap())
+        .build_with_warnings();
+
+    let errors = result.unwrap_err();
+    assert_eq!(1, errors.len());
+    assert_eq!(ErrorCode::DuplicateSubGraph, errors[0].code);
+
+    let (_, warnings) = GraphBuilder::new_named_directed("G")
+        .add_node(NodeBuilder::new("a").shape(Shape::Box).build().unwrap())
+        .add_node(NodeBuilder::new("a").shape(Shape::Circle).build().unwrap())
+        .add_node(
+            NodeBuilder::new("a")
+                .shape(Shape::Box)
+                .color(Color::Named("red"))
+                .build()
+                .unwrap(),
+        )
+        .build_with_warnings()
+        .unwrap();
+    assert_eq!(
+        vec![
+            ErrorCode::ConflictingNodeAttributes,
+            ErrorCode::ConflictingNodeAttributes
+        ],
+        warnings.iter().map(|w| w.code).collect::<Vec<_>>()
+    );
+    assert_eq!(Some("circle".to_string()), warnings[0].value);
+}
+
+#[test]
+fn cluster_reference_validation() {
+    let cluster = SubGraphBuilder::new_named("cluster_0")
+        .add_node(Node::new("a"))
+        .build()
+        .unwrap();
+    let group = SubGraphBuilder::new_named("group")
+        .add_node(Node::new("b"))
+        .build()
+        .unwrap();
+
+    let errors = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(cluster.clone())
+        .add_sub_graph(group.clone())
+        .add_edge(
+            EdgeBuilder::new("a", "b")
+                .lhead("group".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("b", "c")
+                .ltail("cluster_0".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        vec![
+            "Requires compound=true on the graph",
+            "group is not a cluster",
+            "Cluster cluster_0 does not contain node b",
+        ],
+        errors
+            .iter()
+            .map(|e| e.message.as_ref())
+            .collect::<Vec<_>>()
+    );
+
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .compound("true".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(cluster)
+        .add_sub_graph(group)
+        .add_edge(
+            EdgeBuilder::new("b", "a")
+                .lhead("cluster_0".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build();
+    assert!(g.is_ok());
+}
+
+#[test]
+fn record_port_validation() {
+    let errors = GraphBuilder::new_named_directed("G")
+        .add_node(
+            NodeBuilder::new("N0")
+                .shape(Shape::Record)
+                .label("<f0> a|{<f1> b|c}")
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("N1")
+                .add_attribute(
+                    AttributeName::Label,
+                    AttributeText::html(
+                        r#"<TABLE><TR><TD PORT="p0">x</TD></TR></TABLE>"#,
+                    ),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new_with_position(
+            "N0",
+            PortPosition::Port {
+                port_name: "f1".to_string(),
+                compass_point: None,
+            },
+            "N1",
+            PortPosition::Port {
+                port_name: "p0".to_string(),
+                compass_point: None,
+            },
+        ))
+        .add_edge(Edge::new_with_position(
+            "N0",
+            PortPosition::Port {
+                port_name: "f2".to_string(),
+                compass_point: None,
+            },
+            "N1",
+            PortPosition::Port {
+                port_name: "p1".to_string(),
+                compass_point: None,
+            },
+        ))
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        vec![
+            "Port f2 is not defined by node N0",
+            "Port p1 is not defined by node N1",
+        ],
+        errors
+            .iter()
+            .map(|e| e.message.as_ref())
+            .collect::<Vec<_>>()
+    );
+    assert!(errors.iter().all(|e| e.code == ErrorCode::UnknownPort));
+}
+
+#[test]
+fn html_port_validation_allows_spaced_attributes() {
+    let port = |name: &str| PortPosition::Port {
+        port_name: name.to_string(),
+        compass_point: None,
+    };
+    let result = GraphBuilder::new_named_directed("G")
+        .add_node(
+            NodeBuilder::new("N0")
+                .add_attribute(
+                    AttributeName::Label,
+                    AttributeText::html(
+                        r#"<TABLE><TR><TD PORT = "p0">x</TD><TD port='p1'>y</TD></TR></TABLE>"#,
+                    ),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new_with_position("N0", port("p0"), "N0", port("p1")))
+        .add_edge(Edge::new_with_position("N0", port("p0"), "N0", port("p2")))
+        .build();
+
+    let errors = result.unwrap_err();
+    assert_eq!(1, errors.len());
+    assert_eq!("Port p2 is not defined by node N0", errors[0].message);
+}
+
+#[test]
+fn strict_graph_parallel_edges() {
+    let (_, warnings) = GraphBuilder::new_named_undirected("G")
+        .strict()
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "a"))
+        .add_edge(Edge::new("a", "c"))
+        .build_with_warnings()
+        .unwrap();
+
+    assert_eq!(1, warnings.len());
+    assert_eq!(ErrorCode::ParallelEdge, warnings[0].code);
+    assert_eq!("graph G > edge 1 (b -> a)", warnings[0].path.to_string());
+}
+
+#[test]
+fn lint_unused_and_shadowed_defaults() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_node_attributes(
+            NodeAttributeStatementBuilder::new()
+                .shape(Shape::Box)
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_a")
+                .add_node_attributes(
+                    NodeAttributeStatementBuilder::new()
+                        .shape(Shape::Ellipse)
+                        .build()
+                        .unwrap(),
+                )
+                .add_node(Node::new("a"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("empty")
+                .add_node_attributes(
+                    NodeAttributeStatementBuilder::new()
+                        .color(Color::Named("red"))
+                        .build()
+                        .unwrap(),
+                )
+                .add_edge_attributes(
+                    EdgeAttributeStatementBuilder::new()
+                        .color(Color::Named("blue"))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_node(NodeBuilder::new("b").shape(Shape::Circle).build().unwrap())
+        .build()
+        .unwrap();
+
+    let diagnostics = Linter::default().lint(&g);
+    assert_eq!(
+        vec![
+            "warning[unused-defaults]: graph G > subgraph empty: Node defaults are not applied to any node",
+            "warning[unused-defaults]: graph G > subgraph empty: Edge defaults are not applied to any edge",
+            "warning[shadowed-default]: graph G: shape = \"box\": Default is overridden by every node it applies to",
+        ],
+        diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn lint_record_rankdir_and_long_label() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .rank_dir(RankDir::LeftRight)
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("r")
+                .shape(Shape::Record)
+                .label("<f0> left|<f1> right")
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("l")
+                .label("a label which goes on and on without any line break")
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("m")
+                .label("a label which goes on and on\\nbut is broken up")
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let diagnostics = Linter::default().lint(&g);
+    assert_eq!(2, diagnostics.len());
+    assert_eq!("record-rankdir", diagnostics[0].lint);
+    assert_eq!("graph G > node r", diagnostics[0].path.to_string());
+    assert_eq!(Some("record".to_string()), diagnostics[0].value);
+    assert_eq!("long-label", diagnostics[1].lint);
+    assert_eq!("graph G > node l", diagnostics[1].path.to_string());
+
+    let diagnostics = Linter::default()
+        .allow("record-rankdir")
+        .add_lint(LongLabel::new(20))
+        .lint(&g);
+    assert_eq!(
+        vec!["graph G > node l", "graph G > node m"],
+        diagnostics
+            .iter()
+            .map(|d| d.path.to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+struct NodeCount(usize);
+
+impl Lint for NodeCount {
+    fn name(&self) -> &str {
+        "node-count"
+    }
+
+    fn description(&self) -> &str {
+        "graphs with too many nodes"
+    }
+
+    fn default_level(&self) -> LintLevel {
+        LintLevel::Deny
+    }
+
+    fn check(&self, graph: &Graph) -> Vec<Diagnostic> {
+        if graph.nodes.len() <= self.0 {
+            return Vec::new();
+        }
+        vec![Diagnostic::new(
+            ElementPath::new(PathSegment::Graph(None)),
+            format!("More than {} nodes", self.0),
+        )]
+    }
+}
+
+#[test]
+fn lint_levels_and_custom_lints() {
+    let g = GraphBuilder::new_directed()
+        .add_node(
+            NodeBuilder::new("a")
+                .style(NodeStyle::Invisible)
+                .font_name("Comic Sans MS".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("b")
+                .font_name("helvetica".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(
+            EdgeBuilder::new("b", "a")
+                .style(EdgeStyle::Invisible)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let mut linter = Linter::default();
+    linter
+        .deny("invisible-node-edges")
+        .add_lint(FontAllowList::new(vec!["Helvetica", "Times-Roman"]))
+        .add_lint(NodeCount(1));
+    assert_eq!(Some(LintLevel::Deny), linter.level_of("node-count"));
+    assert_eq!(Some(LintLevel::Warn), linter.level_of("font-allow-list"));
+    assert_eq!(None, linter.level_of("unknown"));
+
+    let diagnostics = linter.lint(&g);
+    assert_eq!(
+        vec![
+            "error[invisible-node-edges]: graph > node a: Node a is invisible but has 1 visible edge",
+            "warning[font-allow-list]: graph > node a: fontname = \"Comic Sans MS\": Font is not in the allow-list",
+            "error[node-count]: graph: More than 1 nodes",
+        ],
+        diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(2, diagnostics.iter().filter(|d| d.is_denied()).count());
+}
+
+#[test]
+fn graph_index_lookups() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_sub_graph(
+                    SubGraphBuilder::new_named("cluster_1")
+                        .add_node(Node::new("c"))
+                        .build()
+                        .unwrap(),
+                )
+                .add_node(Node::new("a"))
+                .add_edge(Edge::new("a", "b"))
+                .build()
+                .unwrap(),
+        )
+        .add_node(Node::new("a"))
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "c"))
+        .add_edge(Edge::new("c", "c"))
+        .add_edge(Edge::new("d", "a"))
+        .build()
+        .unwrap();
+    let index = g.index();
+
+    fn ids<'g>(nodes: Vec<&IndexedNode<'g, '_>>) -> Vec<&'g str> {
+        nodes.iter().map(|n| n.id).collect()
+    }
+    assert_eq!(vec!["c", "a", "b", "d"], ids(index.nodes().collect()));
+    assert_eq!(vec!["c", "a", "b"], ids(index.nodes_in_sub_graph(&[0])));
+    assert_eq!(vec!["c"], ids(index.nodes_in_sub_graph(&[0, 0])));
+
+    let a = index.node("a").unwrap();
+    assert_eq!(2, a.statements.len());
+    assert_eq!(vec![vec![0], vec![]], a.sub_graphs);
+    assert!(index.node("b").unwrap().is_implicit());
+    assert!(index.node("e").is_none());
+
+    assert_eq!(vec!["b"], index.successors("a"));
+    assert_eq!(vec!["d"], index.predecessors("a"));
+    assert_eq!(vec!["b", "d"], index.neighbors("a"));
+    assert_eq!(vec!["b", "c"], index.predecessors("c"));
+    assert_eq!(2, index.out_degree("a"));
+    assert_eq!(1, index.in_degree("a"));
+    assert_eq!(2, index.in_degree("c"));
+
+    let edges = index.edges_between("a", "b");
+    assert_eq!(2, edges.len());
+    assert_eq!((vec![0], 0), (edges[0].sub_graph.clone(), edges[0].index));
+    assert_eq!((vec![], 0), (edges[1].sub_graph.clone(), edges[1].index));
+    assert!(index.edges_between("b", "a").is_empty());
+}
+
+#[test]
+fn undirected_graph_index() {
+    let g = GraphBuilder::new_undirected()
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("c", "a"))
+        .add_edge(Edge::new("a", "a"))
+        .build()
+        .unwrap();
+    let index = g.index();
+
+    assert_eq!(vec!["b", "c", "a"], index.successors("a"));
+    assert_eq!(index.successors("a"), index.predecessors("a"));
+    assert_eq!(4, index.out_degree("a"));
+    assert_eq!(4, index.in_degree("a"));
+    assert_eq!(1, index.edges_between("b", "a").len());
+}
+
+#[test]
+fn all_sub_graphs() {
+    let g = GraphBuilder::new_directed()
+        .add_sub_graph(
+            SubGraphBuilder::new_named("a")
+                .add_sub_graph(SubGraphBuilder::new_named("b").build().unwrap())
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(SubGraphBuilder::new_named("c").build().unwrap())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            (vec![0], Some("a".to_string())),
+            (vec![0, 0], Some("b".to_string())),
+            (vec![1], Some("c".to_string())),
+        ],
+        g.all_sub_graphs()
+            .into_iter()
+            .map(|(path, sub_graph)| (path, sub_graph.id.clone()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn remove_and_rename_nodes() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_graph_attributes(
+            GraphAttributeStatementBuilder::new()
+                .root("a".to_string())
+                .viewport(ViewPort::new_node(100.0, 50.0, None, "a".to_string()))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(Node::new("b"))
+                .add_edge(Edge::new("a", "b"))
+                .build()
+                .unwrap(),
+        )
+        .add_node(Node::new("a"))
+        .add_edge(Edge::new("b", "c"))
+        .add_edge(Edge::new("a", "c"))
+        .build()
+        .unwrap();
+
+    assert!(g.remove_node("b"));
+    assert!(!g.remove_node("b"));
+    assert!(g.sub_graphs[0].nodes.is_empty());
+    assert!(g.sub_graphs[0].edges.is_empty());
+    assert_eq!(1, g.edges.len());
+
+    assert_eq!(
+        Err(MutationError::NodeExists("c".to_string())),
+        g.rename_node("a", "c")
+    );
+    assert_eq!(
+        Err(MutationError::NodeNotFound("b".to_string())),
+        g.rename_node("b", "d")
+    );
+    g.rename_node("a", "x").unwrap();
+    assert_eq!(
+        r#"digraph G {
+    graph [root=x, viewport="100.0,50.0,1.0,'x'"];
+    subgraph cluster_0 {
+    }
+
+    x;
+    x -> c;
+}
+"#,
+        test_input(g).unwrap()
+    );
+}
+
+#[test]
+fn merge_nodes() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(
+                    NodeBuilder::new("b")
+                        .color(Color::Named("blue"))
+                        .shape(Shape::Box)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("a")
+                .color(Color::Named("red"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "c"))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Err(MutationError::AttributeConflict {
+            attribute: "color".to_string(),
+            first: "red".to_string(),
+            second: "blue".to_string(),
+        }),
+        g.merge_nodes("a", "b", ConflictPolicy::Error)
+    );
+    assert_eq!("b", g.edges[1].source);
+
+    g.merge_nodes("a", "b", ConflictPolicy::KeepSecond).unwrap();
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_0 {
+        a [color="blue", shape=box];
+    }
+
+    a;
+    a -> a;
+    a -> c;
+}
+"#,
+        test_input(g).unwrap()
+    );
+}
+
+#[test]
+fn move_nodes_and_remove_edges() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(NodeBuilder::new("a").shape(Shape::Box).build().unwrap())
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(SubGraphBuilder::new_named("cluster_1").build().unwrap())
+        .add_node(
+            NodeBuilder::new("a")
+                .color(Color::Named("red"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("a", "b")
+                .lhead("cluster_1".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("b", "c"))
+        .build_ignore_validation();
+
+    assert_eq!(
+        Err(MutationError::SubGraphNotFound(vec![2])),
+        g.move_node_to_sub_graph("a", &[2])
+    );
+    g.move_node_to_sub_graph("a", &[1]).unwrap();
+    assert!(g.sub_graphs[0].nodes.is_empty());
+    assert!(g.nodes.is_empty());
+    assert_eq!(Some(Shape::Box), g.sub_graphs[1].nodes[0].shape());
+    assert!(g.sub_graphs[1].nodes[0].color().is_some());
+
+    assert_eq!(
+        Err(MutationError::SubGraphExists("cluster_0".to_string())),
+        g.rename_sub_graph(&[1], "cluster_0")
+    );
+    g.rename_sub_graph(&[1], "cluster_a").unwrap();
+    assert_eq!(
+        Some("cluster_a"),
+        g.edges[0].get("lhead").map(|l| l.as_str())
+    );
+
+    assert_eq!(1, g.remove_edge_where(|edge| edge.target == "c"));
+    assert_eq!(1, g.edges.len());
+}
+
+#[test]
+fn graph_algorithms() {
+    let mut g = GraphBuilder::new_directed()
+        .add_node(Node::new("f"))
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "c"))
+        .add_edge(Edge::new("c", "a"))
+        .add_edge(Edge::new("c", "d"))
+        .add_edge(Edge::new("d", "e"))
+        .add_edge(Edge::new("g", "h"))
+        .build()
+        .unwrap();
+
+    assert_eq!(vec![vec!["a", "b", "c", "a"]], algo::cycles(&g));
+    assert!(algo::is_cyclic(&g));
+    assert_eq!(
+        vec!["a", "b", "c", "a"],
+        algo::topological_sort(&g).unwrap_err().cycle
+    );
+    assert_eq!(
+        vec![
+            vec!["g"],
+            vec!["h"],
+            vec!["a", "b", "c"],
+            vec!["d"],
+            vec!["e"],
+            vec!["f"],
+        ],
+        algo::strongly_connected_components(&g)
+    );
+    assert_eq!(
+        vec![vec!["f"], vec!["a", "b", "c", "d", "e"], vec!["g", "h"]],
+        algo::weakly_connected_components(&g)
+    );
+    assert_eq!(
+        Some(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+            "e".to_string(),
+        ]),
+        algo::shortest_path(&g, "a", "e")
+    );
+    assert_eq!(None, algo::shortest_path(&g, "e", "a"));
+    assert_eq!(vec!["c", "b", "a"], algo::ancestors(&g, "d"));
+    assert_eq!(vec!["a", "d", "b", "e"], algo::descendants(&g, "c"));
+
+    g.remove_edge_where(|edge| edge.source == "c" && edge.target == "a");
+    assert!(!algo::is_cyclic(&g));
+    assert_eq!(
+        vec!["f", "a", "b", "c", "d", "e", "g", "h"],
+        algo::topological_sort(&g).unwrap()
+    );
+}
+
+#[test]
+fn undirected_graph_algorithms() {
+    let g = GraphBuilder::new_undirected()
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("c", "b"))
+        .build()
+        .unwrap();
+
+    assert!(!algo::is_cyclic(&g));
+    assert_eq!(
+        Some(vec!["c".to_string(), "b".to_string(), "a".to_string()]),
+        algo::shortest_path(&g, "c", "a")
+    );
+    assert_eq!(
+        vec![vec!["a", "b", "c"]],
+        algo::strongly_connected_components(&g)
+    );
+}
+
+#[test]
+fn highlight_path() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(NodeBuilder::new("b").shape(Shape::Box).build().unwrap())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "c"))
+        .add_edge(Edge::new("a", "c"))
+        .build()
+        .unwrap();
+
+    let path = algo::shortest_path(&g, "a", "c").unwrap();
+    g.highlight_nodes(&["b"], Color::Named("blue"))
+        .highlight_path(&path, EdgeStyle::Bold, Color::Named("red"));
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_0 {
+        b [shape=box, color="blue"];
+    }
+
+    a [color="red"];
+    c [color="red"];
+    a -> b;
+    b -> c;
+    a -> c [style=bold, color="red"];
+}
+"#,
+        test_input(g).unwrap()
+    );
+}
+
+#[test]
+fn transitive_reduction() {
+    let mut g = GraphBuilder::new_directed()
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "c"))
+        .add_edge(Edge::new("a", "c"))
+        .add_edge(Edge::new("c", "d"))
+        .add_edge(Edge::new("a", "d"))
+        .build()
+        .unwrap();
+
+    assert_eq!(Ok(2), g.transitive_reduction());
+    assert_eq!(
+        vec![("a", "b"), ("b", "c"), ("c", "d")],
+        g.edges
+            .iter()
+            .map(|e| (e.source.as_str(), e.target.as_str()))
+            .collect::<Vec<_>>()
+    );
+
+    g.edges.push(Edge::new("d", "b"));
+    assert_eq!(
+        vec!["b", "c", "d", "b"],
+        g.transitive_reduction().unwrap_err().cycle
+    );
+    assert_eq!(4, g.edges.len());
+}
+
+#[test]
+fn collapse_parallel_edges() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_edge(
+            EdgeBuilder::new("a", "b")
+                .weight(1)
+                .label("x".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("b", "a"))
+        .add_edge(
+            EdgeBuilder::new("a", "b")
+                .weight(2)
+                .label("y".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("a", "b")
+                .label("x".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let mut strategy = EdgeMergeStrategy::new();
+    strategy
+        .attribute(AttributeName::Weight, AttributeMerge::Sum)
+        .attribute(
+            AttributeName::Label,
+            AttributeMerge::Concat("\\n".to_string()),
+        );
+    assert_eq!(2, g.collapse_parallel_edges(&strategy));
+    assert_eq!(
+        r#"digraph G {
+    a -> b [weight=3, label="x\ny"];
+    b -> a;
+}
+"#,
+        test_input(g).unwrap()
+    );
+
+    let mut g = GraphBuilder::new_undirected()
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "a"))
+        .build()
+        .unwrap();
+    assert_eq!(1, g.collapse_parallel_edges(&EdgeMergeStrategy::new()));
+}
+
+#[test]
+fn neighborhood() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_node_attributes(
+            NodeAttributeStatementBuilder::new()
+                .shape(Shape::Box)
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(Node::new("a"))
+                .add_edge(Edge::new("b", "c"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_1")
+                .add_node(Node::new("z"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("c", "d"))
+        .add_edge(Edge::new("x", "a"))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        r#"digraph G {
+    node [shape=box];
+    subgraph cluster_0 {
+        a;
+    }
+
+    a -> b;
+}
+"#,
+        test_input(g.neighborhood("a", 1, Direction::Outgoing)).unwrap()
+    );
+
+    assert_eq!(
+        r#"digraph G {
+    node [shape=box];
+    subgraph cluster_0 {
+        a;
+        b -> c;
+    }
+
+    cut_0 [label="…", shape=plaintext];
+    cut_1 [label="…", shape=plaintext];
+    a -> b;
+    c -> cut_0 [style=dashed];
+    cut_1 -> a [style=dashed];
+}
+"#,
+        test_input(
+            g.extract()
+                .placeholders(true)
+                .neighborhood("b", 1, Direction::Both)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn filter_nodes_and_induced_subgraph() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_edge(Edge::new("a", "b"))
+                .build()
+                .unwrap(),
+        )
+        .add_node(NodeBuilder::new("c").shape(Shape::Box).build().unwrap())
+        .add_edge(Edge::new("b", "c"))
+        .build()
+        .unwrap();
+
+    let filtered = g.filter_nodes(|node| !node.is_implicit() || node.id == "a");
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_0 {
+        a;
+    }
+
+    c [shape=box];
+}
+"#,
+        test_input(filtered).unwrap()
+    );
+
+    let induced = g.induced_subgraph(&["b", "c"]);
+    assert!(induced.sub_graphs.is_empty());
+    assert_eq!(1, induced.edges.len());
+    assert!(g.induced_subgraph(&["e"]).nodes.is_empty());
+}
+
+#[test]
+fn collapse_cluster() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_edge(Edge::new("a", "b"))
+                .build()
+                .unwrap(),
+        )
+        .add_node(Node::new("x"))
+        .add_edge(Edge::new("x", "a"))
+        .add_edge(Edge::new("x", "b"))
+        .build()
+        .unwrap();
+    let mut compound = Edge::new("b", "y");
+    compound
+        .attributes
+        .insert(AttributeName::LTail, AttributeText::quoted("cluster_0"));
+    g.edges.push(compound);
+
+    assert_eq!(Ok("cluster_0".to_string()), g.collapse_cluster(&[0]));
+    assert_eq!(
+        r#"digraph G {
+    x;
+    cluster_0 [label="cluster_0", shape=box3d];
+    x -> cluster_0 [label=2];
+    cluster_0 -> y;
+}
+"#,
+        test_input(g.clone()).unwrap()
+    );
+    assert_eq!(
+        Err(MutationError::SubGraphNotFound(vec![0])),
+        g.collapse_cluster(&[0])
+    );
+}
+
+#[test]
+fn collapse_cluster_keeps_edge_labels() {
+    let mut g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_node(Node::new("a"))
+                .add_node(Node::new("b"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("x", "a")
+                .label("HTTP".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("x", "b")
+                .label("HTTP".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    g.collapse_cluster(&[0]).unwrap();
+    assert_eq!(1, g.edges.len());
+    assert_eq!(
+        Some(&AttributeText::quoted("HTTP (2)")),
+        g.edges[0].attributes.get(&AttributeName::Label)
+    );
+}
+
+#[test]
+fn collapse_to_depth() {
+    let g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_sub_graph(
+                    SubGraphBuilder::new_named("cluster_1")
+                        .add_node(Node::new("a"))
+                        .build()
+                        .unwrap(),
+                )
+                .add_edge(Edge::new("b", "a"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_2")
+                .add_node(Node::new("c"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("c", "b"))
+        .build()
+        .unwrap();
+
+    let mut inner = g.clone();
+    assert_eq!(vec!["cluster_1"], inner.collapse_to_depth(1));
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_0 {
+        cluster_1 [label="cluster_1", shape=box3d];
+        b -> cluster_1;
+    }
+
+    subgraph cluster_2 {
+        c;
+    }
+
+    c -> b;
+}
+"#,
+        test_input(inner).unwrap()
+    );
+
+    let mut outer = g.clone();
+    assert_eq!(vec!["cluster_0", "cluster_2"], outer.collapse_to_depth(0));
+    assert!(outer.sub_graphs.is_empty());
+    assert_eq!(1, outer.edges.len());
+    assert_eq!("cluster_2", outer.edges[0].source);
+    assert_eq!("cluster_0", outer.edges[0].target);
+
+    assert!(g.clone().collapse_to_depth(2).is_empty());
+}
+
+#[test]
+fn cluster_by_key() {
+    let mut template = IndexMap::new();
+    template.insert(
+        AttributeName::Color,
+        AttributeText::from(Color::Named("blue")),
+    );
+    let mut filled = IndexMap::new();
+    filled.insert(
+        AttributeName::Style,
+        AttributeText::from(GraphStyle::Filled),
+    );
+
+    let g = GraphBuilder::new_named_directed("G")
+        .add_node(Node::new("a"))
+        .add_node(Node::new("b"))
+        .add_node(Node::new("c"))
+        .add_node(Node::new("d"))
+        .add_node(Node::new("e"))
+        .add_edge(Edge::new("a", "b"))
+        .cluster_template("team x", template)
+        .cluster_by(|node| match node.id.as_str() {
+            "a" => Some("team x/api".to_string()),
+            "b" => Some("/team x//web/".to_string()),
+            "d" => Some("team-x".to_string()),
+            "e" => Some("team x".to_string()),
+            _ => None,
+        })
+        .cluster_template("team-x", filled)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_team_x {
+        graph [label="team x", color="blue"];
+        subgraph cluster_team_x_api {
+            graph [label="api"];
+            a;
+        }
+
+        subgraph cluster_team_x_web {
+            graph [label="web"];
+            b;
+        }
+
+        e;
+    }
+
+    subgraph cluster_team_x_2 {
+        graph [label="team-x", style=filled];
+        d;
+    }
+
+    c;
+    a -> b;
+}
+"#,
+        test_input(g).unwrap()
+    );
+}
+
+fn diff_graphs() -> (Graph<'static>, Graph<'static>) {
+    let old = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_kept")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .label("K")
+                        .build()
+                        .unwrap(),
+                )
+                .add_node(NodeBuilder::new("a").shape(Shape::Box).build().unwrap())
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_old")
+                .add_node(Node::new("x"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("a", "x"))
+        .add_edge(Edge::new("b", "c"))
+        .build()
+        .unwrap();
+    let new = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_kept")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .label("Kept")
+                        .build()
+                        .unwrap(),
+                )
+                .add_node(NodeBuilder::new("a").shape(Shape::Circle).build().unwrap())
+                .build()
+                .unwrap(),
+        )
+        .add_node(Node::new("d"))
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(
+            EdgeBuilder::new("b", "c")
+                .color(Color::Named("blue"))
+                .build()
+                .unwrap(),
+        )
+        .add_edge(Edge::new("b", "d"))
+        .build()
+        .unwrap();
+    (old, new)
+}
+
+#[test]
+fn graph_diff() {
+    let (old, new) = diff_graphs();
+    let changes = diff(&old, &new);
+    assert_eq!(vec!["d"], changes.added_nodes);
+    assert_eq!(vec!["x"], changes.removed_nodes);
+    assert_eq!(vec!["cluster_old"], changes.removed_sub_graphs);
+    assert_eq!(
+        vec![AttributeChange {
+            name: AttributeName::Shape,
+            old: Some(AttributeText::from(Shape::Box)),
+            new: Some(AttributeText::from(Shape::Circle)),
+        }],
+        changes.changed_nodes[0].attributes
+    );
+    assert_eq!(
+        r#"- subgraph cluster_old
+~ subgraph cluster_kept label: "K" -> "Kept"
++ node d
+- node x
+~ node a shape: box -> circle
++ edge b -> d
+- edge a -> x
+~ edge b -> c color: (unset) -> "blue"
+"#,
+        changes.to_string()
+    );
+    assert!(diff(&new, &new).is_empty());
+}
+
+#[test]
+fn visual_graph_diff() {
+    let (old, new) = diff_graphs();
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_kept {
+        graph [label="Kept", color="orange"];
+        a [shape=circle, color="orange"];
+    }
+
+    subgraph cluster_old {
+        graph [color="red", style=dashed];
+        x [color="red", style=dashed];
+    }
+
+    d [color="green"];
+    a -> b;
+    b -> c [color="orange", style=bold];
+    b -> d [color="green"];
+    a -> x [color="red", style=dashed];
+}
+"#,
+        test_input(visual_diff(&old, &new)).unwrap()
+    );
+}
+
+fn merge_graphs() -> (Graph<'static>, Graph<'static>) {
+    let left = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_a")
+                .add_node(
+                    NodeBuilder::new("a")
+                        .color(Color::Named("red"))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(EdgeBuilder::new("a", "b").weight(1).build().unwrap())
+        .build()
+        .unwrap();
+    let right = GraphBuilder::new_directed()
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_a")
+                .add_node(Node::new("b"))
+                .build()
+                .unwrap(),
+        )
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_c")
+                .add_node(Node::new("c"))
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("a")
+                .color(Color::Named("blue"))
+                .shape(Shape::Box)
+                .build()
+                .unwrap(),
+        )
+        .add_edge(EdgeBuilder::new("a", "b").weight(2).build().unwrap())
+        .add_edge(Edge::new("b", "c"))
+        .build()
+        .unwrap();
+    (left, right)
+}
+
+#[test]
+fn merge_graphs_with_policies() {
+    let (mut left, right) = merge_graphs();
+    left.merge(right.clone(), MergePolicy::PreferRight).unwrap();
+    assert_eq!(
+        r#"digraph G {
+    subgraph cluster_a {
+        a [color="blue", shape=box];
+        b;
+    }
+
+    subgraph cluster_c {
+        c;
+    }
+
+    a -> b [weight=2];
+    b -> c;
+}
+"#,
+        test_input(left).unwrap()
+    );
+
+    let (mut left, right) = merge_graphs();
+    assert_eq!(
+        Err(MutationError::AttributeConflict {
+            attribute: "color".to_string(),
+            first: "red".to_string(),
+            second: "blue".to_string(),
+        }),
+        left.merge(right.clone(), MergePolicy::Error)
+    );
+    assert_eq!(1, left.edges.len());
+    assert_eq!(1, left.sub_graphs.len());
+
+    let policy = MergePolicy::custom(|name, left, right| {
+        if *name == AttributeName::Weight {
+            let weight = |text: &AttributeText| text.as_str().parse::<u32>().unwrap();
+            AttributeText::from(weight(left) + weight(right))
+        } else {
+            left.clone()
+        }
+    });
+    left.merge(right, policy).unwrap();
+    assert_eq!(
+        Some(&AttributeText::from(3)),
+        left.edges[0].attributes.get(&AttributeName::Weight)
+    );
+    assert_eq!(
+        Some(&AttributeText::quoted("red")),
+        left.sub_graphs[0].nodes[0]
+            .attributes
+            .get(&AttributeName::Color)
+    );
+
+    assert_eq!(
+        Err(MutationError::MixedDirectedness),
+        left.merge(
+            GraphBuilder::new_undirected().build().unwrap(),
+            MergePolicy::PreferLeft
+        )
+    );
+}
+
+#[test]
+fn merge_strict_graphs() {
+    let mut left = GraphBuilder::new_directed()
+        .strict()
+        .add_edge(Edge::new("a", "b"))
+        .build()
+        .unwrap();
+    let right = GraphBuilder::new_directed()
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("a", "b"))
+        .add_edge(Edge::new("b", "a"))
+        .build()
+        .unwrap();
+    left.merge(right, MergePolicy::PreferLeft).unwrap();
+    assert!(left.strict);
+    assert_eq!(2, left.edges.len());
+}
+
+#[test]
+fn theme_with_classes() {
+    let mut theme = Theme::new();
+    theme
+        .add_node_attributes(
+            NodeAttributeStatementBuilder::new()
+                .font_name("Helvetica".to_string())
+                .build()
+                .unwrap(),
+        )
+        .add_class(
+            "database",
+            NodeAttributeStatementBuilder::new()
+                .shape(Shape::Cylinder)
+                .color(Color::Named("blue"))
+                .build()
+                .unwrap(),
+        )
+        .add_class(
+            "primary",
+            NodeAttributeStatementBuilder::new()
+                .color(Color::Named("red"))
+                .build()
+                .unwrap(),
+        );
+
+    let g = GraphBuilder::new_named_directed("G")
+        .with_theme(&theme)
+        .add_node(
+            NodeBuilder::new("users")
+                .with_class("database")
+                .with_class("primary")
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("orders")
+                .shape(Shape::Box)
+                .with_class("database")
+                .build()
+                .unwrap(),
+        )
+        .add_node(
+            NodeBuilder::new("api")
+                .with_class("unknown")
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(r#"digraph G {
+    node [fontname="Helvetica"];
+    users [class="database primary", shape=cylinder, color="red"];
+    orders [shape=box, class="database", color="blue"];
+    api [class="unknown"];
+}
+"#, test_input(g).unwrap());
+    assert!(theme.class("missing").build().unwrap().is_empty());
+}
+
+#[test]
+fn built_in_themes() {
+    for theme in &[Theme::light(), Theme::dark(), Theme::monochrome()] {
+        assert!(theme.classes.contains_key("database"));
+        let g = GraphBuilder::new_directed()
+            .with_theme(theme)
+            .add_node(
+                NodeBuilder::new("db")
+                    .with_class("database")
+                    .build()
+                    .unwrap(),
+            )
+            .add_edge(Edge::new("db", "api"))
+            .build()
+            .unwrap();
+        assert_eq!(Some(Shape::Cylinder), g.nodes[0].shape());
+    }
+    assert_eq!(
+        r##"digraph {
+    graph [bgcolor="#1e1e1e", fontcolor="#eeeeee", fontname="Helvetica"];
+    node [shape=box, style="rounded,filled", color="#b0b0b0", fillcolor="#2d2d2d", fontcolor="#eeeeee", fontname="Helvetica"];
+    edge [color="#b0b0b0", fontcolor="#eeeeee", fontname="Helvetica"];
+}
+"##,
+        test_input(
+            GraphBuilder::new_directed()
+                .with_theme(&Theme::dark())
+                .build()
+                .unwrap()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn stylesheet_classes() {
+    let mut stylesheet = Stylesheet::new();
+    stylesheet
+        .rule("database", "fill", "#dbe9f6")
+        .rule("database", "stroke", "navy")
+        .rule("team", "stroke-dasharray", "4 2")
+        .rule("legacy", "opacity", "0.5");
+
+    let g = GraphBuilder::new_named_directed("G")
+        .add_sub_graph(
+            SubGraphBuilder::new_named("cluster_0")
+                .add_graph_attributes(
+                    GraphAttributeStatementBuilder::new()
+                        .class("team".to_string())
+                        .build()
+                        .unwrap(),
+                )
+                .add_node(
+                    NodeBuilder::new("users")
+                        .class("database primary".to_string())
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .add_edge(
+            EdgeBuilder::new("api", "users")
+                .class("primary".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let used = used_classes(&g);
+    assert_eq!(
+        vec!["team", "database", "primary"],
+        used.keys().collect::<Vec<_>>()
+    );
+    assert_eq!(2, used["primary"].len());
+
+    let diagnostics = Linter::new().add_lint(stylesheet.clone()).lint(&g);
+    let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+    assert_eq!(
+        vec![
+            "warning[stylesheet-classes]: graph G > subgraph cluster_0 > node users: class = \"primary\": Class primary has no stylesheet rule",
+            "warning[stylesheet-classes]: graph G > edge 0 (api -> users): class = \"primary\": Class primary has no stylesheet rule",
+            "warning[stylesheet-classes]: graph G: Stylesheet rule for class legacy is unused",
+        ],
+        messages
+    );
+
+    assert_eq!(
+        r#".database {
+    fill: #dbe9f6;
+    stroke: navy;
+}
+
+.team {
+    stroke-dasharray: 4 2;
+}
+
+.legacy {
+    opacity: 0.5;
+}
+"#,
+        stylesheet.to_css()
+    );
+    assert!(stylesheet
+        .to_style_element()
+        .starts_with("<style type=\"text/css\"><![CDATA[\n.database {\n"));
+}
+
+#[test]
+fn dot_macro_graph() {
+    let color = Color::Named("blue");
+    let weight = 3;
+    let g = dot! {
+        strict digraph "my graph" {
+            graph [rankdir = LR, splines = ortho];
+            edge [color = (color)];
+            a [label = "Start", shape = box];
+            a -> b -> (format!("n{}", 1)) [weight = (weight)];
+            subgraph cluster_x {
+                label = "X";
+                c
+                { rank = same; d; e }
+            }
+            c -> 2
+        }
+    }
+    .unwrap();
+
+    assert_eq!(test_input(g).unwrap(), r#"strict digraph "my graph" {
+    graph [rankdir=LR, splines=ortho];
+    edge [color="blue"];
+    subgraph cluster_x {
+        graph [label=X];
+        subgraph {
+            graph [rank=same];
+            d;
+            e;
+        }
+
+        c;
+    }
+
+    a [label=Start, shape=box];
+    a -> b [weight=3];
+    b -> n1 [weight=3];
+    c -> 2;
+}
+"#);
+}
+
+#[test]
+fn dot_macro_undirected_graph() {
+    let g = dot! {
+        graph {
+            a -- b [penwidth = 1.5]
+        }
+    }
+    .unwrap();
+
+    assert_eq!(test_input(g).unwrap(), r#"graph {
+    a -- b [penwidth=1.5];
+}
+"#);
+}
+
+#[test]
+fn dot_macro_collects_validation_errors() {
+    let errors = dot! {
+        digraph {
+            a [rankdir = LR];
+            a -> b [penwidth = (-1)];
+            subgraph cluster_x { shape = box }
+        }
+    }
+    .unwrap_err();
+
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_ref()).collect();
+    assert_eq!(vec!["rankdir", "penwidth", "shape"], fields);
+    assert_eq!(ErrorCode::InvalidContext, errors[0].code);
+    assert_eq!(ErrorCode::OutOfRange, errors[1].code);
+}
+
+#[test]
+fn parse_graph() {
+    let g = parse(
+        r#"// Services
+strict digraph "my graph" {
+    rankdir=LR
+    node [shape=box] [color="grey"];
+    subgraph cluster_db { label="Storage"; users; orders }
+    api -> {users orders} [weight=2];
+    web -> api:n -> auth:p1:sw
+    /* a comment */
+    x [label=<<b>X</b>>, tooltip="say \"hi\"" + " there"];
+}"#,
+    )
+    .unwrap();
+
+    assert_eq!(Some("Services".to_string()), g.comment);
+    assert_eq!(
+        test_input(g).unwrap(),
+        r#"// Services
+strict digraph "my graph" {
+    graph [rankdir=LR];
+    node [shape=box, color="grey"];
+    subgraph cluster_db {
+        graph [label="Storage"];
+        users;
+        orders;
+    }
+
+    subgraph {
+        users;
+        orders;
+    }
+
+    x [label=<<b>X</b>>, tooltip="say \"hi\" there"];
+    api -> users [weight=2];
+    api -> orders [weight=2];
+    web -> api:n;
+    api:n -> auth:p1:sw;
+}
+"#
+    );
+}
+
+#[test]
+fn parse_undirected_graph() {
+    let g = parse("graph { a -- b -- c; d }").unwrap();
+
+    assert!(!g.is_directed);
+    assert_eq!(2, g.edges.len());
+    assert_eq!("d", g.nodes[0].id.as_str());
+}
+
+#[test]
+fn parse_formatted_output_is_stable() {
+    let source =
+        "digraph { subgraph cluster_a { a; b } a -> b [label=\"x y\"]; b -> c:n }";
+    let formatted = test_input(parse(source).unwrap()).unwrap();
+
+    assert_eq!(formatted, test_input(parse(&formatted).unwrap()).unwrap());
+}
+
+#[test]
+fn parse_round_trip_keeps_defaults_and_comments() {
+    let round_trip = |source: &str| {
+        let document = parse_document(source).unwrap();
+        let formatted = test_input(document.graph.clone()).unwrap();
+        let reformatted = parse_document(&formatted).unwrap();
+        (document, reformatted)
+    };
+
+    let (document, reformatted) =
+        round_trip("// title\ndigraph { node [shape=box]; a -> b; subgraph s { c } }");
+    assert_eq!(0, document.dropped_comments);
+    assert!(document.same_attributes(&reformatted));
+
+    for source in &[
+        "digraph { a; node [shape=box]; b; node [color=red]; c }",
+        "digraph { node [color=red]; x; node [color=blue]; y }",
+        "digraph { a -> b; edge [color=red]; subgraph s { c -> d } }",
+        "digraph { subgraph s { a } label=x }",
+        "digraph { a [color=red]; subgraph s { a [color=blue] } }",
+    ] {
+        let (document, reformatted) = round_trip(source);
+        assert!(!document.same_attributes(&reformatted), "{}", source);
+    }
+
+    let (document, _) = round_trip("// a\n// b\ndigraph { a /* c */ -> b\n# d\n}");
+    assert_eq!(4, document.dropped_comments);
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse("digraph {\n    a -- b\n}").unwrap_err();
+    assert_eq!((2, 7), (error.line, error.column));
+    assert_eq!("Undirected edge in a directed graph", error.message);
+
+    let error = parse("digraph { a -> }").unwrap_err();
+    assert_eq!("1:16: Expected an id, found \"}\"", error.to_string());
+
+    let error = parse("graph { a [label=\"b }").unwrap_err();
+    assert_eq!("Unterminated string", error.message);
+
+    assert!(parse("digraph { a } b").is_err());
+}
+
+#[test]
+fn validate_parsed_attributes() {
+    let g =
+        parse("digraph { rankdir=LR; a [rankdir=TB, penwidth=-1, colr=red] }").unwrap();
+    let errors = validate_attributes(&g);
+
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_ref()).collect();
+    assert_eq!(vec!["rankdir", "penwidth", "colr"], fields);
+    assert_eq!(ErrorCode::InvalidContext, errors[0].code);
+    assert_eq!("graph > node a", errors[0].path.to_string());
+}